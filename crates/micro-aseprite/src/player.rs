@@ -19,6 +19,7 @@ pub struct AnimationPlayer {
 	pub transform: Mat4,
 	pub color: LinSrgba,
 	pub blend_mode: BlendMode,
+	pub palette: Option<Texture>,
 }
 
 impl AnimationPlayer {
@@ -40,6 +41,7 @@ impl AnimationPlayer {
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::default(),
+			palette: None,
 		}
 	}
 
@@ -114,6 +116,7 @@ impl AnimationPlayer {
 			.transformed(self.transform)
 			.color(self.color)
 			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
 			.draw(ctx);
 	}
 }
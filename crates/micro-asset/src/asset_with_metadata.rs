@@ -155,8 +155,17 @@ impl<L: AssetLoader> AssetWithMetadata<L> {
 	}
 }
 
+/// Returns when the file at `path` was last modified. For directories,
+/// this is the latest modified time of the directory or anything in it,
+/// so editing a file in a directory asset counts as modifying the asset.
 fn file_modified_time(path: &Path) -> std::io::Result<SystemTime> {
-	std::fs::metadata(path)?.modified()
+	let mut modified_time = std::fs::metadata(path)?.modified()?;
+	if path.is_dir() {
+		for entry in std::fs::read_dir(path)? {
+			modified_time = modified_time.max(file_modified_time(&entry?.path())?);
+		}
+	}
+	Ok(modified_time)
 }
 
 #[derive(Debug, Error, Display, From)]
@@ -1,5 +1,7 @@
 mod asset_with_metadata;
 mod loader;
+#[cfg(test)]
+mod test;
 
 pub use loader::*;
 
@@ -1,8 +1,16 @@
+mod bitmap_font;
+mod particle_emitter_settings;
 mod shader;
+mod svg;
 mod texture;
+mod texture_atlas;
 
+pub use bitmap_font::*;
+pub use particle_emitter_settings::*;
 pub use shader::*;
+pub use svg::*;
 pub use texture::*;
+pub use texture_atlas::*;
 
 use serde::Deserialize;
 
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use micro::{
+	Context,
+	graphics::{
+		text::{BitmapFont, LoadBitmapFontError},
+		texture::TextureSettings,
+	},
+};
+
+use super::AssetLoader;
+
+/// Loads [`BitmapFont`]s from BMFont `.fnt` files in the text format.
+///
+/// The settings are used for the page textures.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BitmapFontLoader {
+	pub default_settings: TextureSettings,
+}
+
+impl AssetLoader for BitmapFontLoader {
+	type Asset = BitmapFont;
+
+	type Error = LoadBitmapFontError;
+
+	type Settings = TextureSettings;
+
+	type Context = Context;
+
+	const SUPPORTED_FILE_EXTENSIONS: &'static [&'static str] = &["fnt"];
+
+	fn load(
+		&mut self,
+		ctx: &mut Context,
+		path: &Path,
+		settings: Option<&Self::Settings>,
+	) -> Result<Self::Asset, Self::Error> {
+		BitmapFont::from_bmfont_file(
+			ctx,
+			path,
+			settings.unwrap_or(&self.default_settings).clone(),
+		)
+	}
+}
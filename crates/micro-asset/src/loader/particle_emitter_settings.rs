@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use derive_more::{Display, Error, From};
+use micro::graphics::particles::ParticleEmitterSettings;
+
+use super::AssetLoader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParticleEmitterSettingsLoader;
+
+impl AssetLoader for ParticleEmitterSettingsLoader {
+	type Asset = ParticleEmitterSettings;
+
+	type Error = LoadParticleEmitterSettingsError;
+
+	type Settings = ();
+
+	type Context = ();
+
+	const SUPPORTED_FILE_EXTENSIONS: &'static [&'static str] = &["json"];
+
+	fn load(
+		&mut self,
+		_ctx: &mut (),
+		path: &Path,
+		_settings: Option<&Self::Settings>,
+	) -> Result<Self::Asset, Self::Error> {
+		let json = std::fs::read_to_string(path)?;
+		Ok(serde_json::from_str(&json)?)
+	}
+}
+
+#[derive(Debug, Display, Error, From)]
+pub enum LoadParticleEmitterSettingsError {
+	IoError(std::io::Error),
+	JsonError(serde_json::Error),
+}
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use micro::{
+	Context,
+	graphics::mesh::{LoadSvgError, Mesh, SvgSettings},
+};
+
+use super::AssetLoader;
+
+/// Loads SVG files into [`Mesh`]es.
+///
+/// The shapes are tessellated when the file is loaded, so use a lower
+/// tolerance in the settings for SVGs that will be drawn scaled up.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SvgLoader {
+	pub default_settings: SvgSettings,
+}
+
+impl AssetLoader for SvgLoader {
+	type Asset = Mesh;
+
+	type Error = LoadSvgError;
+
+	type Settings = SvgSettings;
+
+	type Context = Context;
+
+	const SUPPORTED_FILE_EXTENSIONS: &'static [&'static str] = &["svg"];
+
+	fn load(
+		&mut self,
+		ctx: &mut Context,
+		path: &Path,
+		settings: Option<&Self::Settings>,
+	) -> Result<Self::Asset, Self::Error> {
+		Mesh::from_svg_file(ctx, path, settings.unwrap_or(&self.default_settings))
+	}
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use micro::{
+	Context,
+	graphics::texture_atlas::{LoadTextureAtlasError, TextureAtlas, TextureAtlasSettings},
+};
+
+use super::AssetLoader;
+
+/// Packs a directory of images into a [`TextureAtlas`].
+///
+/// The atlas is loaded from a directory with the `.atlas` extension,
+/// such as `characters.atlas/`, and is available under the directory's
+/// name without the extension (`characters`). Adding, removing, or
+/// editing an image in the directory will cause the atlas to be rebuilt
+/// when hot reloading.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextureAtlasLoader {
+	pub default_settings: TextureAtlasSettings,
+}
+
+impl AssetLoader for TextureAtlasLoader {
+	type Asset = TextureAtlas;
+
+	type Error = LoadTextureAtlasError;
+
+	type Settings = TextureAtlasSettings;
+
+	type Context = Context;
+
+	const SUPPORTED_FILE_EXTENSIONS: &'static [&'static str] = &["atlas"];
+
+	fn load(
+		&mut self,
+		ctx: &mut Context,
+		path: &Path,
+		settings: Option<&Self::Settings>,
+	) -> Result<Self::Asset, Self::Error> {
+		TextureAtlas::from_dir(
+			ctx,
+			path,
+			settings.unwrap_or(&self.default_settings).clone(),
+		)
+	}
+}
@@ -0,0 +1,83 @@
+use std::{
+	fs::File,
+	path::{Path, PathBuf},
+	time::{Duration, SystemTime},
+};
+
+use crate::{AssetLoader, Assets};
+
+/// Loads a directory with the `.atlas` extension the same way
+/// [`TextureAtlasLoader`](crate::TextureAtlasLoader) does, but returns
+/// the names of the images in it instead of packing them, since that
+/// requires a graphics context.
+struct AtlasDirLoader;
+
+impl AssetLoader for AtlasDirLoader {
+	type Asset = Vec<String>;
+
+	type Error = std::io::Error;
+
+	type Settings = ();
+
+	type Context = ();
+
+	const SUPPORTED_FILE_EXTENSIONS: &'static [&'static str] = &["atlas"];
+
+	fn load(
+		&mut self,
+		_ctx: &mut (),
+		path: &Path,
+		_settings: Option<&Self::Settings>,
+	) -> Result<Self::Asset, Self::Error> {
+		let mut names = std::fs::read_dir(path)?
+			.map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+			.collect::<std::io::Result<Vec<_>>>()?;
+		names.sort();
+		Ok(names)
+	}
+}
+
+fn test_dir(name: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("micro-asset-test-{}-{name}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::create_dir_all(&dir).unwrap();
+	dir
+}
+
+#[test]
+fn loads_atlas_directories() {
+	let dir = test_dir("loads_atlas_directories");
+	std::fs::create_dir_all(dir.join("sprites/player.atlas")).unwrap();
+	File::create(dir.join("sprites/player.atlas/idle.png")).unwrap();
+	File::create(dir.join("sprites/player.atlas/run.png")).unwrap();
+	let assets = Assets::autoloaded(&mut (), &dir, AtlasDirLoader);
+	assert_eq!(
+		assets.paths().collect::<Vec<_>>(),
+		[Path::new("sprites/player")]
+	);
+	assert_eq!(
+		assets.get("sprites/player").unwrap(),
+		&["idle.png", "run.png"]
+	);
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hot_reloads_when_an_image_in_an_atlas_changes() {
+	let dir = test_dir("hot_reloads_when_an_image_in_an_atlas_changes");
+	std::fs::create_dir_all(dir.join("player.atlas")).unwrap();
+	let image = File::create(dir.join("player.atlas/idle.png")).unwrap();
+	image
+		.set_modified(SystemTime::now() - Duration::from_secs(60))
+		.unwrap();
+	let mut assets = Assets::autoloaded(&mut (), &dir, AtlasDirLoader);
+	assert_eq!(assets.get("player").unwrap(), &["idle.png"]);
+	assets.get_mut("player").unwrap().clear();
+	// not modified, so the asset isn't reloaded
+	assets.update_hot_reload(&mut (), Duration::from_secs(1));
+	assert!(assets.get("player").unwrap().is_empty());
+	image.set_modified(SystemTime::now()).unwrap();
+	assets.update_hot_reload(&mut (), Duration::from_secs(1));
+	assert_eq!(assets.get("player").unwrap(), &["idle.png"]);
+	std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -8,6 +8,7 @@ mod macros;
 mod manually_positioned;
 mod mask;
 mod mouse_input;
+mod nine_slice;
 mod padding;
 mod polygon;
 mod polyline;
@@ -27,6 +28,7 @@ pub use image::*;
 pub use inspector::*;
 pub use manually_positioned::*;
 pub use mask::*;
+pub use nine_slice::*;
 pub use padding::*;
 pub use polygon::*;
 pub use polyline::*;
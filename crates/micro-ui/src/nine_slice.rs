@@ -0,0 +1,125 @@
+use micro::{
+	Context,
+	color::LinSrgba,
+	egui::{self, color_preview},
+	graphics::{NineSliceInsets, NineSliceMode, texture::Texture},
+	math::Vec2,
+};
+
+use crate::{
+	WidgetInspector, WidgetState, child_functions, common_functions, common_widget_trait_functions,
+	sizing_functions,
+};
+
+use super::{LayoutResult, Sizing, Widget};
+
+#[derive(Debug)]
+pub struct NineSlice {
+	id: Option<String>,
+	inspector: Option<WidgetInspector>,
+	sizing: Sizing,
+	nine_slice: micro::graphics::NineSlice,
+	children: Vec<Box<dyn Widget>>,
+}
+
+impl NineSlice {
+	pub fn new(texture: &Texture, insets: impl Into<NineSliceInsets>) -> Self {
+		Self {
+			id: None,
+			inspector: None,
+			sizing: Sizing::EXPAND,
+			nine_slice: micro::graphics::NineSlice::new(texture, insets),
+			children: vec![],
+		}
+	}
+
+	pub fn color(self, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			nine_slice: self.nine_slice.color(color),
+			..self
+		}
+	}
+
+	pub fn edge_mode(self, edge_mode: NineSliceMode) -> Self {
+		Self {
+			nine_slice: self.nine_slice.edge_mode(edge_mode),
+			..self
+		}
+	}
+
+	pub fn center_mode(self, center_mode: NineSliceMode) -> Self {
+		Self {
+			nine_slice: self.nine_slice.center_mode(center_mode),
+			..self
+		}
+	}
+
+	common_functions!();
+	child_functions!();
+	sizing_functions!();
+}
+
+impl Widget for NineSlice {
+	common_widget_trait_functions!();
+
+	fn name(&self) -> &'static str {
+		"nine slice"
+	}
+
+	fn children(&mut self, _ctx: &mut Context, _state: &mut WidgetState) -> Vec<Box<dyn Widget>> {
+		self.children.drain(..).collect()
+	}
+
+	fn allotted_size_for_next_child(
+		&mut self,
+		_ctx: &mut Context,
+		allotted_size_from_parent: Vec2,
+		_previous_child_sizes: &[Vec2],
+		_state: &mut WidgetState,
+	) -> Vec2 {
+		let _span = tracy_client::span!();
+		self.sizing
+			.allotted_size_for_children(allotted_size_from_parent)
+	}
+
+	fn layout(
+		&mut self,
+		_ctx: &mut Context,
+		allotted_size_from_parent: Vec2,
+		child_sizes: &[Vec2],
+		_state: &mut WidgetState,
+	) -> LayoutResult {
+		let _span = tracy_client::span!();
+		LayoutResult {
+			size: self
+				.sizing
+				.final_parent_size(allotted_size_from_parent, child_sizes.iter().copied()),
+			child_positions: std::iter::repeat_n(Vec2::ZERO, child_sizes.len()).collect(),
+		}
+	}
+
+	fn draw_before_children(&mut self, ctx: &mut Context, size: Vec2, _state: &mut WidgetState) {
+		let _span = tracy_client::span!();
+		self.nine_slice.size(size).draw(ctx);
+	}
+
+	fn debug_info(&self, egui_ui: &mut egui::Ui, _state: &WidgetState) {
+		self.sizing.debug_info(egui_ui);
+		egui_ui.horizontal(|ui| {
+			ui.label("Insets:");
+			ui.monospace(format!("{:?}", self.nine_slice.insets));
+		});
+		egui_ui.horizontal(|ui| {
+			ui.label("Edge mode:");
+			ui.monospace(format!("{:?}", self.nine_slice.edge_mode));
+		});
+		egui_ui.horizontal(|ui| {
+			ui.label("Center mode:");
+			ui.monospace(format!("{:?}", self.nine_slice.center_mode));
+		});
+		egui_ui.horizontal(|ui| {
+			ui.label("Color:");
+			color_preview(ui, self.nine_slice.color);
+		});
+	}
+}
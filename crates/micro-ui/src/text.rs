@@ -5,7 +5,8 @@ use micro::{
 	color::{ColorConstants, LinSrgba},
 	egui::{self, color_preview},
 	graphics::text::{
-		Text, TextAlign, TextBuilder, TextHorizontalSizing, TextStretch, TextStyle, TextWeight,
+		self, Text, TextAlign, TextBuilder, TextEffects, TextHorizontalSizing, TextStretch,
+		TextStyle, TextWeight,
 	},
 	math::Vec2,
 };
@@ -25,7 +26,6 @@ pub struct TextWidget {
 	sizing: Sizing,
 	align: TextAlign,
 	color: LinSrgba,
-	shadow: Option<TextShadow>,
 	size_reporting: TextSizeReporting,
 	built: RefCell<Option<Text>>,
 }
@@ -39,7 +39,6 @@ impl TextWidget {
 			sizing: Sizing::SHRINK,
 			align: TextAlign::Left,
 			color: LinSrgba::WHITE,
-			shadow: None,
 			size_reporting: TextSizeReporting::default(),
 			built: RefCell::new(None),
 		}
@@ -105,6 +104,13 @@ impl TextWidget {
 		}
 	}
 
+	pub fn sdf(self, sdf: bool) -> Self {
+		Self {
+			builder: self.builder.sdf(sdf),
+			..self
+		}
+	}
+
 	pub fn color(self, color: impl Into<LinSrgba>) -> Self {
 		Self {
 			color: color.into(),
@@ -112,9 +118,35 @@ impl TextWidget {
 		}
 	}
 
-	pub fn shadow(self, shadow: impl Into<Option<TextShadow>>) -> Self {
+	pub fn shadow(mut self, shadow: impl Into<Option<TextShadow>>) -> Self {
+		self.builder.effects.shadow =
+			shadow
+				.into()
+				.map(|TextShadow { color, offset }| text::TextShadow {
+					offset,
+					color,
+					softness: 0.0,
+				});
+		self
+	}
+
+	pub fn outline(self, width: f32, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			builder: self.builder.outline(width, color),
+			..self
+		}
+	}
+
+	pub fn glow(self, radius: f32, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			builder: self.builder.glow(radius, color),
+			..self
+		}
+	}
+
+	pub fn effects(self, effects: TextEffects) -> Self {
 		Self {
-			shadow: shadow.into(),
+			builder: self.builder.effects(effects),
 			..self
 		}
 	}
@@ -191,22 +223,7 @@ impl Widget for TextWidget {
 			TextSizeReporting::Glyph => built.glyph_bounds(),
 		};
 		let position = bounds.map(|bounds| -bounds.top_left).unwrap_or_default();
-		if let Some(TextShadow { color, offset }) = self.shadow {
-			self.built
-				.borrow()
-				.as_ref()
-				.unwrap()
-				.translated_2d(position + offset)
-				.color(color)
-				.draw(ctx);
-		}
-		self.built
-			.borrow()
-			.as_ref()
-			.unwrap()
-			.translated_2d(position)
-			.color(self.color)
-			.draw(ctx);
+		built.translated_2d(position).color(self.color).draw(ctx);
 	}
 
 	fn debug_info(&self, egui_ui: &mut egui::Ui, _state: &WidgetState) {
@@ -247,17 +264,35 @@ impl Widget for TextWidget {
 			ui.label("Letter spacing:");
 			ui.monospace(format!("{:?}", self.builder.letter_spacing));
 		});
+		egui_ui.horizontal(|ui| {
+			ui.label("SDF:");
+			ui.monospace(format!("{}", self.builder.sdf));
+		});
 		egui_ui.horizontal(|ui| {
 			ui.label("Color:");
 			color_preview(ui, self.color);
 		});
+		egui_ui.horizontal(|ui| {
+			ui.label("Outline:");
+			if let Some(outline) = self.builder.effects.outline {
+				ui.monospace(format!("{}", outline.width));
+				color_preview(ui, outline.color);
+			}
+		});
 		egui_ui.horizontal(|ui| {
 			ui.label("Shadow:");
-			if let Some(shadow) = self.shadow {
+			if let Some(shadow) = self.builder.effects.shadow {
 				ui.monospace(format!("{}", shadow.offset));
 				color_preview(ui, shadow.color);
 			}
 		});
+		egui_ui.horizontal(|ui| {
+			ui.label("Glow:");
+			if let Some(glow) = self.builder.effects.glow {
+				ui.monospace(format!("{}", glow.radius));
+				color_preview(ui, glow.color);
+			}
+		});
 		egui_ui.horizontal(|ui| {
 			ui.label("Size reporting:");
 			ui.monospace(format!("{:?}", self.size_reporting));
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 
 use crate::{WidgetInspector, WidgetState, mouse_input::MouseInput};
 
@@ -7,7 +7,7 @@ use itertools::izip;
 use micro::{
 	Context,
 	color::{LinSrgb, LinSrgba},
-	graphics::{CompareFunction, StencilOperation, StencilState, mesh::Mesh},
+	graphics::mesh::Mesh,
 	input::MouseButton,
 	math::{Mat4, Rect, Vec2},
 };
@@ -215,15 +215,15 @@ impl BakedWidget {
 	fn draw(&mut self, ctx: &mut Context, widget_state: &mut IndexMap<String, WidgetState>) {
 		let _span = tracy_client::span!();
 		let ctx = &mut ctx.push(self.transform);
-		if let Some(baked_mask) = &mut self.mask {
-			{
-				let ctx = &mut ctx.push(StencilState::write(StencilOperation::Replace, 1));
-				baked_mask.draw(ctx, widget_state);
-			}
-			{
-				let ctx = &mut ctx.push(StencilState::read(CompareFunction::Equal, 1));
-				self.draw_non_mask_contents(ctx, widget_state);
-			}
+		if let Some(mut baked_mask) = self.mask.take() {
+			// both closures need the widget state, but only one of them
+			// runs at a time
+			let widget_state = RefCell::new(widget_state);
+			ctx.with_mask(
+				|ctx| baked_mask.draw(ctx, &mut widget_state.borrow_mut()),
+				|ctx| self.draw_non_mask_contents(ctx, &mut widget_state.borrow_mut()),
+			);
+			self.mask = Some(baked_mask);
 		} else {
 			self.draw_non_mask_contents(ctx, widget_state);
 		}
@@ -9,8 +9,8 @@ use std::{
 	collections::HashMap,
 	fmt::Debug,
 	ops::{Deref, DerefMut},
-	path::Path,
-	time::{Duration, Instant},
+	path::{Path, PathBuf},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use egui::{Align, Layout, Panel};
@@ -27,8 +27,13 @@ use crate::{
 	color::ColorConstants,
 	context::graphics::GraphicsContext,
 	egui_integration::{draw_egui_output, egui_raw_input, egui_took_sdl3_event, try_run_ui},
-	graphics::{Canvas, CanvasSettings, IntoScale2d, IntoScale3d, RenderToCanvasSettings},
+	graphics::{
+		Canvas, CanvasSettings, CompareFunction, GpuTimings, IntoScale2d, IntoScale3d,
+		MaskSettings, RenderToCanvasSettings, StencilOperation, StencilState, mesh::Mesh,
+		text::GlyphAtlasStats,
+	},
 	input::{Gamepad, GamepadId, MouseButton, Scancode},
+	math::{Rect, URect},
 	text::TextContext,
 };
 
@@ -118,6 +123,14 @@ where
 					.show(ui, |ui| -> anyhow::Result<()> {
 						egui::MenuBar::new()
 							.ui(ui, |ui| -> anyhow::Result<()> {
+								ui.menu_button("Glyph Atlas", |ui| {
+									ctx.glyph_atlas_stats().ui(ui);
+								});
+								if let Some(gpu_timings) = ctx.gpu_timings() {
+									ui.menu_button("GPU Timings", |ui| {
+										gpu_timings.ui(ui);
+									});
+								}
 								app.debug_menu(&mut ctx, ui)?;
 								ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
 									if let Some(stats) = app.debug_stats(&mut ctx) {
@@ -165,6 +178,17 @@ where
 						*visible = !*visible;
 					}
 				}
+				Event::KeyPressed {
+					key: Scancode::F2, ..
+				} => {
+					if let DevToolsState::Enabled { .. } = ctx.dev_tools_state {
+						let timestamp = SystemTime::now()
+							.duration_since(UNIX_EPOCH)
+							.unwrap_or_default()
+							.as_millis();
+						ctx.capture_screenshot(format!("screenshot-{timestamp}.png"));
+					}
+				}
 				_ => {}
 			}
 			app.event(
@@ -204,6 +228,7 @@ where
 		draw_egui_output(&mut ctx, &egui_ctx, egui_output, &mut egui_textures);
 		drop(span);
 		ctx.graphics.present();
+		ctx.text.end_frame();
 
 		app.post_draw(&mut ctx)?;
 
@@ -360,7 +385,73 @@ impl Context {
 	/// restore the previous set of graphics settings.
 	pub fn push(&mut self, push: impl Into<Push>) -> OnDrop<'_> {
 		self.graphics.push_graphics_state(push.into());
-		OnDrop { ctx: self }
+		OnDrop {
+			ctx: self,
+			mask_depth_to_restore: None,
+		}
+	}
+
+	/// Pushes a rectangular region to crop all drawing operations to.
+	///
+	/// Unlike [`Push::scissor_rect`], the region is given in the
+	/// coordinates of the current transform, and it's intersected with
+	/// the region of any scissor that's already active. If the current
+	/// transform is rotated, the region is cropped using the stencil
+	/// buffer, like [`with_mask`](Self::with_mask).
+	pub fn push_scissor(&mut self, rect: Rect) -> OnDrop<'_> {
+		let _span = tracy_client::span!();
+		let transform = self.graphics.current_transform();
+		let corners = rect
+			.corners()
+			.map(|corner| transform.project_point3(corner.extend(0.0)).truncate());
+		let render_target_size = self.current_render_target_size();
+		let to_pixel = |point: Vec2| {
+			point
+				.round()
+				.clamp(Vec2::ZERO, render_target_size.as_vec2())
+				.as_uvec2()
+		};
+		let bounds = URect::from_corners(
+			to_pixel(corners.into_iter().reduce(Vec2::min).unwrap()),
+			to_pixel(corners.into_iter().reduce(Vec2::max).unwrap()),
+		);
+		let parent_scissor_rect = self
+			.graphics
+			.current_scissor_rect()
+			.unwrap_or(URect::new(UVec2::ZERO, render_target_size));
+		let scissor_rect = bounds
+			.intersection(parent_scissor_rect)
+			.unwrap_or(URect::new(parent_scissor_rect.top_left, UVec2::ZERO));
+		if is_axis_aligned(transform) {
+			return self.push(Push {
+				scissor_rect: Some(Some(scissor_rect)),
+				..Default::default()
+			});
+		}
+		// the scissor rect crops to the bounding box of the region, and
+		// the stencil buffer crops to the exact shape
+		let depth = *self.graphics.mask_depth_mut();
+		let inner_depth = depth.checked_add(1).expect("too many nested masks");
+		{
+			let shader = self.graphics.default_resources.default_shader.clone();
+			let ctx = &mut self.push(Push {
+				shader: Some(shader),
+				stencil_state: Some(mask_stencil_state(depth, StencilOperation::IncrementClamp)),
+				scissor_rect: Some(Some(scissor_rect)),
+				..Default::default()
+			});
+			Mesh::rectangle(ctx, rect).draw(ctx);
+		}
+		*self.graphics.mask_depth_mut() = inner_depth;
+		self.graphics.push_graphics_state(Push {
+			stencil_state: Some(StencilState::read(CompareFunction::Equal, inner_depth)),
+			scissor_rect: Some(Some(scissor_rect)),
+			..Default::default()
+		});
+		OnDrop {
+			ctx: self,
+			mask_depth_to_restore: Some(depth),
+		}
 	}
 
 	/// Pushes a transformation that translates all drawing operations by the
@@ -441,6 +532,64 @@ impl Context {
 		self.push(Mat4::from_rotation_z(rotation))
 	}
 
+	/// Draws `draw_content` so that it's only visible where `draw_mask`
+	/// draws. The mask itself is not visible.
+	///
+	/// Masks can be nested. Content drawn inside a nested mask is only
+	/// visible where all of the active masks overlap.
+	pub fn with_mask<T>(
+		&mut self,
+		draw_mask: impl FnOnce(&mut Context),
+		draw_content: impl FnOnce(&mut Context) -> T,
+	) -> T {
+		self.with_mask_settings(MaskSettings::default(), draw_mask, draw_content)
+	}
+
+	/// Draws `draw_content` masked by the shapes drawn by `draw_mask`
+	/// using the given [`MaskSettings`].
+	pub fn with_mask_settings<T>(
+		&mut self,
+		settings: MaskSettings,
+		draw_mask: impl FnOnce(&mut Context),
+		draw_content: impl FnOnce(&mut Context) -> T,
+	) -> T {
+		let _span = tracy_client::span!();
+		// pixels visible through all of the parent masks have a stencil
+		// value of `depth`. the pixels visible through this mask get a
+		// stencil value of `depth + 1`.
+		let depth = *self.graphics.mask_depth_mut();
+		let inner_depth = depth.checked_add(1).expect("too many nested masks");
+		let mask_shader = settings.alpha_threshold.map(|alpha_threshold| {
+			self.graphics
+				.default_resources
+				.mask_shader
+				.with_params(self, alpha_threshold)
+		});
+		if settings.inverted {
+			self.fill_mask_stencil(depth, StencilOperation::IncrementClamp);
+		}
+		{
+			let ctx = &mut self.push(Push {
+				shader: mask_shader,
+				stencil_state: Some(if settings.inverted {
+					mask_stencil_state(inner_depth, StencilOperation::DecrementClamp)
+				} else {
+					mask_stencil_state(depth, StencilOperation::IncrementClamp)
+				}),
+				..Default::default()
+			});
+			draw_mask(ctx);
+		}
+		*self.graphics.mask_depth_mut() = inner_depth;
+		let output = {
+			let ctx = &mut self.push(StencilState::read(CompareFunction::Equal, inner_depth));
+			draw_content(ctx)
+		};
+		*self.graphics.mask_depth_mut() = depth;
+		self.fill_mask_stencil(inner_depth, StencilOperation::DecrementClamp);
+		output
+	}
+
 	/// Returns `true` if the given keyboard key is currently held down.
 	pub fn is_key_down(&self, scancode: Scancode) -> bool {
 		self.event_pump
@@ -514,6 +663,22 @@ impl Context {
 		self.dev_tools_state
 	}
 
+	/// Returns statistics about the glyph atlas used for drawing text.
+	pub fn glyph_atlas_stats(&self) -> GlyphAtlasStats {
+		self.text.stats()
+	}
+
+	/// Returns how long the GPU took to run each render pass of a recent
+	/// frame, or `None` if GPU timings aren't available.
+	///
+	/// GPU timings are measured when [`Features::TIMESTAMP_QUERY`] is
+	/// enabled, which happens automatically when the dev tools are enabled
+	/// and the graphics adapter supports it. Timings are also sent to Tracy
+	/// as GPU zones.
+	pub fn gpu_timings(&self) -> Option<&GpuTimings> {
+		self.graphics.gpu_timings()
+	}
+
 	pub fn load_font_file(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
 		self.text.load_font_file(path)
 	}
@@ -522,10 +687,43 @@ impl Context {
 		self.text.load_fonts_dir(path);
 	}
 
+	/// Saves the frame that's presented at the end of this frame to a
+	/// PNG file at `path`.
+	///
+	/// The screenshot includes the scaling of the main canvas and the dev
+	/// tools UI, just like the window. It's saved on a worker thread a few
+	/// frames later, and any errors are logged.
+	///
+	/// When the dev tools are enabled, pressing F2 saves a screenshot to
+	/// the current directory.
+	pub fn capture_screenshot(&mut self, path: impl Into<PathBuf>) {
+		self.graphics.capture_screenshot(path.into());
+	}
+
 	/// Quits the game.
 	pub fn quit(&mut self) {
 		self.should_quit = true;
 	}
+
+	/// Applies `operation` to the stencil value of every pixel of the
+	/// current render target whose stencil value is `reference`.
+	fn fill_mask_stencil(&mut self, reference: u8, operation: StencilOperation) {
+		let _span = tracy_client::span!();
+		let shader = self.graphics.default_resources.default_shader.clone();
+		self.graphics.push_untransformed_graphics_state(Push {
+			shader: Some(shader),
+			stencil_state: Some(mask_stencil_state(reference, operation)),
+			enable_depth_testing: Some(false),
+			scissor_rect: Some(None),
+			..Default::default()
+		});
+		let ctx = &mut OnDrop {
+			ctx: self,
+			mask_depth_to_restore: None,
+		};
+		let size = ctx.current_render_target_size().as_vec2();
+		Mesh::rectangle(ctx, Rect::new(Vec2::ZERO, size)).draw(ctx);
+	}
 }
 
 /// Settings for starting an application.
@@ -622,11 +820,19 @@ pub enum DevToolsState {
 #[must_use]
 pub struct OnDrop<'a> {
 	ctx: &'a mut Context,
+	/// If set, the pushed graphics settings include a stencil mask
+	/// that should be removed, restoring the mask depth to this value.
+	mask_depth_to_restore: Option<u8>,
 }
 
 impl Drop for OnDrop<'_> {
 	fn drop(&mut self) {
 		self.ctx.graphics.pop_graphics_state();
+		if let Some(depth) = self.mask_depth_to_restore {
+			*self.ctx.graphics.mask_depth_mut() = depth;
+			self.ctx
+				.fill_mask_stencil(depth + 1, StencilOperation::DecrementClamp);
+		}
 	}
 }
 
@@ -644,6 +850,34 @@ impl DerefMut for OnDrop<'_> {
 	}
 }
 
+/// A [`StencilState`] that applies `operation` to pixels whose stencil
+/// value is `reference` without drawing any color.
+fn mask_stencil_state(reference: u8, operation: StencilOperation) -> StencilState {
+	StencilState {
+		enable_color_writes: false,
+		reference,
+		compare: CompareFunction::Equal,
+		on_fail: StencilOperation::Keep,
+		on_depth_fail: StencilOperation::Keep,
+		on_pass: operation,
+		read_mask: 255,
+		write_mask: 255,
+	}
+}
+
+/// Returns `true` if the transform maps axis-aligned rectangles on the
+/// XY plane to axis-aligned rectangles.
+fn is_axis_aligned(transform: Mat4) -> bool {
+	const EPSILON: f32 = 0.000001;
+	let no_perspective = transform.x_axis.w.abs() < EPSILON
+		&& transform.y_axis.w.abs() < EPSILON
+		&& (transform.w_axis.w - 1.0).abs() < EPSILON;
+	let unrotated = transform.x_axis.y.abs() < EPSILON && transform.y_axis.x.abs() < EPSILON;
+	let rotated_90_degrees =
+		transform.x_axis.x.abs() < EPSILON && transform.y_axis.y.abs() < EPSILON;
+	no_perspective && (unrotated || rotated_90_degrees)
+}
+
 fn main_canvas_transform(canvas_size: UVec2, window_size: UVec2, integer_scale: bool) -> Mat4 {
 	let max_horizontal_scale = window_size.x as f32 / canvas_size.x as f32;
 	let max_vertical_scale = window_size.y as f32 / canvas_size.y as f32;
@@ -1,41 +1,48 @@
 mod cached_resources;
 mod default_resources;
+mod gpu_profiler;
 mod layouts;
 mod render_pass;
+mod screenshot;
 
+pub(crate) use cached_resources::InstanceLayout;
 pub(crate) use default_resources::*;
 pub(crate) use layouts::*;
 
-use std::{any::TypeId, collections::HashMap, fmt::Debug};
+use std::{any::TypeId, collections::HashMap, fmt::Debug, path::PathBuf};
 
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, UVec2, Vec3, uvec2};
 use palette::{LinSrgb, LinSrgba};
 use sdl3::video::Window;
+use tracing::warn;
 use wgpu::{
 	BindGroup, BindGroupDescriptor, BindGroupEntry, BindingResource, Buffer, BufferUsages,
-	CompositeAlphaMode, CurrentSurfaceTexture, DepthBiasState, Device, DeviceDescriptor,
-	IndexFormat, Instance, InstanceDescriptor, LoadOp, Operations, PowerPreference, PresentMode,
-	Queue, RenderPassColorAttachment, RenderPassDepthStencilAttachment, RenderPassDescriptor,
-	RenderPipeline, RequestAdapterOptions, StoreOp, Surface, SurfaceColorSpace,
-	SurfaceConfiguration, SurfaceTargetUnsafe, TextureFormat, TextureUsages, TextureViewDescriptor,
+	CompositeAlphaMode, CurrentSurfaceTexture, DepthBiasState, Device, DeviceDescriptor, Extent3d,
+	Features, IndexFormat, Instance, InstanceDescriptor, LoadOp, Operations, PollType,
+	PowerPreference, PresentMode, Queue, RenderPassColorAttachment,
+	RenderPassDepthStencilAttachment, RenderPassDescriptor, RenderPipeline, RequestAdapterOptions,
+	StoreOp, Surface, SurfaceColorSpace, SurfaceConfiguration, SurfaceTargetUnsafe, TextureFormat,
+	TextureUsages, TextureViewDescriptor,
 	util::{BufferInitDescriptor, DeviceExt},
 };
 
 use crate::{
-	ContextSettings,
+	ContextSettings, DevToolsMode,
 	color::{ColorConstants, lin_srgb_to_wgpu_color, lin_srgba_to_wgpu_color},
 	context::{
 		Push,
 		graphics::{
 			cached_resources::{CachedResources, RenderPipelineSettings},
+			gpu_profiler::GpuProfiler,
 			render_pass::{CanvasRenderPass, RenderPass, RenderPassKind},
+			screenshot::PendingScreenshot,
 		},
 	},
 	graphics::{
-		BlendMode, Canvas, CompiledShader, RenderToCanvasSettings, Shader, StencilState,
-		StorageBuffer, Vertex,
-		canvas::CanvasKind,
+		BlendAlphaMode, BlendMode, Canvas, CanvasSettings, CompiledShader, GpuTimings,
+		RenderToCanvasSettings, Shader, StencilState, StorageBuffer, Vertex, Vertex2d,
+		canvas::{CanvasKind, CanvasReadback},
 		texture::{InternalTextureSettings, Texture, TextureSettings},
 	},
 	math::URect,
@@ -56,6 +63,27 @@ pub(crate) struct GraphicsContext {
 	pub(crate) compiled_shaders: HashMap<String, CompiledShader>,
 	render_passes: Vec<RenderPass>,
 	canvas_render_pass_stack: Vec<CanvasRenderPass>,
+	pending_readbacks: Vec<(Texture, CanvasReadback)>,
+	screenshot_requests: Vec<PathBuf>,
+	pending_screenshots: Vec<PendingScreenshot>,
+	/// Copies of canvases read by shader-based blend modes, by size
+	/// and format.
+	blend_destination_textures: HashMap<(UVec2, TextureFormat), BlendDestinationTexture>,
+	/// Shader params for the blend shader, by blend mode and whether
+	/// colors have premultiplied alpha.
+	blend_params_bind_groups: HashMap<[u32; 2], BindGroup>,
+	/// Shader params for the palette shader, by palette offset and size.
+	palette_params_bind_groups: HashMap<[i32; 4], BindGroup>,
+	warned_about_shader_blend_modes: bool,
+	warned_about_unsupported_palettes: bool,
+	/// How many masks are currently active for the window surface.
+	main_surface_mask_depth: u8,
+	/// Canvases allocated for [`RenderGraph`](crate::graphics::canvas::RenderGraph)s.
+	transient_canvases: Vec<TransientCanvas>,
+	/// Measures render pass timings if timestamp queries are supported.
+	gpu_profiler: Option<GpuProfiler>,
+	/// The number of frames that have been presented so far.
+	pub(crate) frame: u64,
 }
 
 impl GraphicsContext {
@@ -78,8 +106,13 @@ impl GraphicsContext {
 			.get_texture_format_features(TextureFormat::Rgba8UnormSrgb)
 			.flags
 			.supported_sample_counts();
+		// timestamp queries are used to show GPU timings in the dev tools
+		let profiling_features = match settings.dev_tools_mode {
+			DevToolsMode::Enabled { .. } => adapter.features() & Features::TIMESTAMP_QUERY,
+			DevToolsMode::Disabled => Features::empty(),
+		};
 		let (device, queue) = pollster::block_on(adapter.request_device(&DeviceDescriptor {
-			required_features: settings.required_graphics_features,
+			required_features: settings.required_graphics_features | profiling_features,
 			..Default::default()
 		}))
 		.expect("error getting graphics device");
@@ -92,7 +125,9 @@ impl GraphicsContext {
 			.unwrap_or(surface_capabilities.formats[0]);
 		let (width, height) = window.size();
 		let config = SurfaceConfiguration {
-			usage: TextureUsages::RENDER_ATTACHMENT,
+			// copying from the surface is needed for screenshots
+			usage: TextureUsages::RENDER_ATTACHMENT
+				| (surface_capabilities.usages & TextureUsages::COPY_SRC),
 			format: surface_format,
 			width,
 			height,
@@ -107,6 +142,10 @@ impl GraphicsContext {
 		let mut compiled_shaders = HashMap::new();
 		let default_resources =
 			DefaultResources::new(&device, &queue, &layouts, &mut compiled_shaders);
+		let gpu_profiler = device
+			.features()
+			.contains(Features::TIMESTAMP_QUERY)
+			.then(|| GpuProfiler::new(&adapter, &device, &queue));
 		let main_surface_depth_stencil_texture = Texture::new(
 			&device,
 			&queue,
@@ -134,6 +173,18 @@ impl GraphicsContext {
 			compiled_shaders,
 			render_passes: vec![],
 			canvas_render_pass_stack: vec![],
+			pending_readbacks: vec![],
+			screenshot_requests: vec![],
+			pending_screenshots: vec![],
+			blend_destination_textures: HashMap::new(),
+			blend_params_bind_groups: HashMap::new(),
+			palette_params_bind_groups: HashMap::new(),
+			warned_about_shader_blend_modes: false,
+			warned_about_unsupported_palettes: false,
+			main_surface_mask_depth: 0,
+			transient_canvases: vec![],
+			gpu_profiler,
+			frame: 0,
 		};
 		ctx.graphics_state_stack.push(ctx.default_graphics_state());
 		ctx
@@ -166,6 +217,7 @@ impl GraphicsContext {
 			canvas,
 			settings,
 			draw_commands: vec![],
+			mask_depth: 0,
 		});
 	}
 
@@ -177,7 +229,35 @@ impl GraphicsContext {
 		self.render_passes.push(canvas_render_pass.into());
 	}
 
-	pub(crate) fn queue_draw_command<V: Vertex>(&mut self, settings: QueueDrawCommandSettings) {
+	pub(crate) fn queue_draw_command<V: Vertex>(&mut self, mut settings: QueueDrawCommandSettings) {
+		let palette_shader = match &settings.palette {
+			Some(palette) => self.prepare_palette_shader::<V>(palette, &settings),
+			None => None,
+		};
+		let blend_shader = if settings.blend_mode.is_shader_based() {
+			let blend_shader = if palette_shader.is_some() {
+				if !self.warned_about_unsupported_palettes {
+					warn!(
+						"{:?} can't be combined with a palette, falling back to alpha blending",
+						settings.blend_mode
+					);
+					self.warned_about_unsupported_palettes = true;
+				}
+				None
+			} else {
+				self.prepare_shader_blend::<V>(&settings)
+			};
+			settings.blend_mode = match (blend_shader.is_some(), settings.blend_mode) {
+				(true, _) => BlendMode::Replace,
+				(false, BlendMode::Overlay(alpha_mode) | BlendMode::SoftLight(alpha_mode)) => {
+					BlendMode::Alpha(alpha_mode)
+				}
+				(false, blend_mode) => blend_mode,
+			};
+			blend_shader
+		} else {
+			None
+		};
 		let coordinate_system_transform = self.coordinate_system_transform();
 		let sample_count = self
 			.canvas_render_pass_stack
@@ -213,11 +293,18 @@ impl GraphicsContext {
 			.graphics_state_stack
 			.last()
 			.expect("no graphics state on stack");
+		let shader = palette_shader
+			.as_ref()
+			.or(blend_shader.as_ref())
+			.unwrap_or(&graphics_state.shader);
 		let texture_view_dimension = settings.texture.view_dimension();
+		let (instance_buffer, instance_layout) = settings.instance_buffer.unzip();
 		let draw_command = DrawCommand {
 			vertex_buffer: settings.vertex_buffer,
 			index_buffer: settings.index_buffer,
+			instance_buffer,
 			range: settings.range,
+			base_vertex: settings.base_vertex,
 			instances: settings.instances,
 			texture: settings.texture,
 			draw_params: DrawParams {
@@ -229,19 +316,19 @@ impl GraphicsContext {
 				normal_transform: settings.transform.inverse().transpose(),
 			},
 			scissor_rect: graphics_state.scissor_rect,
-			shader_params_bind_group: graphics_state
-				.shader
+			shader_params_bind_group: shader
 				.params_bind_group
 				.as_ref()
 				.unwrap_or(&self.default_resources.default_shader_params_bind_group)
 				.clone(),
-			storage_buffers: graphics_state.shader.storage_buffers.clone(),
-			shader_textures: graphics_state.shader.textures.clone(),
+			storage_buffers: shader.storage_buffers.clone(),
+			shader_textures: shader.textures.clone(),
 			stencil_reference: graphics_state.stencil_state.reference,
 			render_pipeline_settings: RenderPipelineSettings {
 				vertex_type,
-				shader_name: graphics_state.shader.name.clone(),
-				shader_source: graphics_state.shader.source.clone(),
+				instance_layout,
+				shader_name: shader.name.clone(),
+				shader_source: shader.source.clone(),
 				blend_mode: settings.blend_mode,
 				enable_color_writes: graphics_state.stencil_state.enable_color_writes,
 				enable_depth_testing: graphics_state.enable_depth_testing,
@@ -250,8 +337,8 @@ impl GraphicsContext {
 				sample_count,
 				texture_format,
 				texture_view_dimension,
-				num_storage_buffers: graphics_state.shader.storage_buffers.len(),
-				num_shader_textures: graphics_state.shader.textures.len(),
+				num_storage_buffers: shader.storage_buffers.len(),
+				num_shader_textures: shader.textures.len(),
 			},
 		};
 		draw_commands.push(draw_command);
@@ -267,10 +354,253 @@ impl GraphicsContext {
 		);
 	}
 
+	/// Pushes a set of graphics settings, using `new.transform` (or no
+	/// transform) instead of combining it with the current transform.
+	pub(crate) fn push_untransformed_graphics_state(&mut self, new: Push) {
+		let mut graphics_state = self
+			.graphics_state_stack
+			.last()
+			.cloned()
+			.unwrap_or_else(|| self.default_graphics_state())
+			.push(&new);
+		graphics_state.transform = new.transform.unwrap_or(Mat4::IDENTITY);
+		self.graphics_state_stack.push(graphics_state);
+	}
+
+	/// The transform of the current graphics state, not including the
+	/// transform from pixels to normalized device coordinates.
+	pub(crate) fn current_transform(&self) -> Mat4 {
+		self.graphics_state_stack
+			.last()
+			.expect("no graphics state on stack")
+			.transform
+	}
+
+	pub(crate) fn current_scissor_rect(&self) -> Option<URect> {
+		self.graphics_state_stack
+			.last()
+			.expect("no graphics state on stack")
+			.scissor_rect
+	}
+
+	/// The number of masks currently active for the current render target.
+	///
+	/// Each mask increments the stencil value of the pixels it covers,
+	/// so this is also the stencil value of the pixels that are visible
+	/// through all of the active masks.
+	pub(crate) fn mask_depth_mut(&mut self) -> &mut u8 {
+		self.canvas_render_pass_stack
+			.last_mut()
+			.map(|canvas_render_pass| &mut canvas_render_pass.mask_depth)
+			.unwrap_or(&mut self.main_surface_mask_depth)
+	}
+
 	pub(crate) fn pop_graphics_state(&mut self) {
 		self.graphics_state_stack.pop();
 	}
 
+	/// Sets up a draw with a [shader-based](BlendMode::is_shader_based)
+	/// blend mode by copying the current canvas so the blend shader can
+	/// read it. Returns the blend shader to use, or `None` if the blend
+	/// mode isn't supported for this draw.
+	fn prepare_shader_blend<V: Vertex>(
+		&mut self,
+		settings: &QueueDrawCommandSettings,
+	) -> Option<Shader> {
+		let _span = tracy_client::span!();
+		let supported = !self.canvas_render_pass_stack.is_empty()
+			&& TypeId::of::<V>() == TypeId::of::<Vertex2d>()
+			&& settings.instance_buffer.is_none();
+		if !supported {
+			if !self.warned_about_shader_blend_modes {
+				warn!(
+					"{:?} is only supported for non-instanced 2D meshes drawn to a canvas, \
+					falling back to alpha blending",
+					settings.blend_mode
+				);
+				self.warned_about_shader_blend_modes = true;
+			}
+			return None;
+		}
+		let canvas_render_pass = self.canvas_render_pass_stack.last_mut().unwrap();
+		let canvas = canvas_render_pass.canvas.clone();
+		let destination = self
+			.blend_destination_textures
+			.entry((canvas.size(), canvas.format()))
+			.or_insert_with(|| BlendDestinationTexture {
+				texture: Texture::new(
+					&self.device,
+					&self.queue,
+					canvas.size(),
+					1,
+					None,
+					TextureSettings {
+						label: "Blend Destination Texture".into(),
+						..Default::default()
+					},
+					InternalTextureSettings {
+						format: canvas.format(),
+						sample_count: 1,
+					},
+				),
+				used_this_frame: false,
+			});
+		destination.used_this_frame = true;
+		let destination = destination.texture.clone();
+		// end the canvas render pass so far, copy what's been drawn, and then
+		// continue drawing to the canvas in a new render pass
+		let draw_commands = std::mem::take(&mut canvas_render_pass.draw_commands);
+		let continued_settings = RenderToCanvasSettings {
+			render_pass_label: canvas_render_pass.settings.render_pass_label.clone(),
+			..RenderToCanvasSettings::no_clear()
+		};
+		let settings_so_far =
+			std::mem::replace(&mut canvas_render_pass.settings, continued_settings);
+		self.render_passes.push(RenderPass {
+			kind: RenderPassKind::Canvas {
+				canvas: canvas.clone(),
+				settings: settings_so_far,
+			},
+			draw_commands,
+		});
+		self.render_passes.push(RenderPass {
+			kind: RenderPassKind::CopyTexture {
+				source: canvas.drawable_texture(),
+				destination: destination.clone(),
+			},
+			draw_commands: vec![],
+		});
+		let (mode, alpha_mode) = match settings.blend_mode {
+			BlendMode::Overlay(alpha_mode) => (0, alpha_mode),
+			BlendMode::SoftLight(alpha_mode) => (1, alpha_mode),
+			blend_mode => unreachable!("{blend_mode:?} is not shader-based"),
+		};
+		let params: [u32; 2] = [mode, (alpha_mode == BlendAlphaMode::Premultiplied) as u32];
+		let params_bind_group = self
+			.blend_params_bind_groups
+			.entry(params)
+			.or_insert_with(|| {
+				let params_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+					label: Some("Blend Shader - Shader Params Buffer"),
+					contents: bytemuck::cast_slice(&params),
+					usage: BufferUsages::UNIFORM,
+				});
+				self.device.create_bind_group(&BindGroupDescriptor {
+					label: Some("Blend Shader - Shader Params Bind Group"),
+					layout: &self.layouts.shader_params_bind_group_layout,
+					entries: &[BindGroupEntry {
+						binding: 0,
+						resource: params_buffer.as_entire_binding(),
+					}],
+				})
+			})
+			.clone();
+		Some(Shader {
+			params_bind_group: Some(params_bind_group),
+			..self
+				.default_resources
+				.blend_shader
+				.with_textures(vec![destination])
+		})
+	}
+
+	/// Returns the built-in palette shader set up to look up colors in
+	/// `palette`, or `None` if palettes aren't supported for this draw.
+	fn prepare_palette_shader<V: Vertex>(
+		&mut self,
+		palette: &Texture,
+		settings: &QueueDrawCommandSettings,
+	) -> Option<Shader> {
+		let _span = tracy_client::span!();
+		let supported =
+			TypeId::of::<V>() == TypeId::of::<Vertex2d>() && settings.instance_buffer.is_none();
+		if !supported {
+			if !self.warned_about_unsupported_palettes {
+				warn!(
+					"palettes are only supported for non-instanced 2D meshes, drawing without a palette"
+				);
+				self.warned_about_unsupported_palettes = true;
+			}
+			return None;
+		}
+		// the params only depend on the palette's region, so palettes
+		// in the same place share a bind group
+		let offset = palette.region.top_left.as_ivec2();
+		let params: [i32; 4] = [offset.x, offset.y, palette.region.size.x as i32, 0];
+		let params_bind_group = self
+			.palette_params_bind_groups
+			.entry(params)
+			.or_insert_with(|| {
+				let params_buffer = self.device.create_buffer_init(&BufferInitDescriptor {
+					label: Some("Palette Shader - Shader Params Buffer"),
+					contents: bytemuck::cast_slice(&params),
+					usage: BufferUsages::UNIFORM,
+				});
+				self.device.create_bind_group(&BindGroupDescriptor {
+					label: Some("Palette Shader - Shader Params Bind Group"),
+					layout: &self.layouts.shader_params_bind_group_layout,
+					entries: &[BindGroupEntry {
+						binding: 0,
+						resource: params_buffer.as_entire_binding(),
+					}],
+				})
+			})
+			.clone();
+		Some(Shader {
+			params_bind_group: Some(params_bind_group),
+			..self
+				.default_resources
+				.palette_shader
+				.with_textures(vec![palette.clone()])
+		})
+	}
+
+	/// Returns the `index`th pooled canvas with the given size and
+	/// compatible settings, creating it if it doesn't exist yet.
+	///
+	/// Pooled canvases that aren't used during a frame are freed at the
+	/// end of the frame.
+	pub(crate) fn transient_canvas(
+		&mut self,
+		size: UVec2,
+		settings: CanvasSettings,
+		index: usize,
+	) -> Canvas {
+		if let Some(transient_canvas) = self
+			.transient_canvases
+			.iter_mut()
+			.filter(|transient_canvas| {
+				transient_canvas.canvas.size() == size
+					&& transient_canvas.settings.is_compatible_with(&settings)
+			})
+			.nth(index)
+		{
+			transient_canvas.used_this_frame = true;
+			return transient_canvas.canvas.clone();
+		}
+		let canvas = Canvas::new_from_graphics_ctx(self, size, settings.clone());
+		self.transient_canvases.push(TransientCanvas {
+			canvas: canvas.clone(),
+			settings,
+			used_this_frame: true,
+		});
+		canvas
+	}
+
+	pub(crate) fn gpu_timings(&self) -> Option<&GpuTimings> {
+		self.gpu_profiler.as_ref()?.latest_timings()
+	}
+
+	/// Copies the `texture` into the `readback` at the end of the frame.
+	pub(crate) fn queue_readback(&mut self, texture: Texture, readback: CanvasReadback) {
+		self.pending_readbacks.push((texture, readback));
+	}
+
+	/// Saves the next presented frame to the file at `path`.
+	pub(crate) fn capture_screenshot(&mut self, path: PathBuf) {
+		self.screenshot_requests.push(path);
+	}
+
 	pub(crate) fn present_mode(&self) -> PresentMode {
 		self.config.present_mode
 	}
@@ -314,11 +644,18 @@ impl GraphicsContext {
 			error => panic!("error getting surface texture: {:?}", error),
 		};
 		let output = frame.texture.create_view(&TextureViewDescriptor::default());
+		if let Some(gpu_profiler) = &mut self.gpu_profiler {
+			gpu_profiler.begin_frame();
+		}
 
 		// clear the main surface to the specified clear color
 		{
+			let profiled_pass = self
+				.gpu_profiler
+				.as_mut()
+				.and_then(|gpu_profiler| gpu_profiler.begin_pass("Main Surface Clear Pass"));
 			let _render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
-				label: Some("Main Surface Render Pass"),
+				label: Some("Main Surface Clear Pass"),
 				color_attachments: &[Some(RenderPassColorAttachment {
 					view: &output,
 					resolve_target: None,
@@ -339,10 +676,17 @@ impl GraphicsContext {
 						store: StoreOp::Store,
 					}),
 				}),
-				timestamp_writes: None,
+				timestamp_writes: self
+					.gpu_profiler
+					.as_ref()
+					.and_then(|gpu_profiler| gpu_profiler.timestamp_writes(profiled_pass)),
 				occlusion_query_set: None,
 				multiview_mask: None,
 			});
+			drop(_render_pass);
+			if let Some(gpu_profiler) = &mut self.gpu_profiler {
+				gpu_profiler.end_pass(profiled_pass);
+			}
 		}
 
 		// run render passes
@@ -351,9 +695,40 @@ impl GraphicsContext {
 			mut draw_commands,
 		} in self.render_passes.drain(..)
 		{
+			let label = match &kind {
+				RenderPassKind::MainSurface => "Main Surface Render Pass",
+				RenderPassKind::Canvas { settings, .. } => &settings.render_pass_label,
+				RenderPassKind::CopyTexture { .. } => "Copy Texture",
+			};
+			let profiled_pass = match &kind {
+				RenderPassKind::CopyTexture { .. } => None,
+				_ => self
+					.gpu_profiler
+					.as_mut()
+					.and_then(|gpu_profiler| gpu_profiler.begin_pass(label)),
+			};
+			let timestamp_writes = self
+				.gpu_profiler
+				.as_ref()
+				.and_then(|gpu_profiler| gpu_profiler.timestamp_writes(profiled_pass));
 			let render_pass_descriptor = match &kind {
+				RenderPassKind::CopyTexture {
+					source,
+					destination,
+				} => {
+					encoder.copy_texture_to_texture(
+						source.texture.as_image_copy(),
+						destination.texture.as_image_copy(),
+						Extent3d {
+							width: source.size().x,
+							height: source.size().y,
+							depth_or_array_layers: 1,
+						},
+					);
+					continue;
+				}
 				RenderPassKind::MainSurface => RenderPassDescriptor {
-					label: Some("Main Surface Render Pass"),
+					label: Some(label),
 					color_attachments: &[Some(RenderPassColorAttachment {
 						view: &output,
 						resolve_target: None,
@@ -374,14 +749,14 @@ impl GraphicsContext {
 							store: StoreOp::Store,
 						}),
 					}),
-					timestamp_writes: None,
+					timestamp_writes: timestamp_writes.clone(),
 					occlusion_query_set: None,
 					multiview_mask: None,
 				},
 				RenderPassKind::Canvas {
 					canvas, settings, ..
 				} => RenderPassDescriptor {
-					label: Some(&settings.render_pass_label),
+					label: Some(label),
 					color_attachments: &[Some(RenderPassColorAttachment {
 						view: match &canvas.kind {
 							CanvasKind::Normal { texture }
@@ -421,7 +796,7 @@ impl GraphicsContext {
 							store: StoreOp::Store,
 						}),
 					}),
-					timestamp_writes: None,
+					timestamp_writes: timestamp_writes.clone(),
 					occlusion_query_set: None,
 					multiview_mask: None,
 				},
@@ -429,6 +804,9 @@ impl GraphicsContext {
 			let default_scissor_size = match &kind {
 				RenderPassKind::MainSurface => uvec2(self.config.width, self.config.height),
 				RenderPassKind::Canvas { canvas, .. } => canvas.size(),
+				RenderPassKind::CopyTexture { .. } => {
+					unreachable!("copies don't use render passes")
+				}
 			};
 			let render_pass = encoder.begin_render_pass(&render_pass_descriptor);
 			run_draw_commands(
@@ -439,10 +817,51 @@ impl GraphicsContext {
 				render_pass,
 				URect::new(UVec2::ZERO, default_scissor_size),
 			);
+			if let Some(gpu_profiler) = &mut self.gpu_profiler {
+				gpu_profiler.end_pass(profiled_pass);
+			}
+		}
+		if let Some(gpu_profiler) = &mut self.gpu_profiler {
+			gpu_profiler.end_frame(&mut encoder);
+		}
+
+		// copy textures for readbacks now that everything has been drawn
+		for (texture, readback) in self.pending_readbacks.drain(..) {
+			readback.record_copy(&mut encoder, &texture.texture);
+		}
+		if !self.screenshot_requests.is_empty() {
+			let paths = std::mem::take(&mut self.screenshot_requests);
+			if self.config.usage.contains(TextureUsages::COPY_SRC) {
+				let readback = CanvasReadback::new(
+					&self.device,
+					uvec2(self.config.width, self.config.height),
+					self.config.format,
+				);
+				readback.record_copy(&mut encoder, &frame.texture);
+				self.pending_screenshots
+					.push(PendingScreenshot { paths, readback });
+			} else {
+				warn!("the window surface does not support screenshots");
+			}
 		}
 
 		self.queue.submit([encoder.finish()]);
 		self.queue.present(frame);
+		self.frame += 1;
+
+		// resolve readbacks from previous frames without blocking
+		self.device
+			.poll(PollType::Poll)
+			.expect("error polling graphics device");
+		self.pending_screenshots
+			.retain_mut(|screenshot| !screenshot.try_save());
+		if let Some(gpu_profiler) = &mut self.gpu_profiler {
+			gpu_profiler.collect_timings();
+		}
+		self.transient_canvases
+			.retain_mut(|transient_canvas| std::mem::take(&mut transient_canvas.used_this_frame));
+		self.blend_destination_textures
+			.retain(|_, destination| std::mem::take(&mut destination.used_this_frame));
 
 		self.graphics_state_stack.clear();
 		self.graphics_state_stack
@@ -486,19 +905,24 @@ impl GraphicsContext {
 pub(crate) struct QueueDrawCommandSettings {
 	pub(crate) vertex_buffer: Buffer,
 	pub(crate) index_buffer: Buffer,
+	pub(crate) instance_buffer: Option<(Buffer, InstanceLayout)>,
 	pub(crate) range: (u32, u32),
+	pub(crate) base_vertex: i32,
 	pub(crate) instances: (u32, u32),
 	pub(crate) texture: Texture,
 	pub(crate) transform: Mat4,
 	pub(crate) color: LinSrgba,
 	pub(crate) blend_mode: BlendMode,
+	pub(crate) palette: Option<Texture>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 struct DrawCommand {
 	vertex_buffer: Buffer,
 	index_buffer: Buffer,
+	instance_buffer: Option<Buffer>,
 	range: (u32, u32),
+	base_vertex: i32,
 	instances: (u32, u32),
 	texture: Texture,
 	draw_params: DrawParams,
@@ -525,6 +949,7 @@ impl From<CanvasRenderPass> for RenderPass {
 			canvas,
 			settings,
 			draw_commands,
+			..
 		}: CanvasRenderPass,
 	) -> Self {
 		Self {
@@ -534,6 +959,17 @@ impl From<CanvasRenderPass> for RenderPass {
 	}
 }
 
+struct TransientCanvas {
+	canvas: Canvas,
+	settings: CanvasSettings,
+	used_this_frame: bool,
+}
+
+struct BlendDestinationTexture {
+	texture: Texture,
+	used_this_frame: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct GraphicsState {
 	transform: Mat4,
@@ -574,7 +1010,9 @@ fn run_draw_commands(
 	for DrawCommand {
 		vertex_buffer,
 		index_buffer,
+		instance_buffer,
 		range,
+		base_vertex,
 		instances,
 		texture,
 		draw_params,
@@ -639,8 +1077,18 @@ fn run_draw_commands(
 			&[],
 		);
 		render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+		if let Some(instance_buffer) = &instance_buffer {
+			render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
+		}
 		render_pass.set_index_buffer(index_buffer.slice(..), IndexFormat::Uint32);
-		let scissor_rect = scissor_rect.unwrap_or(default_scissor_rect);
+		// scissor rects outside of the render target are invalid
+		let scissor_rect = scissor_rect
+			.map(|scissor_rect| {
+				scissor_rect
+					.intersection(default_scissor_rect)
+					.unwrap_or(URect::new(UVec2::ZERO, UVec2::ZERO))
+			})
+			.unwrap_or(default_scissor_rect);
 		render_pass.set_scissor_rect(
 			scissor_rect.left(),
 			scissor_rect.top(),
@@ -648,7 +1096,7 @@ fn run_draw_commands(
 			scissor_rect.size.y,
 		);
 		render_pass.set_stencil_reference(stencil_reference as u32);
-		render_pass.draw_indexed(range.0..range.1, 0, instances.0..instances.1);
+		render_pass.draw_indexed(range.0..range.1, base_vertex, instances.0..instances.1);
 	}
 }
 
@@ -0,0 +1,102 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec2 aTexCoord;
+layout (location = 2) in vec4 aColor;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+
+void main()
+{
+    gl_Position = globalTransform * vec4(aPos, 0.0, 1.0);
+    texCoord = aTexCoord;
+    vertexColor = aColor * blendColor;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+
+layout (binding = 1) uniform texture2D inTexture;
+layout (binding = 2) uniform sampler inSampler;
+
+layout (set = 1, binding = 0)
+uniform BlendParams {
+	// 0 = overlay, 1 = soft light
+	uint mode;
+	// whether the colors being drawn have premultiplied alpha
+	uint premultiplied;
+};
+
+// a copy of the canvas being drawn to
+layout (set = 3, binding = 0) uniform texture2D destinationTexture;
+layout (set = 3, binding = 1) uniform sampler destinationSampler;
+
+out vec4 fragColor;
+
+vec3 overlay(vec3 source, vec3 destination)
+{
+    return mix(
+        2.0 * source * destination,
+        1.0 - 2.0 * (1.0 - source) * (1.0 - destination),
+        step(0.5, destination)
+    );
+}
+
+vec3 softLight(vec3 source, vec3 destination)
+{
+    vec3 d = mix(
+        ((16.0 * destination - 12.0) * destination + 4.0) * destination,
+        sqrt(destination),
+        step(0.25, destination)
+    );
+    return mix(
+        destination - (1.0 - 2.0 * source) * destination * (1.0 - destination),
+        destination + (2.0 * source - 1.0) * (d - destination),
+        step(0.5, source)
+    );
+}
+
+void main()
+{
+    vec4 source = texture(sampler2D(inTexture, inSampler), texCoord);
+    if (premultiplied == 1u) {
+        // only the texture is premultiplied, not the vertex color
+        source.rgb = source.a > 0.0 ? source.rgb / source.a : vec3(0.0);
+    }
+    source *= vertexColor;
+    if (source.a == 0.0) discard;
+
+    // the canvas has premultiplied alpha
+    vec4 destination = texelFetch(
+        sampler2D(destinationTexture, destinationSampler),
+        ivec2(gl_FragCoord.xy),
+        0
+    );
+    vec3 destinationColor = destination.a > 0.0 ? destination.rgb / destination.a : vec3(0.0);
+
+    vec3 blended = mode == 0u
+        ? overlay(source.rgb, destinationColor)
+        : softLight(source.rgb, destinationColor);
+    // composite the blended color over the canvas, leaving the parts of
+    // the source and destination that don't overlap unchanged
+    vec3 color = source.rgb * source.a * (1.0 - destination.a)
+        + blended * source.a * destination.a
+        + destination.rgb * (1.0 - source.a);
+    fragColor = vec4(color, source.a + destination.a * (1.0 - source.a));
+}
+
+#endif
@@ -75,9 +75,16 @@ impl VertexInfo {
 	}
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct InstanceLayout {
+	pub(crate) size: usize,
+	pub(crate) attributes: Vec<VertexAttribute>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(super) struct RenderPipelineSettings {
 	pub(super) vertex_type: TypeId,
+	pub(super) instance_layout: Option<InstanceLayout>,
 	pub(super) shader_name: String,
 	pub(super) shader_source: String,
 	pub(super) blend_mode: BlendMode,
@@ -102,6 +109,18 @@ fn create_render_pipeline(
 	let span = tracy_client::span!();
 	span.emit_text(&format!("{:#?}", settings));
 	let vertex_info = &vertex_info[&settings.vertex_type];
+	let mut vertex_buffer_layouts = vec![Some(VertexBufferLayout {
+		array_stride: vertex_info.size as u64,
+		step_mode: VertexStepMode::Vertex,
+		attributes: &vertex_info.attributes,
+	})];
+	if let Some(instance_layout) = &settings.instance_layout {
+		vertex_buffer_layouts.push(Some(VertexBufferLayout {
+			array_stride: instance_layout.size as u64,
+			step_mode: VertexStepMode::Instance,
+			attributes: &instance_layout.attributes,
+		}));
+	}
 	let storage_buffers_bind_group_layout =
 		device.create_bind_group_layout(&BindGroupLayoutDescriptor {
 			label: Some("Storage Buffers Bind Group Layout"),
@@ -137,11 +156,7 @@ fn create_render_pipeline(
 			module: &compiled_shaders[&settings.shader_source].vertex,
 			entry_point: Some("main"),
 			compilation_options: PipelineCompilationOptions::default(),
-			buffers: &[Some(VertexBufferLayout {
-				array_stride: vertex_info.size as u64,
-				step_mode: VertexStepMode::Vertex,
-				attributes: &vertex_info.attributes,
-			})],
+			buffers: &vertex_buffer_layouts,
 		},
 		primitive: PrimitiveState::default(),
 		depth_stencil: Some(DepthStencilState {
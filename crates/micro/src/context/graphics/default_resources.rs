@@ -15,10 +15,30 @@ use crate::{
 };
 
 const DEFAULT_SHADER_SOURCE: &str = include_str!("shader.glsl");
+const DEFAULT_SKINNED_SHADER_SOURCE: &str = include_str!("skinned_shader.glsl");
+const DEFAULT_LIT_SHADER_SOURCE: &str = include_str!("lit_shader.glsl");
+const SHADOW_DEPTH_SHADER_SOURCE: &str = include_str!("shadow_depth_shader.glsl");
+const PARTICLE_SHADER_SOURCE: &str = include_str!("particle_shader.glsl");
+const SDF_TEXT_SHADER_SOURCE: &str = include_str!("sdf_text_shader.glsl");
+const BLEND_SHADER_SOURCE: &str = include_str!("blend_shader.glsl");
+const MASK_SHADER_SOURCE: &str = include_str!("mask_shader.glsl");
+const PALETTE_SHADER_SOURCE: &str = include_str!("palette_shader.glsl");
+const MULTI_TEXTURE_SPRITE_SHADER_TEMPLATE: &str = include_str!("multi_texture_sprite_shader.glsl");
+const MAX_MULTI_TEXTURE_SPRITE_BATCH_TEXTURES: u32 = 16;
 
 pub(crate) struct DefaultResources {
 	pub(crate) default_texture: Texture,
 	pub(crate) default_shader: Shader,
+	pub(crate) default_skinned_shader: Shader,
+	pub(crate) default_lit_shader: Shader,
+	pub(crate) shadow_depth_shader: Shader,
+	pub(crate) particle_shader: Shader,
+	pub(crate) sdf_text_shader: Shader,
+	pub(crate) blend_shader: Shader,
+	pub(crate) mask_shader: Shader,
+	pub(crate) palette_shader: Shader,
+	pub(crate) multi_texture_sprite_shader: Shader,
+	pub(crate) max_multi_texture_sprite_batch_textures: usize,
 	pub(crate) default_shader_params_bind_group: BindGroup,
 }
 
@@ -45,6 +65,72 @@ impl DefaultResources {
 			compiled_shaders,
 		)
 		.expect("error compiling default shader");
+		let default_skinned_shader = Shader::new(
+			"Default Skinned Shader",
+			DEFAULT_SKINNED_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling default skinned shader");
+		let default_lit_shader = Shader::new(
+			"Default Lit Shader",
+			DEFAULT_LIT_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling default lit shader");
+		let shadow_depth_shader = Shader::new(
+			"Shadow Depth Shader",
+			SHADOW_DEPTH_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling shadow depth shader");
+		let particle_shader = Shader::new(
+			"Particle Shader",
+			PARTICLE_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling particle shader");
+		let sdf_text_shader = Shader::new(
+			"SDF Text Shader",
+			SDF_TEXT_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling SDF text shader");
+		let blend_shader = Shader::new(
+			"Blend Shader",
+			BLEND_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling blend shader");
+		let mask_shader = Shader::new("Mask Shader", MASK_SHADER_SOURCE, device, compiled_shaders)
+			.expect("error compiling mask shader");
+		let palette_shader = Shader::new(
+			"Palette Shader",
+			PALETTE_SHADER_SOURCE,
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling palette shader");
+		// one texture and sampler are already used by the mesh bind group
+		let max_multi_texture_sprite_batch_textures = (device
+			.limits()
+			.max_sampled_textures_per_shader_stage
+			.min(device.limits().max_samplers_per_shader_stage)
+			- 1)
+		.min(MAX_MULTI_TEXTURE_SPRITE_BATCH_TEXTURES)
+			as usize;
+		let multi_texture_sprite_shader = Shader::new(
+			"Multi-Texture Sprite Shader",
+			multi_texture_sprite_shader_source(max_multi_texture_sprite_batch_textures),
+			device,
+			compiled_shaders,
+		)
+		.expect("error compiling multi-texture sprite shader");
 		let default_shader_params_bind_group = {
 			let buffer = device.create_buffer_init(&BufferInitDescriptor {
 				label: Some("Default Shader - Shader Params Buffer"),
@@ -63,7 +149,40 @@ impl DefaultResources {
 		Self {
 			default_texture,
 			default_shader,
+			default_skinned_shader,
+			default_lit_shader,
+			shadow_depth_shader,
+			particle_shader,
+			sdf_text_shader,
+			blend_shader,
+			mask_shader,
+			palette_shader,
+			multi_texture_sprite_shader,
+			max_multi_texture_sprite_batch_textures,
 			default_shader_params_bind_group,
 		}
 	}
 }
+
+fn multi_texture_sprite_shader_source(num_textures: usize) -> String {
+	let bindings = (0..num_textures)
+		.map(|i| {
+			format!(
+				"layout (set = 3, binding = {}) uniform texture2D texture{i};\n\
+				layout (set = 3, binding = {}) uniform sampler sampler{i};\n",
+				i * 2,
+				i * 2 + 1
+			)
+		})
+		.collect::<String>();
+	let samples = (0..num_textures)
+		.map(|i| {
+			format!(
+				"if (textureIndex == {i}u) return textureGrad(sampler2D(texture{i}, sampler{i}), coords, ddx, ddy);\n"
+			)
+		})
+		.collect::<String>();
+	MULTI_TEXTURE_SPRITE_SHADER_TEMPLATE
+		.replace("// TEXTURE_BINDINGS", &bindings)
+		.replace("// TEXTURE_SAMPLES", &samples)
+}
@@ -0,0 +1,299 @@
+use std::{
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use tracing::warn;
+use wgpu::{
+	Adapter, Backend, Buffer, BufferUsages, CommandEncoder, ComputePassDescriptor,
+	ComputePassTimestampWrites, Device, MapMode, PollType, QUERY_SIZE, QuerySet,
+	QuerySetDescriptor, QueryType, Queue, RenderPassTimestampWrites, wgt::BufferDescriptor,
+};
+
+use crate::graphics::{GpuPassTiming, GpuTimings};
+
+/// The maximum number of render passes that can be timed each frame.
+const MAX_PASSES_PER_FRAME: u32 = 128;
+/// The number of frames that can be waiting for their timestamps to be
+/// read back at once. If all of them are in use, the next frame isn't
+/// profiled.
+const NUM_FRAMES_IN_FLIGHT: usize = 4;
+
+/// Measures how long each render pass takes on the GPU using timestamp
+/// queries, and reports the timings to the dev tools and Tracy.
+pub(crate) struct GpuProfiler {
+	frames: Vec<ProfiledFrame>,
+	/// The index of the frame currently being recorded, if it's being
+	/// profiled.
+	current_frame: Option<usize>,
+	/// The number of nanoseconds per timestamp tick.
+	timestamp_period: f32,
+	tracy_context: Option<tracy_client::GpuContext>,
+	latest_timings: Option<GpuTimings>,
+	warned_about_too_many_passes: bool,
+}
+
+impl GpuProfiler {
+	pub(crate) fn new(adapter: &Adapter, device: &Device, queue: &Queue) -> Self {
+		let _span = tracy_client::span!();
+		let timestamp_period = queue.get_timestamp_period();
+		let tracy_context = tracy_client::Client::running().and_then(|client| {
+			let context_type = match adapter.get_info().backend {
+				Backend::Vulkan => tracy_client::GpuContextType::Vulkan,
+				Backend::Dx12 => tracy_client::GpuContextType::Direct3D12,
+				_ => tracy_client::GpuContextType::Invalid,
+			};
+			client
+				.new_gpu_context(
+					Some("micro"),
+					context_type,
+					current_gpu_timestamp(device, queue) as i64,
+					timestamp_period,
+				)
+				.inspect_err(|err| warn!("error creating Tracy GPU context: {err}"))
+				.ok()
+		});
+		Self {
+			frames: (0..NUM_FRAMES_IN_FLIGHT)
+				.map(|_| ProfiledFrame::new(device))
+				.collect(),
+			current_frame: None,
+			timestamp_period,
+			tracy_context,
+			latest_timings: None,
+			warned_about_too_many_passes: false,
+		}
+	}
+
+	/// The timings of the most recent frame whose timestamps have been
+	/// read back.
+	pub(crate) fn latest_timings(&self) -> Option<&GpuTimings> {
+		self.latest_timings.as_ref()
+	}
+
+	/// Starts profiling a frame if there's a free set of queries.
+	pub(crate) fn begin_frame(&mut self) {
+		self.current_frame = self
+			.frames
+			.iter()
+			.position(|frame| *frame.state.lock().unwrap() == FrameState::Free);
+	}
+
+	/// Reserves timestamp queries for a render pass with the given label.
+	/// Returns the index of the pass, which can be passed to
+	/// [`timestamp_writes`](Self::timestamp_writes) and
+	/// [`end_pass`](Self::end_pass), or `None` if the pass won't be timed.
+	pub(crate) fn begin_pass(&mut self, label: &str) -> Option<u32> {
+		let frame = &mut self.frames[self.current_frame?];
+		let index = frame.passes.len() as u32;
+		if index >= MAX_PASSES_PER_FRAME {
+			if !self.warned_about_too_many_passes {
+				warn!(
+					"only the first {MAX_PASSES_PER_FRAME} render passes of a frame are profiled"
+				);
+				self.warned_about_too_many_passes = true;
+			}
+			return None;
+		}
+		let tracy_span = self
+			.tracy_context
+			.as_ref()
+			.and_then(|context| context.span_alloc(label, "present", file!(), line!()).ok());
+		frame.passes.push(ProfiledPass {
+			label: label.to_string(),
+			tracy_span,
+		});
+		Some(index)
+	}
+
+	pub(crate) fn timestamp_writes(
+		&self,
+		pass: Option<u32>,
+	) -> Option<RenderPassTimestampWrites<'_>> {
+		let pass = pass?;
+		Some(RenderPassTimestampWrites {
+			query_set: &self.frames[self.current_frame?].query_set,
+			beginning_of_pass_write_index: Some(pass * 2),
+			end_of_pass_write_index: Some(pass * 2 + 1),
+		})
+	}
+
+	/// Marks the end of the render pass on the CPU side.
+	pub(crate) fn end_pass(&mut self, pass: Option<u32>) {
+		let (Some(pass), Some(current_frame)) = (pass, self.current_frame) else {
+			return;
+		};
+		if let Some(tracy_span) = &mut self.frames[current_frame].passes[pass as usize].tracy_span {
+			tracy_span.end_zone();
+		}
+	}
+
+	/// Records copying the timestamps of the current frame to a buffer
+	/// that will be read once the GPU is done with the frame.
+	pub(crate) fn end_frame(&mut self, encoder: &mut CommandEncoder) {
+		let Some(current_frame) = self.current_frame.take() else {
+			return;
+		};
+		let frame = &self.frames[current_frame];
+		if frame.passes.is_empty() {
+			return;
+		}
+		let num_queries = frame.passes.len() as u32 * 2;
+		encoder.resolve_query_set(&frame.query_set, 0..num_queries, &frame.resolve_buffer, 0);
+		encoder.copy_buffer_to_buffer(
+			&frame.resolve_buffer,
+			0,
+			&frame.readback_buffer,
+			0,
+			(num_queries * QUERY_SIZE) as u64,
+		);
+		*frame.state.lock().unwrap() = FrameState::Pending;
+		let state = frame.state.clone();
+		encoder.map_buffer_on_submit(&frame.readback_buffer, MapMode::Read, .., move |result| {
+			result.expect("error mapping buffer");
+			*state.lock().unwrap() = FrameState::Mapped;
+		});
+	}
+
+	/// Reads the timestamps of any frames the GPU has finished with.
+	pub(crate) fn collect_timings(&mut self) {
+		let _span = tracy_client::span!();
+		for frame in &mut self.frames {
+			let mut state = frame.state.lock().unwrap();
+			if *state != FrameState::Mapped {
+				continue;
+			}
+			let timestamps = {
+				let num_bytes = (frame.passes.len() as u32 * 2 * QUERY_SIZE) as u64;
+				let view = frame
+					.readback_buffer
+					.get_mapped_range(..num_bytes)
+					.expect("error mapping range");
+				bytemuck::pod_collect_to_vec::<u8, u64>(&view)
+			};
+			frame.readback_buffer.unmap();
+			let passes = frame
+				.passes
+				.drain(..)
+				.zip(timestamps.chunks_exact(2))
+				.map(|(pass, timestamps)| {
+					let (start, end) = (timestamps[0], timestamps[1]);
+					if let Some(tracy_span) = &pass.tracy_span {
+						tracy_span.upload_timestamp_start(start as i64);
+						tracy_span.upload_timestamp_end(end as i64);
+					}
+					GpuPassTiming {
+						label: pass.label,
+						duration: Duration::from_nanos(
+							(end.saturating_sub(start) as f64 * self.timestamp_period as f64)
+								as u64,
+						),
+					}
+				})
+				.collect();
+			self.latest_timings = Some(GpuTimings { passes });
+			*state = FrameState::Free;
+		}
+	}
+}
+
+struct ProfiledFrame {
+	query_set: QuerySet,
+	resolve_buffer: Buffer,
+	readback_buffer: Buffer,
+	passes: Vec<ProfiledPass>,
+	state: Arc<Mutex<FrameState>>,
+}
+
+impl ProfiledFrame {
+	fn new(device: &Device) -> Self {
+		let num_queries = MAX_PASSES_PER_FRAME * 2;
+		let query_set = device.create_query_set(&QuerySetDescriptor {
+			label: Some("GPU Profiler Query Set"),
+			ty: QueryType::Timestamp,
+			count: num_queries,
+		});
+		let resolve_buffer = device.create_buffer(&BufferDescriptor {
+			label: Some("GPU Profiler Resolve Buffer"),
+			size: (num_queries * QUERY_SIZE) as u64,
+			usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+			mapped_at_creation: false,
+		});
+		let readback_buffer = device.create_buffer(&BufferDescriptor {
+			label: Some("GPU Profiler Readback Buffer"),
+			size: (num_queries * QUERY_SIZE) as u64,
+			usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+			mapped_at_creation: false,
+		});
+		Self {
+			query_set,
+			resolve_buffer,
+			readback_buffer,
+			passes: vec![],
+			state: Arc::new(Mutex::new(FrameState::Free)),
+		}
+	}
+}
+
+struct ProfiledPass {
+	label: String,
+	tracy_span: Option<tracy_client::GpuSpan>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameState {
+	/// Ready to be used for a new frame.
+	Free,
+	/// Waiting for the GPU to finish the frame.
+	Pending,
+	/// The timestamps are ready to be read.
+	Mapped,
+}
+
+/// Writes a timestamp with an empty compute pass and blocks until it's
+/// been read back. Used to sync the Tracy GPU timeline with the CPU.
+fn current_gpu_timestamp(device: &Device, queue: &Queue) -> u64 {
+	let query_set = device.create_query_set(&QuerySetDescriptor {
+		label: Some("GPU Profiler Calibration Query Set"),
+		ty: QueryType::Timestamp,
+		count: 1,
+	});
+	let resolve_buffer = device.create_buffer(&BufferDescriptor {
+		label: Some("GPU Profiler Calibration Resolve Buffer"),
+		size: QUERY_SIZE as u64,
+		usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+		mapped_at_creation: false,
+	});
+	let readback_buffer = device.create_buffer(&BufferDescriptor {
+		label: Some("GPU Profiler Calibration Readback Buffer"),
+		size: QUERY_SIZE as u64,
+		usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	});
+	let mut encoder = device.create_command_encoder(&Default::default());
+	encoder.begin_compute_pass(&ComputePassDescriptor {
+		label: Some("GPU Profiler Calibration Pass"),
+		timestamp_writes: Some(ComputePassTimestampWrites {
+			query_set: &query_set,
+			beginning_of_pass_write_index: Some(0),
+			end_of_pass_write_index: None,
+		}),
+	});
+	encoder.resolve_query_set(&query_set, 0..1, &resolve_buffer, 0);
+	encoder.copy_buffer_to_buffer(&resolve_buffer, 0, &readback_buffer, 0, QUERY_SIZE as u64);
+	encoder.map_buffer_on_submit(&readback_buffer, MapMode::Read, .., |result| {
+		result.expect("error mapping buffer");
+	});
+	queue.submit([encoder.finish()]);
+	device
+		.poll(PollType::wait_indefinitely())
+		.expect("error polling graphics device");
+	let timestamp = {
+		let view = readback_buffer
+			.get_mapped_range(..)
+			.expect("error mapping range");
+		bytemuck::pod_read_unaligned::<u64>(&view)
+	};
+	readback_buffer.unmap();
+	timestamp
+}
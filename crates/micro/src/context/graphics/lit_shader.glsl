@@ -0,0 +1,149 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aNormal;
+layout (location = 2) in vec2 aTexCoord;
+layout (location = 3) in vec4 aColor;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+	mat4 normalTransform;
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+layout (location = 2) out vec3 worldPosition;
+layout (location = 3) out vec3 worldNormal;
+
+void main()
+{
+    gl_Position = globalTransform * vec4(aPos, 1.0);
+    texCoord = aTexCoord;
+    vertexColor = aColor * blendColor;
+    worldPosition = (localTransform * vec4(aPos, 1.0)).xyz;
+    worldNormal = mat3(normalTransform) * aNormal;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+const uint LIGHT_KIND_DIRECTIONAL = 0;
+const uint LIGHT_KIND_POINT = 1;
+const uint LIGHT_KIND_SPOT = 2;
+
+struct Light {
+	// xyz = position, w = kind
+	vec4 positionAndKind;
+	// xyz = direction, w = range
+	vec4 directionAndRange;
+	// rgb = color, a = intensity
+	vec4 colorAndIntensity;
+	// x = cosine of inner cone angle, y = cosine of outer cone angle,
+	// z = whether the light uses the shadow map
+	vec4 cone;
+};
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+layout (location = 2) in vec3 worldPosition;
+layout (location = 3) in vec3 worldNormal;
+
+layout (binding = 1) uniform texture2D inTexture;
+layout (binding = 2) uniform sampler inSampler;
+
+layout (set = 1, binding = 0)
+uniform Material {
+	float specularStrength;
+	float shininess;
+};
+
+layout (set = 2, binding = 0)
+readonly buffer Lights {
+	vec4 ambientColor;
+	vec4 cameraPosition;
+	mat4 shadowViewProjection;
+	// x = whether the shadow map is used, y = depth bias, z = texel size
+	vec4 shadowParams;
+	uint numLights;
+	Light lights[];
+};
+
+layout (set = 3, binding = 0) uniform texture2D shadowMap;
+layout (set = 3, binding = 1) uniform sampler shadowMapSampler;
+
+out vec4 fragColor;
+
+float unpackDepth(vec3 packedDepth)
+{
+    return dot(packedDepth, vec3(1.0, 1.0 / 255.0, 1.0 / 65025.0));
+}
+
+float shadowFactor(vec3 normal, vec3 lightDirection)
+{
+    vec4 lightSpacePosition = shadowViewProjection * vec4(worldPosition, 1.0);
+    vec3 ndc = lightSpacePosition.xyz / lightSpacePosition.w;
+    vec2 uv = vec2(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0 || ndc.z > 1.0) {
+        return 1.0;
+    }
+    float bias = max(shadowParams.y * (1.0 - dot(normal, lightDirection)), shadowParams.y * 0.1);
+    float lit = 0.0;
+    for (int x = -1; x <= 1; x++) {
+        for (int y = -1; y <= 1; y++) {
+            vec2 offset = vec2(float(x), float(y)) * shadowParams.z;
+            vec3 packedDepth = textureLod(sampler2D(shadowMap, shadowMapSampler), uv + offset, 0.0).rgb;
+            lit += ndc.z - bias > unpackDepth(packedDepth) ? 0.0 : 1.0;
+        }
+    }
+    return lit / 9.0;
+}
+
+vec3 lightContribution(Light light, vec3 normal, vec3 viewDirection)
+{
+    uint kind = uint(light.positionAndKind.w);
+    vec3 lightDirection;
+    float attenuation = 1.0;
+    if (kind == LIGHT_KIND_DIRECTIONAL) {
+        lightDirection = normalize(-light.directionAndRange.xyz);
+        if (light.cone.z != 0.0 && shadowParams.x != 0.0) {
+            attenuation *= shadowFactor(normal, lightDirection);
+        }
+    } else {
+        vec3 toLight = light.positionAndKind.xyz - worldPosition;
+        float distanceToLight = length(toLight);
+        lightDirection = toLight / max(distanceToLight, 0.0001);
+        float falloff = clamp(1.0 - distanceToLight / light.directionAndRange.w, 0.0, 1.0);
+        attenuation *= falloff * falloff;
+        if (kind == LIGHT_KIND_SPOT) {
+            float theta = dot(lightDirection, normalize(-light.directionAndRange.xyz));
+            attenuation *= smoothstep(light.cone.y, light.cone.x, theta);
+        }
+    }
+    float diffuse = max(dot(normal, lightDirection), 0.0);
+    vec3 halfway = normalize(lightDirection + viewDirection);
+    float specular = diffuse > 0.0
+        ? pow(max(dot(normal, halfway), 0.0), shininess) * specularStrength
+        : 0.0;
+    return light.colorAndIntensity.rgb * light.colorAndIntensity.a * attenuation * (diffuse + specular);
+}
+
+void main()
+{
+    vec4 baseColor = texture(sampler2D(inTexture, inSampler), texCoord) * vertexColor;
+    if (baseColor.a == 0.0) discard;
+    vec3 normal = normalize(worldNormal);
+    vec3 viewDirection = normalize(cameraPosition.xyz - worldPosition);
+    vec3 light = ambientColor.rgb;
+    for (uint i = 0; i < numLights; i++) {
+        light += lightContribution(lights[i], normal, viewDirection);
+    }
+    fragColor = vec4(baseColor.rgb * light, baseColor.a);
+}
+
+#endif
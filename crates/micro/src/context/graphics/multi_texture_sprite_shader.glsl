@@ -0,0 +1,57 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec2 aTexCoord;
+layout (location = 2) in vec4 aColor;
+layout (location = 3) in uint aTextureIndex;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+layout (location = 2) flat out uint textureIndex;
+
+void main()
+{
+    gl_Position = globalTransform * vec4(aPos, 0.0, 1.0);
+    texCoord = aTexCoord;
+    vertexColor = aColor * blendColor;
+    textureIndex = aTextureIndex;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+layout (location = 2) flat in uint textureIndex;
+
+// TEXTURE_BINDINGS
+
+out vec4 fragColor;
+
+// Uses explicit gradients because the texture is picked per sprite,
+// which isn't uniform control flow.
+vec4 sampleTexture(vec2 coords, vec2 ddx, vec2 ddy)
+{
+    // TEXTURE_SAMPLES
+    return vec4(0.0);
+}
+
+void main()
+{
+    vec2 ddx = dFdx(texCoord);
+    vec2 ddy = dFdy(texCoord);
+    fragColor = sampleTexture(texCoord, ddx, ddy) * vertexColor;
+    if (fragColor.a == 0.0) discard;
+}
+
+#endif
@@ -0,0 +1,65 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec2 aTexCoord;
+layout (location = 2) in vec4 aColor;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+
+void main()
+{
+    gl_Position = globalTransform * vec4(aPos, 0.0, 1.0);
+    texCoord = aTexCoord;
+    vertexColor = aColor * blendColor;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+
+// the palette indices, stored in the red channel
+layout (binding = 1) uniform texture2D inTexture;
+layout (binding = 2) uniform sampler inSampler;
+
+layout (set = 1, binding = 0)
+uniform PaletteParams {
+	// the position of the first color of the palette within
+	// the palette texture
+	ivec2 paletteOffset;
+	// the number of colors in the palette
+	int paletteSize;
+};
+
+layout (set = 3, binding = 0) uniform texture2D paletteTexture;
+layout (set = 3, binding = 1) uniform sampler paletteSampler;
+
+out vec4 fragColor;
+
+void main()
+{
+    float value = texture(sampler2D(inTexture, inSampler), texCoord).r;
+    // indices past the end of the palette use the last color instead of
+    // reading outside the palette's region
+    int index = clamp(int(round(value * 255.0)), 0, max(paletteSize - 1, 0));
+    fragColor = texelFetch(
+        sampler2D(paletteTexture, paletteSampler),
+        paletteOffset + ivec2(index, 0),
+        0
+    ) * vertexColor;
+    if (fragColor.a == 0.0) discard;
+}
+
+#endif
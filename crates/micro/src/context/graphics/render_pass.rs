@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::graphics::{Canvas, RenderToCanvasSettings};
+use crate::graphics::{Canvas, RenderToCanvasSettings, texture::Texture};
 
 use super::DrawCommand;
 
@@ -29,6 +29,12 @@ pub(super) enum RenderPassKind {
 		canvas: Canvas,
 		settings: RenderToCanvasSettings,
 	},
+	/// Copies one texture to another between render passes. Has no
+	/// draw commands.
+	CopyTexture {
+		source: Texture,
+		destination: Texture,
+	},
 }
 
 impl Debug for RenderPassKind {
@@ -40,6 +46,7 @@ impl Debug for RenderPassKind {
 				.field("canvas", &format!("Canvas ('{}')", &canvas.label))
 				.field("settings", settings)
 				.finish(),
+			Self::CopyTexture { .. } => write!(f, "CopyTexture"),
 		}
 	}
 }
@@ -48,4 +55,6 @@ pub(super) struct CanvasRenderPass {
 	pub canvas: Canvas,
 	pub settings: RenderToCanvasSettings,
 	pub draw_commands: Vec<DrawCommand>,
+	/// How many masks are currently active for this canvas.
+	pub mask_depth: u8,
 }
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use image::RgbaImage;
+use tracing::error;
+use wgpu::TextureFormat;
+
+use crate::graphics::canvas::CanvasReadback;
+
+/// A copy of the window surface that will be saved to one or more files
+/// once it's been read back from the GPU.
+pub(crate) struct PendingScreenshot {
+	pub paths: Vec<PathBuf>,
+	pub readback: CanvasReadback,
+}
+
+impl PendingScreenshot {
+	/// Starts saving the screenshot on a worker thread if the pixels are
+	/// available. Returns `true` if the screenshot is done with.
+	pub fn try_save(&mut self) -> bool {
+		let Some(pixels) = self.readback.try_take() else {
+			return false;
+		};
+		let paths = std::mem::take(&mut self.paths);
+		let size = self.readback.size();
+		let format = self.readback.format();
+		std::thread::spawn(move || {
+			let _span = tracy_client::span!("save screenshot");
+			let Some(image) = surface_pixels_to_image(pixels, size.x, size.y, format) else {
+				error!("cannot save screenshots of a {format:?} surface");
+				return;
+			};
+			for path in paths {
+				if let Err(err) = image.save(&path) {
+					error!("error saving screenshot to {}: {}", path.display(), err);
+				}
+			}
+		});
+		true
+	}
+}
+
+fn surface_pixels_to_image(
+	mut pixels: Vec<u8>,
+	width: u32,
+	height: u32,
+	format: TextureFormat,
+) -> Option<RgbaImage> {
+	match format {
+		TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {}
+		TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+			for pixel in pixels.chunks_exact_mut(4) {
+				pixel.swap(0, 2);
+			}
+		}
+		_ => return None,
+	}
+	// the window is always opaque, but the surface's alpha channel
+	// isn't necessarily filled in
+	for pixel in pixels.chunks_exact_mut(4) {
+		pixel[3] = u8::MAX;
+	}
+	RgbaImage::from_raw(width, height, pixels)
+}
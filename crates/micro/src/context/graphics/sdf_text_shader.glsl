@@ -0,0 +1,150 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec2 aPos;
+layout (location = 1) in vec2 aTexCoord;
+layout (location = 2) in vec4 aColor;
+layout (location = 3) in vec4 aGlyphRect;
+layout (location = 4) in vec2 aPositionPerTexCoordX;
+layout (location = 5) in vec2 aPositionPerTexCoordY;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+};
+
+layout (set = 1, binding = 0)
+uniform TextEffects {
+	vec4 outlineColor;
+	vec4 shadowColor;
+	vec4 glowColor;
+	vec2 shadowOffset;
+	float outlineWidth;
+	float shadowSoftness;
+	float glowRadius;
+	float softness;
+	float spread;
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+layout (location = 2) out float blendAlpha;
+layout (location = 3) out vec2 localPosition;
+layout (location = 4) flat out vec4 glyphRect;
+
+void main()
+{
+    // grow the quad so effects that reach past the glyph's region of the
+    // atlas (like shadows with a large offset) aren't cut off
+    float shadowExtent = shadowColor.a > 0.0 ? length(shadowOffset) + shadowSoftness : 0.0;
+    float extent = max(max(outlineWidth, glowRadius), shadowExtent) + softness + 1.0;
+    vec2 direction = sign(aTexCoord - (aGlyphRect.xy + aGlyphRect.zw) / 2.0);
+    vec2 axisLengths = vec2(length(aPositionPerTexCoordX), length(aPositionPerTexCoordY));
+    vec2 texCoordOffset = all(greaterThan(axisLengths, vec2(0.0)))
+        ? direction * extent / axisLengths
+        : vec2(0.0);
+    vec2 position = aPos + mat2(aPositionPerTexCoordX, aPositionPerTexCoordY) * texCoordOffset;
+
+    gl_Position = globalTransform * vec4(position, 0.0, 1.0);
+    texCoord = aTexCoord + texCoordOffset;
+    vertexColor = aColor * blendColor;
+    blendAlpha = blendColor.a;
+    localPosition = position;
+    glyphRect = aGlyphRect;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+layout (location = 2) in float blendAlpha;
+layout (location = 3) in vec2 localPosition;
+layout (location = 4) flat in vec4 glyphRect;
+
+layout (binding = 1) uniform texture2D inTexture;
+layout (binding = 2) uniform sampler inSampler;
+
+layout (set = 1, binding = 0)
+uniform TextEffects {
+	vec4 outlineColor;
+	vec4 shadowColor;
+	vec4 glowColor;
+	vec2 shadowOffset;
+	float outlineWidth;
+	float shadowSoftness;
+	float glowRadius;
+	float softness;
+	float spread;
+};
+
+out vec4 fragColor;
+
+// samples outside the glyph's region of the atlas belong to other glyphs,
+// so they're treated as being as far from the glyph as possible
+float sampleDistance(vec2 coords)
+{
+    float distance = texture(sampler2D(inTexture, inSampler), coords).a;
+    bool inside = all(greaterThanEqual(coords, glyphRect.xy))
+        && all(lessThanEqual(coords, glyphRect.zw));
+    return inside ? distance : 0.0;
+}
+
+// blends a non-premultiplied color on top of another
+vec4 over(vec4 top, vec4 bottom)
+{
+    float alpha = top.a + bottom.a * (1.0 - top.a);
+    if (alpha <= 0.0) return vec4(0.0);
+    vec3 color = (top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / alpha;
+    return vec4(color, alpha);
+}
+
+void main()
+{
+    // effect sizes are given in the text's own pixels, so work out how
+    // texture coordinates and distances change per pixel of text
+    vec2 atlasSize = vec2(textureSize(sampler2D(inTexture, inSampler), 0));
+    mat2 texCoordDerivatives = mat2(dFdx(texCoord), dFdy(texCoord));
+    mat2 positionDerivatives = mat2(dFdx(localPosition), dFdy(localPosition));
+    float positionDeterminant = determinant(positionDerivatives);
+    mat2 texCoordsPerPixel = abs(positionDeterminant) > 1e-12
+        ? texCoordDerivatives * inverse(positionDerivatives)
+        : mat2(0.0);
+    mat2 texelsPerPixel = mat2(atlasSize.x, 0.0, 0.0, atlasSize.y) * texCoordsPerPixel;
+    float distancePerPixel = sqrt(abs(determinant(texelsPerPixel))) / (spread * 2.0);
+
+    // 0.5 is the edge of the glyph, higher values are inside the glyph
+    float distance = sampleDistance(texCoord);
+    float antialiasing = max(fwidth(distance) * 0.75, 0.0001);
+    float smoothing = antialiasing + softness * distancePerPixel;
+    vec4 color = vec4(0.0);
+
+    if (glowRadius > 0.0) {
+        float glowEdge = 0.5 - glowRadius * distancePerPixel;
+        float glow = smoothstep(glowEdge, 0.5, distance);
+        color = over(vec4(glowColor.rgb, glowColor.a * blendAlpha * glow), color);
+    }
+
+    if (shadowColor.a > 0.0) {
+        float shadowDistance = sampleDistance(texCoord - texCoordsPerPixel * shadowOffset);
+        float shadowSmoothing = antialiasing + shadowSoftness * distancePerPixel;
+        float shadow = smoothstep(0.5 - shadowSmoothing, 0.5 + shadowSmoothing, shadowDistance);
+        color = over(vec4(shadowColor.rgb, shadowColor.a * blendAlpha * shadow), color);
+    }
+
+    if (outlineWidth > 0.0) {
+        float outlineEdge = 0.5 - outlineWidth * distancePerPixel;
+        float outline = smoothstep(outlineEdge - smoothing, outlineEdge + smoothing, distance);
+        color = over(vec4(outlineColor.rgb, outlineColor.a * blendAlpha * outline), color);
+    }
+
+    float fill = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    fragColor = over(vec4(vertexColor.rgb, vertexColor.a * fill), color);
+    if (fragColor.a == 0.0) discard;
+}
+
+#endif
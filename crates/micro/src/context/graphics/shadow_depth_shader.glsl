@@ -0,0 +1,38 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec3 aPos;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+	mat4 normalTransform;
+};
+
+void main()
+{
+    gl_Position = globalTransform * vec4(aPos, 1.0);
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+out vec4 fragColor;
+
+vec3 packDepth(float depth)
+{
+    vec3 packedDepth = fract(depth * vec3(1.0, 255.0, 65025.0));
+    packedDepth -= packedDepth.yzz * vec3(1.0 / 255.0, 1.0 / 255.0, 0.0);
+    return packedDepth;
+}
+
+void main()
+{
+    fragColor = vec4(packDepth(gl_FragCoord.z), 1.0);
+}
+
+#endif
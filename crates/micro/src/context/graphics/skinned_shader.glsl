@@ -0,0 +1,57 @@
+#version 460
+
+#ifdef VERTEX
+
+layout (location = 0) in vec3 aPos;
+layout (location = 1) in vec3 aNormal;
+layout (location = 2) in vec2 aTexCoord;
+layout (location = 3) in vec4 aColor;
+layout (location = 4) in uvec4 aJoints;
+layout (location = 5) in vec4 aWeights;
+
+layout (binding = 0)
+uniform DrawParams {
+	mat4 globalTransform;
+	mat4 localTransform;
+	vec4 blendColor;
+	mat4 normalTransform;
+};
+
+layout (set = 2, binding = 0)
+readonly buffer JointMatrices {
+	mat4 jointMatrices[];
+};
+
+layout (location = 0) out vec2 texCoord;
+layout (location = 1) out vec4 vertexColor;
+
+void main()
+{
+    mat4 skinTransform = aWeights.x * jointMatrices[aJoints.x]
+        + aWeights.y * jointMatrices[aJoints.y]
+        + aWeights.z * jointMatrices[aJoints.z]
+        + aWeights.w * jointMatrices[aJoints.w];
+    gl_Position = globalTransform * skinTransform * vec4(aPos, 1.0);
+    texCoord = aTexCoord;
+    vertexColor = aColor * blendColor;
+}
+
+#endif
+
+#ifdef FRAGMENT
+
+layout (location = 0) in vec2 texCoord;
+layout (location = 1) in vec4 vertexColor;
+
+layout (binding = 1) uniform texture2D inTexture;
+layout (binding = 2) uniform sampler inSampler;
+
+out vec4 fragColor;
+
+void main()
+{
+    fragColor = texture(sampler2D(inTexture, inSampler), texCoord) * vertexColor;
+    if (fragColor.a == 0.0) discard;
+}
+
+#endif
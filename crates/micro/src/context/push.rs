@@ -22,7 +22,13 @@ pub struct Push {
 	/// Whether the depth buffer is used to occlude fragments.
 	pub enable_depth_testing: Option<bool>,
 	pub depth_bias_state: Option<DepthBiasState>,
-	/// A rectangular region to crop all drawing operations to.
+	/// A rectangular region to crop all drawing operations to, in pixels
+	/// of the current render target.
+	///
+	/// This ignores the current transform and replaces any scissor rect
+	/// that's already active. Use
+	/// [`Context::push_scissor`](crate::Context::push_scissor) to crop to a
+	/// region in the current transform's coordinates.
 	pub scissor_rect: Option<Option<URect>>,
 }
 
@@ -1,7 +1,12 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+	collections::HashMap,
+	hash::{Hash, Hasher},
+	path::Path,
+	sync::Arc,
+};
 
-use cosmic_text::{CacheKey, FontSystem, SwashCache, fontdb::Database};
-use etagere::{Allocation, BucketedAtlasAllocator, size2};
+use cosmic_text::{CacheKey, CacheKeyFlags, FontSystem, SwashCache, fontdb::Database};
+use etagere::{Allocation, BucketedAtlasAllocator, Size, size2};
 use glam::{IVec2, UVec2, ivec2};
 use image::RgbaImage;
 use tracing::warn;
@@ -9,20 +14,32 @@ use wgpu::{Device, FilterMode, Queue, TextureFormat};
 
 use crate::{
 	context::graphics::GraphicsContext,
-	graphics::texture::{InternalTextureSettings, Texture, TextureSettings},
+	graphics::{
+		text::GlyphAtlasStats,
+		texture::{InternalTextureSettings, Texture, TextureSettings},
+	},
 	math::IRect,
 };
 
-const STARTING_ATLAS_SIZE: u32 = 256;
-const MAX_ATLAS_SIZE: u32 = 8192;
+const STARTING_PAGE_SIZE: u32 = 256;
+const MAX_PAGE_SIZE: u32 = 4096;
+const MAX_PAGES: usize = 4;
 const PADDING: i32 = 1;
+/// The font size glyphs are rasterized at for signed distance field text.
+pub(crate) const SDF_FONT_SIZE: f32 = 48.0;
+/// The maximum distance from a glyph's outline (in pixels at
+/// [`SDF_FONT_SIZE`]) that the signed distance field covers.
+pub(crate) const SDF_SPREAD: i32 = 8;
 
 pub(crate) struct TextContext {
 	pub(crate) font_system: FontSystem,
 	swash_cache: SwashCache,
-	atlas_allocator: BucketedAtlasAllocator,
-	glyphs: HashMap<CacheKey, GlyphAllocation>,
-	pub(crate) texture: Texture,
+	pages: Vec<GlyphAtlasPage>,
+	glyphs: HashMap<GlyphKey, CachedGlyph>,
+	max_page_size: u32,
+	frame: u64,
+	num_rasterizations: u64,
+	num_evictions: u64,
 }
 
 impl TextContext {
@@ -30,27 +47,16 @@ impl TextContext {
 		Self {
 			font_system: FontSystem::new_with_locale_and_db("en-US".to_string(), Database::new()),
 			swash_cache: SwashCache::new(),
-			atlas_allocator: BucketedAtlasAllocator::new(size2(
-				STARTING_ATLAS_SIZE as i32,
-				STARTING_ATLAS_SIZE as i32,
-			)),
-			glyphs: HashMap::new(),
-			texture: Texture::new(
+			pages: vec![GlyphAtlasPage::new(
 				&graphics.device,
 				&graphics.queue,
-				UVec2::splat(STARTING_ATLAS_SIZE),
-				1,
-				None,
-				TextureSettings {
-					minifying_filter: FilterMode::Linear,
-					magnifying_filter: FilterMode::Linear,
-					..Default::default()
-				},
-				InternalTextureSettings {
-					format: TextureFormat::Rgba8UnormSrgb,
-					sample_count: 1,
-				},
-			),
+				STARTING_PAGE_SIZE,
+			)],
+			glyphs: HashMap::new(),
+			max_page_size: MAX_PAGE_SIZE.min(graphics.device.limits().max_texture_dimension_2d),
+			frame: 0,
+			num_rasterizations: 0,
+			num_evictions: 0,
 		}
 	}
 
@@ -62,27 +68,117 @@ impl TextContext {
 		self.font_system.db_mut().load_fonts_dir(path);
 	}
 
+	/// Returns the current texture for a page of the glyph atlas.
+	pub fn page_texture(&self, page: usize) -> &Texture {
+		&self.pages[page].texture
+	}
+
+	/// Advances the frame counter used to find the least recently
+	/// used glyphs.
+	pub fn end_frame(&mut self) {
+		self.frame += 1;
+	}
+
+	pub fn stats(&self) -> GlyphAtlasStats {
+		GlyphAtlasStats {
+			page_sizes: self
+				.pages
+				.iter()
+				.map(|page| page.allocator.size().width as u32)
+				.collect(),
+			num_glyphs: self.glyphs.len(),
+			num_glyphs_in_use: self
+				.glyphs
+				.values()
+				.filter(|glyph| glyph.is_in_use())
+				.count(),
+			used_area: self
+				.pages
+				.iter()
+				.map(|page| page.allocator.allocated_space() as u64)
+				.sum(),
+			total_area: self
+				.pages
+				.iter()
+				.map(|page| page.allocator.size().area() as u64)
+				.sum(),
+			num_rasterizations: self.num_rasterizations,
+			num_evictions: self.num_evictions,
+		}
+	}
+
 	pub fn glyph_rect(
 		&mut self,
 		device: &Device,
 		queue: &Queue,
 		cache_key: CacheKey,
 	) -> Option<GlyphInfo> {
-		self.glyphs
-			.get(&cache_key)
-			.map(|allocation| GlyphInfo {
-				texture_rect: etagere_rectangle_to_irect(allocation.allocation.rectangle),
-				offset: allocation.offset,
-			})
-			.or_else(|| self.insert_glyph_rect(device, queue, cache_key))
+		let key = GlyphKey::Normal(cache_key);
+		if let Some(info) = self.cached_glyph_info(key) {
+			return Some(info);
+		}
+		let (image, offset) = self.rasterize(cache_key)?;
+		self.insert_glyph(device, queue, key, &image, offset)
 	}
 
-	fn insert_glyph_rect(
+	/// Returns the location of a signed distance field version of a glyph
+	/// in the atlas, rasterizing it if needed.
+	///
+	/// The glyph is always rasterized at [`SDF_FONT_SIZE`], so the
+	/// `cache_key`'s font size and subpixel offsets are ignored. The
+	/// returned offset is in pixels at [`SDF_FONT_SIZE`].
+	pub fn sdf_glyph_rect(
 		&mut self,
 		device: &Device,
 		queue: &Queue,
 		cache_key: CacheKey,
 	) -> Option<GlyphInfo> {
+		let (cache_key, _, _) = CacheKey::new(
+			cache_key.font_id,
+			cache_key.glyph_id,
+			SDF_FONT_SIZE,
+			(0.0, 0.0),
+			cache_key.font_weight,
+			cache_key.flags | CacheKeyFlags::DISABLE_HINTING,
+		);
+		let key = GlyphKey::Sdf(cache_key);
+		if let Some(info) = self.cached_glyph_info(key) {
+			return Some(info);
+		}
+		let (image, offset) = self.rasterize(cache_key)?;
+		let image = signed_distance_field(&image);
+		let offset = offset - IVec2::splat(SDF_SPREAD);
+		self.insert_glyph(device, queue, key, &image, offset)
+	}
+
+	fn cached_glyph_info(&mut self, key: GlyphKey) -> Option<GlyphInfo> {
+		let glyph = self.glyphs.get_mut(&key)?;
+		glyph.last_used_frame = self.frame;
+		Some(glyph.info())
+	}
+
+	fn insert_glyph(
+		&mut self,
+		device: &Device,
+		queue: &Queue,
+		key: GlyphKey,
+		image: &RgbaImage,
+		offset: IVec2,
+	) -> Option<GlyphInfo> {
+		let (page, allocation) = self.allocate(device, queue, image)?;
+		let glyph = CachedGlyph {
+			page,
+			allocation,
+			offset,
+			last_used_frame: self.frame,
+			lease: GlyphLease::default(),
+		};
+		let info = glyph.info();
+		self.glyphs.insert(key, glyph);
+		Some(info)
+	}
+
+	fn rasterize(&mut self, cache_key: CacheKey) -> Option<(RgbaImage, IVec2)> {
 		let mut min_x: Option<i32> = None;
 		let mut min_y: Option<i32> = None;
 		let mut max_x: Option<i32> = None;
@@ -123,44 +219,215 @@ impl TextContext {
 				);
 			},
 		);
-		let TryAllocateResult {
-			allocation,
-			new_size,
-		} = try_allocate(
-			&mut self.atlas_allocator,
-			width as i32 + PADDING * 2,
-			height as i32 + PADDING * 2,
+		self.num_rasterizations += 1;
+		Some((image, ivec2(min_x, min_y)))
+	}
+
+	/// Finds space for a glyph in the atlas and copies the glyph's image
+	/// into it.
+	///
+	/// Existing pages are grown first, then new pages are added up to
+	/// [`MAX_PAGES`]. Once there's no room for more pages, glyphs that
+	/// aren't used by any [`Text`](crate::graphics::text::Text) are
+	/// evicted from one page at a time, starting with the least recently
+	/// used ones.
+	fn allocate(
+		&mut self,
+		device: &Device,
+		queue: &Queue,
+		image: &RgbaImage,
+	) -> Option<(usize, Allocation)> {
+		let _span = tracy_client::span!();
+		let size = size2(
+			image.width() as i32 + PADDING * 2,
+			image.height() as i32 + PADDING * 2,
 		);
-		let allocation = allocation?;
-		if let Some(new_size) = new_size {
-			self.texture = self
-				.texture
-				.resized_inner(device, queue, UVec2::splat(new_size));
+		if size.width as u32 > self.max_page_size || size.height as u32 > self.max_page_size {
+			warn!("glyph is too large to fit in the text atlas");
+			return None;
 		}
+		let page_and_allocation = (0..self.pages.len())
+			.find_map(|page| {
+				self.pages[page]
+					.allocate(device, queue, size, self.max_page_size)
+					.map(|allocation| (page, allocation))
+			})
+			.or_else(|| {
+				if self.pages.len() >= MAX_PAGES {
+					return None;
+				}
+				let mut page = GlyphAtlasPage::new(device, queue, STARTING_PAGE_SIZE);
+				let allocation = page.allocate(device, queue, size, self.max_page_size)?;
+				self.pages.push(page);
+				Some((self.pages.len() - 1, allocation))
+			})
+			.or_else(|| self.evict_and_allocate(size));
+		let Some((page, allocation)) = page_and_allocation else {
+			warn!("no more space in text atlas");
+			return None;
+		};
 		let rectangle =
 			etagere_rectangle_to_irect(allocation.rectangle).padded((-PADDING, -PADDING));
-		let offset = ivec2(min_x, min_y);
-		self.texture
-			.replace_inner(queue, rectangle.top_left.as_uvec2(), &image);
-		self.glyphs
-			.insert(cache_key, GlyphAllocation { allocation, offset });
-		Some(GlyphInfo {
-			texture_rect: rectangle,
-			offset,
-		})
+		self.pages[page]
+			.texture
+			.replace_inner(queue, rectangle.top_left.as_uvec2(), image);
+		Some((page, allocation))
+	}
+
+	/// Evicts unused glyphs until there's room for a glyph of the
+	/// given `size`.
+	///
+	/// Glyphs are only evicted from one page at a time, so space is freed
+	/// up in one place instead of leaving gaps across every page. Pages are
+	/// tried in order of their least recently used glyph.
+	fn evict_and_allocate(&mut self, size: Size) -> Option<(usize, Allocation)> {
+		let _span = tracy_client::span!();
+		let mut evictable_glyphs = vec![vec![]; self.pages.len()];
+		for (key, glyph) in &self.glyphs {
+			if !glyph.is_in_use() {
+				evictable_glyphs[glyph.page].push((glyph.last_used_frame, *key));
+			}
+		}
+		for glyphs in &mut evictable_glyphs {
+			glyphs.sort_by_key(|(last_used_frame, _)| *last_used_frame);
+		}
+		let mut pages = (0..self.pages.len())
+			.filter(|&page| !evictable_glyphs[page].is_empty())
+			.collect::<Vec<_>>();
+		pages.sort_by_key(|&page| evictable_glyphs[page][0].0);
+		for page in pages {
+			for &(_, key) in &evictable_glyphs[page] {
+				let glyph = self
+					.glyphs
+					.remove(&key)
+					.expect("glyph should be in the cache");
+				let allocator = &mut self.pages[page].allocator;
+				allocator.deallocate(glyph.allocation.id);
+				self.num_evictions += 1;
+				if let Some(allocation) = allocator.allocate(size) {
+					return Some((page, allocation));
+				}
+			}
+		}
+		None
 	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct GlyphInfo {
+	pub page: usize,
 	pub texture_rect: IRect,
 	pub offset: IVec2,
+	/// Keeps the glyph from being evicted from the atlas while it's held.
+	pub lease: GlyphLease,
+}
+
+/// A handle that keeps a glyph from being evicted from the atlas.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GlyphLease(Arc<()>);
+
+impl PartialEq for GlyphLease {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for GlyphLease {}
+
+impl Hash for GlyphLease {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		Arc::as_ptr(&self.0).hash(state);
+	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-struct GlyphAllocation {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GlyphKey {
+	Normal(CacheKey),
+	Sdf(CacheKey),
+}
+
+#[derive(Debug)]
+struct CachedGlyph {
+	page: usize,
 	allocation: Allocation,
 	offset: IVec2,
+	last_used_frame: u64,
+	lease: GlyphLease,
+}
+
+impl CachedGlyph {
+	fn info(&self) -> GlyphInfo {
+		GlyphInfo {
+			page: self.page,
+			texture_rect: etagere_rectangle_to_irect(self.allocation.rectangle)
+				.padded((-PADDING, -PADDING)),
+			offset: self.offset,
+			lease: self.lease.clone(),
+		}
+	}
+
+	fn is_in_use(&self) -> bool {
+		Arc::strong_count(&self.lease.0) > 1
+	}
+}
+
+struct GlyphAtlasPage {
+	allocator: BucketedAtlasAllocator,
+	texture: Texture,
+}
+
+impl GlyphAtlasPage {
+	fn new(device: &Device, queue: &Queue, size: u32) -> Self {
+		Self {
+			allocator: BucketedAtlasAllocator::new(size2(size as i32, size as i32)),
+			texture: Texture::new(
+				device,
+				queue,
+				UVec2::splat(size),
+				1,
+				None,
+				TextureSettings {
+					minifying_filter: FilterMode::Linear,
+					magnifying_filter: FilterMode::Linear,
+					..Default::default()
+				},
+				InternalTextureSettings {
+					format: TextureFormat::Rgba8UnormSrgb,
+					sample_count: 1,
+				},
+			),
+		}
+	}
+
+	/// Allocates space on the page, growing the page if needed.
+	///
+	/// Growing the page replaces the texture with a larger one, so
+	/// [`Text`](crate::graphics::text::Text)s created before that keep
+	/// drawing from the old texture.
+	fn allocate(
+		&mut self,
+		device: &Device,
+		queue: &Queue,
+		size: Size,
+		max_size: u32,
+	) -> Option<Allocation> {
+		let mut allocation = self.allocator.allocate(size);
+		let mut grown = false;
+		while allocation.is_none() && (self.allocator.size().width as u32) < max_size {
+			let next_size = (self.allocator.size().width as u32 * 2).min(max_size) as i32;
+			self.allocator.grow(size2(next_size, next_size));
+			grown = true;
+			allocation = self.allocator.allocate(size);
+		}
+		if grown {
+			self.texture = self.texture.resized_inner(
+				device,
+				queue,
+				UVec2::splat(self.allocator.size().width as u32),
+			);
+		}
+		allocation
+	}
 }
 
 fn etagere_rectangle_to_irect(rectangle: etagere::Rectangle) -> IRect {
@@ -170,32 +437,50 @@ fn etagere_rectangle_to_irect(rectangle: etagere::Rectangle) -> IRect {
 	)
 }
 
-fn try_allocate(
-	allocator: &mut BucketedAtlasAllocator,
-	width: i32,
-	height: i32,
-) -> TryAllocateResult {
-	let mut new_size = None;
-	loop {
-		if let Some(allocation) = allocator.allocate(size2(width, height)) {
-			return TryAllocateResult {
-				allocation: Some(allocation),
-				new_size,
-			};
-		}
-		if allocator.size().width as u32 == MAX_ATLAS_SIZE {
-			warn!("no more space in text atlas");
-			return TryAllocateResult {
-				allocation: None,
-				new_size: None,
-			};
+/// Converts a rasterized glyph to a signed distance field with
+/// [`SDF_SPREAD`] pixels of padding on each side.
+///
+/// The distance is stored in the alpha channel, where 0.5 is the outline
+/// of the glyph, 1.0 is [`SDF_SPREAD`] pixels inside the glyph, and 0.0 is
+/// [`SDF_SPREAD`] pixels outside the glyph.
+fn signed_distance_field(image: &RgbaImage) -> RgbaImage {
+	let _span = tracy_client::span!();
+	let width = image.width() as i32 + SDF_SPREAD * 2;
+	let height = image.height() as i32 + SDF_SPREAD * 2;
+	let inside = |x: i32, y: i32| {
+		let x = x - SDF_SPREAD;
+		let y = y - SDF_SPREAD;
+		x >= 0
+			&& y >= 0 && x < image.width() as i32
+			&& y < image.height() as i32
+			&& image.get_pixel(x as u32, y as u32)[3] >= 128
+	};
+	let mut sdf = RgbaImage::new(width as u32, height as u32);
+	for y in 0..height {
+		for x in 0..width {
+			let is_inside = inside(x, y);
+			// the closest pixel with the opposite state is within the
+			// spread, so only that area needs to be searched
+			let mut closest_distance_squared = (SDF_SPREAD * SDF_SPREAD) as f32;
+			for search_y in (y - SDF_SPREAD).max(0)..=(y + SDF_SPREAD).min(height - 1) {
+				for search_x in (x - SDF_SPREAD).max(0)..=(x + SDF_SPREAD).min(width - 1) {
+					if inside(search_x, search_y) == is_inside {
+						continue;
+					}
+					let distance_squared = ((search_x - x).pow(2) + (search_y - y).pow(2)) as f32;
+					closest_distance_squared = closest_distance_squared.min(distance_squared);
+				}
+			}
+			// the outline is halfway between the two closest pixels
+			let distance = closest_distance_squared.sqrt() - 0.5;
+			let signed_distance = if is_inside { distance } else { -distance };
+			let value = 0.5 + signed_distance / (SDF_SPREAD as f32 * 2.0);
+			sdf.put_pixel(
+				x as u32,
+				y as u32,
+				image::Rgba([255, 255, 255, (value.clamp(0.0, 1.0) * 255.0).round() as u8]),
+			);
 		}
-		allocator.grow(allocator.size() * 2);
-		new_size = Some(allocator.size().width as u32);
 	}
-}
-
-struct TryAllocateResult {
-	allocation: Option<Allocation>,
-	new_size: Option<u32>,
+	sdf
 }
@@ -3,24 +3,36 @@
 mod blend_mode;
 mod camera_3d;
 pub mod canvas;
+mod gpu_timings;
+mod instance_buffer;
 mod into_index_range;
 mod into_instance_range;
 mod into_scale;
+pub mod lighting;
+mod mask;
 pub mod mesh;
+mod nine_slice;
+pub mod particles;
 mod shader;
+pub mod skeletal_animation;
 pub mod sprite_batch;
 mod stencil;
 mod storage_buffer;
 pub mod text;
 pub mod texture;
+pub mod texture_atlas;
 mod vertex;
 
 pub use blend_mode::*;
 pub use camera_3d::*;
-pub use canvas::{Canvas, CanvasSettings, RenderToCanvasSettings};
+pub use canvas::{Canvas, CanvasSettings, RenderGraph, RenderGraphPass, RenderToCanvasSettings};
+pub use gpu_timings::*;
+pub use instance_buffer::*;
 pub use into_index_range::*;
 pub use into_instance_range::*;
 pub use into_scale::*;
+pub use mask::*;
+pub use nine_slice::*;
 pub use shader::*;
 pub use stencil::*;
 pub use storage_buffer::*;
@@ -124,5 +136,19 @@ macro_rules! standard_draw_param_methods {
 			new.blend_mode = blend_mode;
 			new
 		}
+
+		/// Sets the palette to look up colors in when drawing. Only useful
+		/// for indexed textures created with `Texture::from_indices`.
+		///
+		/// Drawing with a palette uses a built-in shader instead of the
+		/// current one.
+		pub fn palette<'a>(
+			&self,
+			palette: impl Into<Option<&'a $crate::graphics::texture::Texture>>,
+		) -> Self {
+			let mut new = self.clone();
+			new.palette = palette.into().cloned().map(Into::into);
+			new
+		}
 	};
 }
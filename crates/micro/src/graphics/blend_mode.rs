@@ -1,10 +1,19 @@
+#[cfg(test)]
+mod test;
+
 use wgpu::{BlendComponent, BlendFactor, BlendOperation, BlendState};
 
 /// Determines how the pixels written by a drawing operation interact with
 /// the pixels already at the same place.
 ///
-/// These are the same as the corresponding blend modes in
-/// [LÖVE](https://love2d.org/wiki/BlendMode).
+/// [`Alpha`](Self::Alpha), [`Add`](Self::Add), [`Subtract`](Self::Subtract),
+/// and [`Multiply`](Self::Multiply) are the same as the corresponding blend
+/// modes in [LÖVE](https://love2d.org/wiki/BlendMode).
+///
+/// [`Overlay`](Self::Overlay) and [`SoftLight`](Self::SoftLight) can't be
+/// done with the GPU's blending hardware, so they're done in a shader
+/// instead, which only works when drawing to a [`Canvas`](crate::graphics::Canvas).
+/// See [`is_shader_based`](Self::is_shader_based) for details.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlendMode {
@@ -16,10 +25,65 @@ pub enum BlendMode {
 	Subtract(BlendAlphaMode),
 	/// Colors are multiplied by what's already on the surface.
 	Multiply,
+	/// Colors are inverted, multiplied by the inverted colors already on
+	/// the surface, and then inverted again, which brightens the surface.
+	///
+	/// The colors being drawn should have premultiplied alpha.
+	Screen,
+	/// The color of each pixel becomes the lighter of the color being drawn
+	/// and the color already on the surface. Alpha is blended the same way
+	/// as [`BlendMode::Alpha`].
+	Lighten,
+	/// The color of each pixel becomes the darker of the color being drawn
+	/// and the color already on the surface. Alpha is blended the same way
+	/// as [`BlendMode::Alpha`].
+	Darken,
+	/// What's drawn replaces what's already on the surface, including
+	/// the alpha component.
+	Replace,
+	/// Each component, including alpha, becomes the smaller of the value
+	/// being drawn and the value already on the surface.
+	Min,
+	/// Each component, including alpha, becomes the larger of the value
+	/// being drawn and the value already on the surface.
+	Max,
+	/// Dark parts of the surface are multiplied by the colors being drawn
+	/// and light parts are screened, which increases contrast.
+	///
+	/// This is done in a shader. See [`is_shader_based`](Self::is_shader_based).
+	Overlay(BlendAlphaMode),
+	/// Like [`BlendMode::Overlay`], but softer, as if the surface was lit
+	/// by a diffuse light with the colors being drawn.
+	///
+	/// This is done in a shader. See [`is_shader_based`](Self::is_shader_based).
+	SoftLight(BlendAlphaMode),
+	/// A custom blend state for the GPU's blending hardware.
+	Custom(BlendState),
 }
 
 impl BlendMode {
-	pub(crate) fn to_blend_state(self) -> BlendState {
+	/// Returns `true` if this blend mode is done in a shader instead of
+	/// with the GPU's blending hardware.
+	///
+	/// Shader-based blend modes need to read the pixels that are already
+	/// on the surface, so they only work when drawing to a
+	/// [`Canvas`](crate::graphics::Canvas). Each draw using one of these
+	/// blend modes copies the canvas, so they're much slower than other
+	/// blend modes. The current shader is replaced with the blending shader,
+	/// and only meshes using the default vertex type are supported.
+	///
+	/// When drawing to the window surface, these blend modes fall back to
+	/// [`BlendMode::Alpha`].
+	pub fn is_shader_based(self) -> bool {
+		matches!(self, BlendMode::Overlay(_) | BlendMode::SoftLight(_))
+	}
+
+	/// Returns the state of the GPU's blending hardware used for this
+	/// blend mode.
+	///
+	/// For [shader-based](Self::is_shader_based) blend modes, this is
+	/// [`BlendState::REPLACE`], since the shader does the blending.
+	pub fn to_blend_state(self) -> BlendState {
 		match self {
 			BlendMode::Alpha(blend_alpha_mode) => BlendState {
 				color: BlendComponent {
@@ -78,6 +142,58 @@ impl BlendMode {
 					operation: BlendOperation::Add,
 				},
 			},
+			BlendMode::Screen => BlendState {
+				color: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::OneMinusSrc,
+					operation: BlendOperation::Add,
+				},
+				alpha: BlendComponent::OVER,
+			},
+			BlendMode::Lighten => BlendState {
+				color: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Max,
+				},
+				alpha: BlendComponent::OVER,
+			},
+			BlendMode::Darken => BlendState {
+				color: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Min,
+				},
+				alpha: BlendComponent::OVER,
+			},
+			BlendMode::Min => BlendState {
+				color: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Min,
+				},
+				alpha: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Min,
+				},
+			},
+			BlendMode::Max => BlendState {
+				color: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Max,
+				},
+				alpha: BlendComponent {
+					src_factor: BlendFactor::One,
+					dst_factor: BlendFactor::One,
+					operation: BlendOperation::Max,
+				},
+			},
+			BlendMode::Replace | BlendMode::Overlay(_) | BlendMode::SoftLight(_) => {
+				BlendState::REPLACE
+			}
+			BlendMode::Custom(blend_state) => blend_state,
 		}
 	}
 }
@@ -0,0 +1,85 @@
+use wgpu::{BlendComponent, BlendFactor, BlendOperation, BlendState};
+
+use crate::graphics::{BlendAlphaMode, BlendMode};
+
+#[test]
+fn screen() {
+	assert_eq!(
+		BlendMode::Screen.to_blend_state(),
+		BlendState {
+			color: BlendComponent {
+				src_factor: BlendFactor::One,
+				dst_factor: BlendFactor::OneMinusSrc,
+				operation: BlendOperation::Add,
+			},
+			alpha: BlendComponent::OVER,
+		}
+	);
+}
+
+#[test]
+fn lighten_and_darken() {
+	let lighten = BlendMode::Lighten.to_blend_state();
+	let darken = BlendMode::Darken.to_blend_state();
+	assert_eq!(lighten.color.operation, BlendOperation::Max);
+	assert_eq!(darken.color.operation, BlendOperation::Min);
+	for blend_state in [lighten, darken] {
+		assert_eq!(blend_state.color.src_factor, BlendFactor::One);
+		assert_eq!(blend_state.color.dst_factor, BlendFactor::One);
+		// alpha is blended the same way as the alpha blend mode
+		assert_eq!(
+			blend_state.alpha,
+			BlendMode::Alpha(BlendAlphaMode::AlphaMultiply)
+				.to_blend_state()
+				.alpha
+		);
+	}
+}
+
+#[test]
+fn min_and_max_include_alpha() {
+	let min = BlendMode::Min.to_blend_state();
+	let max = BlendMode::Max.to_blend_state();
+	for (blend_state, operation) in [(min, BlendOperation::Min), (max, BlendOperation::Max)] {
+		let component = BlendComponent {
+			src_factor: BlendFactor::One,
+			dst_factor: BlendFactor::One,
+			operation,
+		};
+		assert_eq!(
+			blend_state,
+			BlendState {
+				color: component,
+				alpha: component,
+			}
+		);
+	}
+}
+
+#[test]
+fn shader_based_blend_modes_replace() {
+	for blend_mode in [
+		BlendMode::Overlay(BlendAlphaMode::AlphaMultiply),
+		BlendMode::Overlay(BlendAlphaMode::Premultiplied),
+		BlendMode::SoftLight(BlendAlphaMode::AlphaMultiply),
+		BlendMode::SoftLight(BlendAlphaMode::Premultiplied),
+	] {
+		assert!(blend_mode.is_shader_based());
+		assert_eq!(blend_mode.to_blend_state(), BlendState::REPLACE);
+	}
+	assert!(!BlendMode::Screen.is_shader_based());
+	assert!(!BlendMode::Replace.is_shader_based());
+}
+
+#[test]
+fn custom() {
+	let blend_state = BlendState {
+		color: BlendComponent {
+			src_factor: BlendFactor::DstAlpha,
+			dst_factor: BlendFactor::Zero,
+			operation: BlendOperation::Subtract,
+		},
+		alpha: BlendComponent::REPLACE,
+	};
+	assert_eq!(BlendMode::Custom(blend_state).to_blend_state(), blend_state);
+}
@@ -165,7 +165,7 @@ impl Camera3d {
 		})
 	}
 
-	fn undo_2d_coordinate_system_transform(ctx: &Context) -> Mat4 {
+	pub(crate) fn undo_2d_coordinate_system_transform(ctx: &Context) -> Mat4 {
 		let current_render_target_size = ctx.current_render_target_size();
 		(Mat4::from_translation(Vec3::new(-1.0, 1.0, 0.0))
 			* Mat4::from_scale(Vec3::new(
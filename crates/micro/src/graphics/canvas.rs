@@ -1,5 +1,11 @@
 //! Types for drawing to off-screen render targets.
 
+mod readback;
+mod render_graph;
+
+pub use readback::*;
+pub use render_graph::*;
+
 use std::ops::{Deref, DerefMut};
 
 use glam::{Mat4, UVec2, Vec2};
@@ -39,6 +45,8 @@ pub struct Canvas {
 	pub color: LinSrgba,
 	/// The blend mode to use when drawing this canvas.
 	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this canvas.
+	pub palette: Option<Texture>,
 }
 
 impl Canvas {
@@ -80,6 +88,11 @@ impl Canvas {
 		}
 	}
 
+	/// Reads the pixels of the canvas, blocking until the GPU has copied
+	/// them. Only canvases created with [`CanvasSettings::readable`] can be
+	/// read this way.
+	///
+	/// Prefer [`Canvas::read_async`], which doesn't stall the frame.
 	pub fn read<T>(&self, ctx: &Context, f: impl FnOnce(&[u8]) -> T) -> T {
 		let bytes_per_pixel = self
 			.format
@@ -136,6 +149,21 @@ impl Canvas {
 		result
 	}
 
+	/// Starts reading the pixels of the canvas without blocking.
+	///
+	/// The pixels are copied at the end of the current frame, after
+	/// everything has been drawn, and can be taken from the returned
+	/// [`CanvasReadback`] once it's ready, usually a frame or two later.
+	/// This works on any canvas, whether or not it's
+	/// [`readable`](CanvasSettings::readable).
+	pub fn read_async(&self, ctx: &mut Context) -> CanvasReadback {
+		let _span = tracy_client::span!();
+		let readback = CanvasReadback::new(&ctx.graphics.device, self.size(), self.format);
+		ctx.graphics
+			.queue_readback(self.drawable_texture(), readback.clone());
+		readback
+	}
+
 	/// Sets future drawing operations to happen on this canvas instead of the
 	/// window. Returns an object which, when dropped, sets the render
 	/// target back to the window.
@@ -157,6 +185,7 @@ impl Canvas {
 			.transformed(self.transform)
 			.color(self.color)
 			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
 			.draw(ctx)
 	}
 
@@ -239,6 +268,7 @@ impl Canvas {
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::Alpha(BlendAlphaMode::Premultiplied),
+			palette: None,
 		}
 	}
 }
@@ -266,6 +296,17 @@ pub struct CanvasSettings {
 	pub readable: bool,
 }
 
+impl CanvasSettings {
+	/// Returns `true` if canvases created with these settings can be used
+	/// in place of canvases created with the `other` settings.
+	pub(crate) fn is_compatible_with(&self, other: &Self) -> bool {
+		self.texture_settings == other.texture_settings
+			&& self.sample_count == other.sample_count
+			&& self.format == other.format
+			&& self.readable == other.readable
+	}
+}
+
 impl Default for CanvasSettings {
 	fn default() -> Self {
 		Self {
@@ -0,0 +1,131 @@
+use std::sync::{Arc, Mutex};
+
+use glam::UVec2;
+use wgpu::{
+	Buffer, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, CommandEncoder, Device, Extent3d, MapMode,
+	TexelCopyBufferInfo, TexelCopyBufferLayout, TextureFormat, wgt::BufferDescriptor,
+};
+
+/// A pending copy of a canvas's pixels from the GPU, created by
+/// [`Canvas::read_async`](super::Canvas::read_async).
+///
+/// The pixels are copied at the end of the frame the readback was
+/// created on and are usually available a frame or two later.
+/// This can be cheaply cloned.
+#[derive(Debug, Clone)]
+pub struct CanvasReadback {
+	inner: Arc<ReadbackInner>,
+}
+
+impl CanvasReadback {
+	pub(crate) fn new(device: &Device, size: UVec2, format: TextureFormat) -> Self {
+		let bytes_per_pixel = format
+			.block_copy_size(None)
+			.expect("could not get bytes per pixel");
+		let padded_bytes_per_row = (bytes_per_pixel * size.x)
+			.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+			.max(COPY_BYTES_PER_ROW_ALIGNMENT);
+		let buffer = device.create_buffer(&BufferDescriptor {
+			label: Some("Canvas Readback Buffer"),
+			size: padded_bytes_per_row as u64 * size.y as u64,
+			usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+			mapped_at_creation: false,
+		});
+		Self {
+			inner: Arc::new(ReadbackInner {
+				buffer,
+				size,
+				format,
+				bytes_per_pixel,
+				padded_bytes_per_row,
+				state: Mutex::new(ReadbackState::Pending),
+			}),
+		}
+	}
+
+	/// Returns the size of the region being read in pixels.
+	pub fn size(&self) -> UVec2 {
+		self.inner.size
+	}
+
+	/// Returns the format of the pixels being read.
+	pub fn format(&self) -> TextureFormat {
+		self.inner.format
+	}
+
+	/// Returns `true` if the pixels have been copied from the GPU and
+	/// haven't been taken yet.
+	pub fn is_ready(&self) -> bool {
+		*self.inner.state.lock().unwrap() == ReadbackState::Mapped
+	}
+
+	/// Returns the pixels, row by row with no padding, if they've been
+	/// copied from the GPU.
+	///
+	/// The pixels can only be taken once. After that, this always
+	/// returns `None`.
+	pub fn try_take(&self) -> Option<Vec<u8>> {
+		let mut state = self.inner.state.lock().unwrap();
+		if *state != ReadbackState::Mapped {
+			return None;
+		}
+		let unpadded_bytes_per_row = (self.inner.bytes_per_pixel * self.inner.size.x) as usize;
+		let mut pixels = Vec::with_capacity(unpadded_bytes_per_row * self.inner.size.y as usize);
+		{
+			let view = self
+				.inner
+				.buffer
+				.get_mapped_range(..)
+				.expect("error mapping range");
+			for row in view.chunks_exact(self.inner.padded_bytes_per_row as usize) {
+				pixels.extend_from_slice(&row[..unpadded_bytes_per_row]);
+			}
+		}
+		self.inner.buffer.unmap();
+		*state = ReadbackState::Taken;
+		Some(pixels)
+	}
+
+	/// Records a copy of the `texture` into the readback buffer, which will
+	/// be mapped after the `encoder`'s commands are submitted.
+	pub(crate) fn record_copy(&self, encoder: &mut CommandEncoder, texture: &wgpu::Texture) {
+		encoder.copy_texture_to_buffer(
+			texture.as_image_copy(),
+			TexelCopyBufferInfo {
+				buffer: &self.inner.buffer,
+				layout: TexelCopyBufferLayout {
+					offset: 0,
+					bytes_per_row: Some(self.inner.padded_bytes_per_row),
+					rows_per_image: Some(self.inner.size.y),
+				},
+			},
+			Extent3d {
+				width: self.inner.size.x,
+				height: self.inner.size.y,
+				depth_or_array_layers: 1,
+			},
+		);
+		let inner = self.inner.clone();
+		encoder.map_buffer_on_submit(&self.inner.buffer, MapMode::Read, .., move |result| {
+			result.expect("error mapping buffer");
+			*inner.state.lock().unwrap() = ReadbackState::Mapped;
+		});
+	}
+}
+
+#[derive(Debug)]
+struct ReadbackInner {
+	buffer: Buffer,
+	size: UVec2,
+	format: TextureFormat,
+	bytes_per_pixel: u32,
+	padded_bytes_per_row: u32,
+	state: Mutex<ReadbackState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReadbackState {
+	Pending,
+	Mapped,
+	Taken,
+}
@@ -0,0 +1,483 @@
+#[cfg(test)]
+mod test;
+
+use std::fmt::Debug;
+
+use derive_more::derive::{Display, Error};
+use glam::UVec2;
+
+use crate::Context;
+
+use super::{Canvas, CanvasSettings, RenderToCanvasSettings};
+
+/// A set of render passes that draw to canvases, declared up front so
+/// they can be scheduled together.
+///
+/// Each pass declares the canvases it reads from and the canvas it draws
+/// to. When the graph is [run](RenderGraph::run):
+/// - Passes are ordered so that every canvas is drawn to before it's read
+///   from. Passes that draw to the same canvas run in the order they were
+///   added. A pass that reads a canvas sees what the passes added before it
+///   drew there, so a canvas can be reused for multiple steps, like
+///   alternating between two canvases for a blur. If every pass that draws
+///   to the canvas was added later, the pass sees the canvas once they've
+///   all run.
+/// - Passes whose output is never used are skipped.
+/// - Transient canvases are allocated from a pool that's reused across
+///   frames. Transient canvases with the same size and settings share
+///   the same GPU resources if they aren't in use at the same time.
+#[derive(Default)]
+pub struct RenderGraph<'a> {
+	canvases: Vec<GraphCanvas>,
+	passes: Vec<RenderGraphPass<'a>>,
+}
+
+impl<'a> RenderGraph<'a> {
+	/// Creates an empty [`RenderGraph`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Declares a canvas that only exists while the graph is running.
+	///
+	/// The contents of a transient canvas are undefined until a pass
+	/// draws to it, so the first pass that draws to it should clear it
+	/// (which is the default).
+	pub fn transient_canvas(
+		&mut self,
+		size: UVec2,
+		settings: CanvasSettings,
+	) -> RenderGraphCanvasId {
+		self.canvases
+			.push(GraphCanvas::Transient { size, settings });
+		RenderGraphCanvasId(self.canvases.len() - 1)
+	}
+
+	/// Declares an existing canvas for use in the graph.
+	///
+	/// Passes that draw to imported canvases are never skipped.
+	pub fn import_canvas(&mut self, canvas: &Canvas) -> RenderGraphCanvasId {
+		self.canvases.push(GraphCanvas::Imported(canvas.clone()));
+		RenderGraphCanvasId(self.canvases.len() - 1)
+	}
+
+	/// Adds a pass to the graph.
+	pub fn add_pass(&mut self, pass: RenderGraphPass<'a>) {
+		self.passes.push(pass);
+	}
+
+	/// Returns the graph with the pass added.
+	pub fn with_pass(mut self, pass: RenderGraphPass<'a>) -> Self {
+		self.add_pass(pass);
+		self
+	}
+
+	/// Runs the passes of the graph.
+	pub fn run(self, ctx: &mut Context) -> Result<(), RenderGraphError> {
+		let _span = tracy_client::span!();
+		let schedule = self.schedule()?;
+
+		// get the canvases backing each transient canvas slot from the pool
+		let mut allocated_counts: Vec<(UVec2, &CanvasSettings, usize)> = vec![];
+		let slot_canvases = schedule
+			.slots
+			.iter()
+			.map(|(size, settings)| {
+				let index =
+					match allocated_counts
+						.iter_mut()
+						.find(|(other_size, other_settings, _)| {
+							other_size == size && other_settings.is_compatible_with(settings)
+						}) {
+						Some((_, _, count)) => {
+							*count += 1;
+							*count - 1
+						}
+						None => {
+							allocated_counts.push((*size, settings, 1));
+							0
+						}
+					};
+				ctx.graphics
+					.transient_canvas(*size, settings.clone(), index)
+			})
+			.collect::<Vec<_>>();
+		let canvases = self
+			.canvases
+			.into_iter()
+			.zip(&schedule.canvas_slots)
+			.map(|(canvas, slot)| match canvas {
+				GraphCanvas::Imported(canvas) => Some(canvas),
+				GraphCanvas::Transient { .. } => slot.map(|slot| slot_canvases[slot].clone()),
+			})
+			.collect::<Vec<_>>();
+
+		let mut passes = self.passes.into_iter().map(Some).collect::<Vec<_>>();
+		let mut drawn_to = vec![false; canvases.len()];
+		for pass_index in schedule.passes {
+			let pass = passes[pass_index].take().expect("pass scheduled twice");
+			let pass_canvases = PassCanvases {
+				canvases: canvases
+					.iter()
+					.enumerate()
+					.map(|(index, canvas)| {
+						let id = RenderGraphCanvasId(index);
+						if pass.reads.contains(&id) || pass.output == Some(id) {
+							canvas.clone()
+						} else {
+							None
+						}
+					})
+					.collect(),
+			};
+			let Some(output) = pass.output else {
+				(pass.draw)(ctx, &pass_canvases);
+				continue;
+			};
+			let settings = pass.settings.unwrap_or_else(|| {
+				let settings = if drawn_to[output.0] {
+					RenderToCanvasSettings::no_clear()
+				} else {
+					RenderToCanvasSettings::default()
+				};
+				RenderToCanvasSettings {
+					render_pass_label: pass.name.clone(),
+					..settings
+				}
+			});
+			drawn_to[output.0] = true;
+			let canvas = canvases[output.0]
+				.as_ref()
+				.expect("output canvas of a scheduled pass was not allocated");
+			let ctx = &mut canvas.render_to(ctx, settings);
+			(pass.draw)(ctx, &pass_canvases);
+		}
+		Ok(())
+	}
+
+	/// Returns the index of the pass whose drawing the pass at `index`
+	/// sees when reading `canvas`, or `None` if no pass draws to it.
+	fn writer_read_by(&self, index: usize, canvas: RenderGraphCanvasId) -> Option<usize> {
+		let writers = || {
+			self.passes
+				.iter()
+				.enumerate()
+				.filter(move |(_, pass)| pass.output == Some(canvas))
+				.map(|(index, _)| index)
+		};
+		writers()
+			.rfind(|&writer| writer < index)
+			.or_else(|| writers().next_back())
+	}
+
+	/// Decides which passes to run, in which order, and which transient
+	/// canvases can share the same GPU resources.
+	fn schedule(&self) -> Result<Schedule, RenderGraphError> {
+		let _span = tracy_client::span!();
+		for pass in &self.passes {
+			if let Some(output) = pass.output
+				&& pass.reads.contains(&output)
+			{
+				return Err(RenderGraphError::ReadsOwnOutput {
+					pass: pass.name.clone(),
+				});
+			}
+		}
+
+		// a pass that reads a canvas depends on the most recent pass added
+		// before it that draws to the canvas (or the last pass that draws to
+		// it, if they were all added later). passes that draw to a canvas
+		// depend on the passes that read what was drawn to it before, and
+		// passes that draw to the same target (or the current render
+		// target) depend on the previous one.
+		let mut dependencies = vec![vec![]; self.passes.len()];
+		for (index, pass) in self.passes.iter().enumerate() {
+			for (other_index, other) in self.passes.iter().enumerate() {
+				if other_index < index && other.output == pass.output {
+					dependencies[index].push(other_index);
+				}
+			}
+			for &read in &pass.reads {
+				let Some(writer) = self.writer_read_by(index, read) else {
+					continue;
+				};
+				dependencies[index].push(writer);
+				for (other_index, other) in self.passes.iter().enumerate() {
+					if other_index > writer && other_index != index && other.output == Some(read) {
+						dependencies[other_index].push(index);
+					}
+				}
+			}
+		}
+		for (canvas_index, canvas) in self.canvases.iter().enumerate() {
+			let id = RenderGraphCanvasId(canvas_index);
+			if !matches!(canvas, GraphCanvas::Transient { .. }) {
+				continue;
+			}
+			let drawn_to = self.passes.iter().any(|pass| pass.output == Some(id));
+			if drawn_to {
+				continue;
+			}
+			if let Some(pass) = self.passes.iter().find(|pass| pass.reads.contains(&id)) {
+				return Err(RenderGraphError::ReadsUndrawnCanvas {
+					pass: pass.name.clone(),
+					canvas: canvas.label().to_string(),
+				});
+			}
+		}
+
+		// topological sort, preferring the order the passes were added in
+		let mut order = Vec::with_capacity(self.passes.len());
+		let mut scheduled = vec![false; self.passes.len()];
+		while order.len() < self.passes.len() {
+			let next = (0..self.passes.len()).find(|&index| {
+				!scheduled[index]
+					&& dependencies[index]
+						.iter()
+						.all(|&dependency| scheduled[dependency])
+			});
+			let Some(next) = next else {
+				return Err(RenderGraphError::Cycle {
+					passes: (0..self.passes.len())
+						.filter(|&index| !scheduled[index])
+						.map(|index| self.passes[index].name.clone())
+						.collect(),
+				});
+			};
+			scheduled[next] = true;
+			order.push(next);
+		}
+
+		// walk backwards from the passes that draw to the current render
+		// target or imported canvases to find the passes that are needed
+		let mut needed_canvases = vec![false; self.canvases.len()];
+		let mut needed_passes = vec![false; self.passes.len()];
+		for &index in order.iter().rev() {
+			let pass = &self.passes[index];
+			let needed = match pass.output {
+				None => true,
+				Some(output) => {
+					matches!(self.canvases[output.0], GraphCanvas::Imported(_))
+						|| needed_canvases[output.0]
+				}
+			};
+			if !needed {
+				continue;
+			}
+			needed_passes[index] = true;
+			for read in &pass.reads {
+				needed_canvases[read.0] = true;
+			}
+		}
+		let passes = order
+			.into_iter()
+			.filter(|&index| needed_passes[index])
+			.collect::<Vec<_>>();
+
+		// assign transient canvases to slots, reusing a slot once the
+		// canvas that was using it is no longer needed. passes that draw
+		// to the current render target may be recorded into an enclosing
+		// canvas render pass that only runs after the whole graph, so the
+		// canvases they read have to stay intact until the end.
+		let mut lifetimes = vec![None::<(usize, usize)>; self.canvases.len()];
+		for (position, &index) in passes.iter().enumerate() {
+			let pass = &self.passes[index];
+			for id in pass.reads.iter().chain(&pass.output) {
+				let last_use = if pass.output.is_none() {
+					passes.len()
+				} else {
+					position
+				};
+				let lifetime = lifetimes[id.0].get_or_insert((position, last_use));
+				lifetime.1 = lifetime.1.max(last_use);
+			}
+		}
+		let mut canvases_by_first_use = (0..self.canvases.len())
+			.filter_map(|index| lifetimes[index].map(|lifetime| (index, lifetime)))
+			.collect::<Vec<_>>();
+		canvases_by_first_use.sort_by_key(|(_, (first_use, _))| *first_use);
+		let mut slots: Vec<(UVec2, CanvasSettings)> = vec![];
+		let mut slot_last_uses: Vec<usize> = vec![];
+		let mut canvas_slots = vec![None; self.canvases.len()];
+		for (canvas_index, (first_use, last_use)) in canvases_by_first_use {
+			let GraphCanvas::Transient { size, settings } = &self.canvases[canvas_index] else {
+				continue;
+			};
+			let free_slot = (0..slots.len()).find(|&slot| {
+				let (slot_size, slot_settings) = &slots[slot];
+				slot_size == size
+					&& slot_settings.is_compatible_with(settings)
+					&& slot_last_uses[slot] < first_use
+			});
+			let slot = free_slot.unwrap_or_else(|| {
+				slots.push((*size, settings.clone()));
+				slot_last_uses.push(0);
+				slots.len() - 1
+			});
+			slot_last_uses[slot] = last_use;
+			canvas_slots[canvas_index] = Some(slot);
+		}
+
+		Ok(Schedule {
+			passes,
+			slots,
+			canvas_slots,
+		})
+	}
+}
+
+impl Debug for RenderGraph<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RenderGraph")
+			.field("canvases", &self.canvases)
+			.field("passes", &self.passes)
+			.finish()
+	}
+}
+
+type DrawPassFn<'a> = Box<dyn FnOnce(&mut Context, &PassCanvases) + 'a>;
+
+/// A pass in a [`RenderGraph`].
+pub struct RenderGraphPass<'a> {
+	name: String,
+	reads: Vec<RenderGraphCanvasId>,
+	output: Option<RenderGraphCanvasId>,
+	settings: Option<RenderToCanvasSettings>,
+	draw: DrawPassFn<'a>,
+}
+
+impl<'a> RenderGraphPass<'a> {
+	/// Creates a new [`RenderGraphPass`] that runs the `draw` function.
+	///
+	/// By default, the pass draws to the current render target and doesn't
+	/// read from any canvases.
+	pub fn new(
+		name: impl Into<String>,
+		draw: impl FnOnce(&mut Context, &PassCanvases) + 'a,
+	) -> Self {
+		Self {
+			name: name.into(),
+			reads: vec![],
+			output: None,
+			settings: None,
+			draw: Box::new(draw),
+		}
+	}
+
+	/// Declares that the pass reads from the canvas.
+	pub fn reads(mut self, canvas: RenderGraphCanvasId) -> Self {
+		self.reads.push(canvas);
+		self
+	}
+
+	/// Sets the canvas the pass draws to.
+	pub fn draws_to(self, canvas: RenderGraphCanvasId) -> Self {
+		Self {
+			output: Some(canvas),
+			..self
+		}
+	}
+
+	/// Sets the settings to use when drawing to the output canvas.
+	///
+	/// By default, the first pass that draws to a canvas clears it, and
+	/// later passes draw on top of the existing contents.
+	pub fn settings(self, settings: RenderToCanvasSettings) -> Self {
+		Self {
+			settings: Some(settings),
+			..self
+		}
+	}
+}
+
+impl Debug for RenderGraphPass<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RenderGraphPass")
+			.field("name", &self.name)
+			.field("reads", &self.reads)
+			.field("output", &self.output)
+			.field("settings", &self.settings)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Identifies a canvas declared in a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderGraphCanvasId(usize);
+
+/// The canvases a [`RenderGraphPass`] declared it uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassCanvases {
+	canvases: Vec<Option<Canvas>>,
+}
+
+impl PassCanvases {
+	/// Returns the canvas with the given ID.
+	///
+	/// # Panics
+	///
+	/// Panics if the pass didn't declare that it reads from or draws to
+	/// the canvas.
+	pub fn get(&self, id: RenderGraphCanvasId) -> &Canvas {
+		self.canvases
+			.get(id.0)
+			.and_then(Option::as_ref)
+			.expect("the pass did not declare that it uses this canvas")
+	}
+}
+
+/// An error that can occur when running a [`RenderGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Error, Display)]
+pub enum RenderGraphError {
+	/// Some passes depend on each other's output, so they can't
+	/// be ordered.
+	#[display("The passes {passes:?} depend on each other")]
+	Cycle {
+		/// The names of the passes that couldn't be ordered.
+		passes: Vec<String>,
+	},
+	/// A pass reads from the canvas it draws to.
+	#[display("The pass '{pass}' reads from the canvas it draws to")]
+	ReadsOwnOutput {
+		/// The name of the pass.
+		pass: String,
+	},
+	/// A pass reads from a transient canvas that no pass draws to.
+	#[display("The pass '{pass}' reads from the canvas '{canvas}', which no pass draws to")]
+	ReadsUndrawnCanvas {
+		/// The name of the pass.
+		pass: String,
+		/// The label of the canvas.
+		canvas: String,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum GraphCanvas {
+	Transient {
+		size: UVec2,
+		settings: CanvasSettings,
+	},
+	Imported(Canvas),
+}
+
+impl GraphCanvas {
+	fn label(&self) -> &str {
+		match self {
+			GraphCanvas::Transient { settings, .. } => &settings.label,
+			GraphCanvas::Imported(canvas) => &canvas.label,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Schedule {
+	/// The indices of the passes to run, in order.
+	passes: Vec<usize>,
+	/// The size and settings of each canvas that needs to be allocated
+	/// for transient canvases.
+	slots: Vec<(UVec2, CanvasSettings)>,
+	/// For each canvas in the graph, the slot used for it, if it's a
+	/// transient canvas used by any of the scheduled passes.
+	canvas_slots: Vec<Option<usize>>,
+}
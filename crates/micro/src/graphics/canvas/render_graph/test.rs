@@ -0,0 +1,122 @@
+use glam::UVec2;
+
+use crate::graphics::canvas::CanvasSettings;
+
+use super::{RenderGraph, RenderGraphError, RenderGraphPass};
+
+fn pass_names(graph: &RenderGraph, passes: &[usize]) -> Vec<String> {
+	passes
+		.iter()
+		.map(|&index| graph.passes[index].name.clone())
+		.collect()
+}
+
+#[test]
+fn orders_passes_by_dependencies() {
+	let mut graph = RenderGraph::new();
+	let scene = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let bloom = graph.transient_canvas(UVec2::splat(32), CanvasSettings::default());
+	graph.add_pass(
+		RenderGraphPass::new("composite", |_, _| {})
+			.reads(scene)
+			.reads(bloom),
+	);
+	graph.add_pass(
+		RenderGraphPass::new("bloom", |_, _| {})
+			.reads(scene)
+			.draws_to(bloom),
+	);
+	graph.add_pass(RenderGraphPass::new("scene", |_, _| {}).draws_to(scene));
+	let schedule = graph.schedule().unwrap();
+	assert_eq!(
+		pass_names(&graph, &schedule.passes),
+		["scene", "bloom", "composite"]
+	);
+}
+
+#[test]
+fn skips_unused_passes() {
+	let mut graph = RenderGraph::new();
+	let scene = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let unused = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	graph.add_pass(RenderGraphPass::new("scene", |_, _| {}).draws_to(scene));
+	graph.add_pass(
+		RenderGraphPass::new("unused", |_, _| {})
+			.reads(scene)
+			.draws_to(unused),
+	);
+	graph.add_pass(RenderGraphPass::new("composite", |_, _| {}).reads(scene));
+	let schedule = graph.schedule().unwrap();
+	assert_eq!(pass_names(&graph, &schedule.passes), ["scene", "composite"]);
+	assert_eq!(schedule.canvas_slots[unused.0], None);
+}
+
+#[test]
+fn aliases_transient_canvases() {
+	let mut graph = RenderGraph::new();
+	let a = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let b = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let c = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	graph.add_pass(RenderGraphPass::new("a", |_, _| {}).draws_to(a));
+	graph.add_pass(RenderGraphPass::new("b", |_, _| {}).reads(a).draws_to(b));
+	graph.add_pass(RenderGraphPass::new("c", |_, _| {}).reads(b).draws_to(c));
+	graph.add_pass(RenderGraphPass::new("composite", |_, _| {}).reads(c));
+	let schedule = graph.schedule().unwrap();
+	assert_eq!(schedule.slots.len(), 2);
+	assert_eq!(schedule.canvas_slots[a.0], schedule.canvas_slots[c.0]);
+	assert_ne!(schedule.canvas_slots[a.0], schedule.canvas_slots[b.0]);
+}
+
+#[test]
+fn keeps_canvases_read_by_the_current_render_target_until_the_end() {
+	let mut graph = RenderGraph::new();
+	let a = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let b = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	graph.add_pass(RenderGraphPass::new("a", |_, _| {}).draws_to(a));
+	graph.add_pass(RenderGraphPass::new("show a", |_, _| {}).reads(a));
+	graph.add_pass(RenderGraphPass::new("b", |_, _| {}).draws_to(b));
+	graph.add_pass(RenderGraphPass::new("show b", |_, _| {}).reads(b));
+	let schedule = graph.schedule().unwrap();
+	assert_eq!(schedule.slots.len(), 2);
+	assert_ne!(schedule.canvas_slots[a.0], schedule.canvas_slots[b.0]);
+}
+
+#[test]
+fn detects_cycles() {
+	let mut graph = RenderGraph::new();
+	let a = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let b = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	graph.add_pass(RenderGraphPass::new("a", |_, _| {}).reads(b).draws_to(a));
+	graph.add_pass(RenderGraphPass::new("b", |_, _| {}).reads(a).draws_to(b));
+	assert_eq!(
+		graph.schedule(),
+		Err(RenderGraphError::Cycle {
+			passes: vec!["a".into(), "b".into()]
+		})
+	);
+}
+
+#[test]
+fn reuses_canvases_for_multiple_steps() {
+	let mut graph = RenderGraph::new();
+	let a = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	let b = graph.transient_canvas(UVec2::splat(64), CanvasSettings::default());
+	graph.add_pass(RenderGraphPass::new("scene", |_, _| {}).draws_to(a));
+	graph.add_pass(
+		RenderGraphPass::new("blur x", |_, _| {})
+			.reads(a)
+			.draws_to(b),
+	);
+	graph.add_pass(
+		RenderGraphPass::new("blur y", |_, _| {})
+			.reads(b)
+			.draws_to(a),
+	);
+	graph.add_pass(RenderGraphPass::new("composite", |_, _| {}).reads(a));
+	let schedule = graph.schedule().unwrap();
+	assert_eq!(
+		pass_names(&graph, &schedule.passes),
+		["scene", "blur x", "blur y", "composite"]
+	);
+	assert_ne!(schedule.canvas_slots[a.0], schedule.canvas_slots[b.0]);
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::egui;
+
+/// How long the GPU took to run each render pass of a frame, measured
+/// with timestamp queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuTimings {
+	/// The timings of each render pass, in the order they ran.
+	pub passes: Vec<GpuPassTiming>,
+}
+
+impl GpuTimings {
+	/// Returns the total time the GPU spent on render passes.
+	pub fn total(&self) -> Duration {
+		self.passes.iter().map(|pass| pass.duration).sum()
+	}
+
+	/// Shows the timings in an egui [`Ui`](egui::Ui).
+	pub fn ui(&self, ui: &mut egui::Ui) {
+		egui::Grid::new("gpu_timings")
+			.num_columns(2)
+			.striped(true)
+			.show(ui, |ui| {
+				for pass in &self.passes {
+					ui.label(&pass.label);
+					ui.label(format_duration(pass.duration));
+					ui.end_row();
+				}
+				ui.strong("Total");
+				ui.strong(format_duration(self.total()));
+				ui.end_row();
+			});
+	}
+}
+
+/// How long the GPU took to run a render pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuPassTiming {
+	/// The label of the render pass.
+	pub label: String,
+	/// The time between the start and end of the render pass.
+	pub duration: Duration,
+}
+
+fn format_duration(duration: Duration) -> String {
+	format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
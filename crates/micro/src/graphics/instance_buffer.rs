@@ -0,0 +1,96 @@
+use std::marker::PhantomData;
+
+use bytemuck::Pod;
+use wgpu::{
+	Buffer, BufferUsages,
+	util::{BufferInitDescriptor, DeviceExt},
+};
+
+use crate::{
+	Context,
+	context::graphics::InstanceLayout,
+	graphics::{HasVertexAttributes, IntoInstanceRange},
+};
+
+/// A buffer of per-instance data that can be used when drawing a
+/// [`Mesh`](crate::graphics::mesh::Mesh) with
+/// [`draw_instanced`](crate::graphics::mesh::Mesh::draw_instanced).
+///
+/// Each instance's attributes are read by the vertex shader like vertex
+/// attributes. The attribute locations must not overlap with the
+/// locations used by the mesh's vertex type.
+///
+/// This can be cheaply cloned. Clones will point to the same instance
+/// data on the GPU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceBuffer<T: Pod + HasVertexAttributes> {
+	pub(crate) buffer: Buffer,
+	len: u32,
+	_phantom_data: PhantomData<T>,
+	/// The min and max instance indices to draw.
+	///
+	/// Setting this results in only some of the instances being drawn.
+	/// When `None`, all the instances are drawn.
+	pub range: Option<(u32, u32)>,
+}
+
+impl<T: Pod + HasVertexAttributes> InstanceBuffer<T> {
+	/// Creates a new [`InstanceBuffer`] with the specified instances.
+	pub fn new(ctx: &Context, instances: &[T]) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			buffer: ctx
+				.graphics
+				.device
+				.create_buffer_init(&BufferInitDescriptor {
+					label: Some("Instance Buffer"),
+					contents: bytemuck::cast_slice(instances),
+					usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+				}),
+			len: instances.len() as u32,
+			_phantom_data: PhantomData,
+			range: None,
+		}
+	}
+
+	/// Sets the range of instances to draw.
+	///
+	/// Setting this results in only some of the instances being drawn.
+	/// When `None`, all the instances are drawn.
+	pub fn range(&self, range: impl IntoInstanceRange) -> Self {
+		let mut new = self.clone();
+		new.range = Some(range.into_instance_range());
+		new
+	}
+
+	/// Returns the number of instances in the buffer.
+	pub fn len(&self) -> u32 {
+		self.len
+	}
+
+	/// Returns `true` if the buffer has no instances.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Overwrites instance data starting at the specified `index`.
+	///
+	/// The data must fit within the buffer's original size.
+	///
+	/// This will modify all clones of this [`InstanceBuffer`] as well.
+	pub fn set_instances(&self, ctx: &Context, index: usize, instances: &[T]) {
+		let _span = tracy_client::span!();
+		ctx.graphics.queue.write_buffer(
+			&self.buffer,
+			(index * std::mem::size_of::<T>()) as u64,
+			bytemuck::cast_slice(instances),
+		);
+	}
+
+	pub(crate) fn layout(&self) -> InstanceLayout {
+		InstanceLayout {
+			size: std::mem::size_of::<T>(),
+			attributes: T::attributes(),
+		}
+	}
+}
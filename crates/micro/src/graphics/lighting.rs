@@ -0,0 +1,347 @@
+//! Basic lighting for 3D meshes.
+//!
+//! Meshes made of [`Vertex3d`](crate::graphics::Vertex3d)s can be lit by
+//! uploading a [`Lighting`] setup to a [`LightBuffer`] and drawing the meshes
+//! with [`LightBuffer::push`] active. Lighting is calculated using the
+//! Blinn-Phong model. The mesh's own transform is treated as its position in
+//! the world, and any transforms on the graphics stack (like a
+//! [`Camera3d`](crate::graphics::Camera3d)) are treated as the camera.
+
+mod shadow_map;
+#[cfg(test)]
+mod test;
+
+pub use shadow_map::*;
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec3, Vec4, vec4};
+use palette::LinSrgb;
+
+use crate::{
+	Context, Push,
+	color::ColorConstants,
+	context::OnDrop,
+	graphics::{Shader, StorageBuffer, texture::Texture},
+};
+
+/// A set of lights that illuminate a scene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lighting {
+	/// The color of light that reaches every surface regardless of
+	/// the other lights.
+	pub ambient_color: LinSrgb,
+	/// Lights that shine in one direction from infinitely far away,
+	/// like the sun.
+	pub directional_lights: Vec<DirectionalLight>,
+	/// Lights that shine in every direction from a single point.
+	pub point_lights: Vec<PointLight>,
+	/// Lights that shine in a cone from a single point.
+	pub spot_lights: Vec<SpotLight>,
+}
+
+impl Lighting {
+	/// Returns the total number of lights.
+	pub fn num_lights(&self) -> usize {
+		self.directional_lights.len() + self.point_lights.len() + self.spot_lights.len()
+	}
+
+	fn shadow_casting_light(&self) -> Option<(usize, &DirectionalLight, &ShadowMap)> {
+		self.directional_lights
+			.iter()
+			.enumerate()
+			.find_map(|(i, light)| {
+				light
+					.shadow_map
+					.as_ref()
+					.map(|shadow_map| (i, light, shadow_map))
+			})
+	}
+}
+
+impl Default for Lighting {
+	fn default() -> Self {
+		Self {
+			ambient_color: LinSrgb::new(0.1, 0.1, 0.1),
+			directional_lights: vec![],
+			point_lights: vec![],
+			spot_lights: vec![],
+		}
+	}
+}
+
+/// A light that shines in one direction from infinitely far away.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionalLight {
+	/// The direction the light travels in.
+	pub direction: Vec3,
+	/// The color of the light.
+	pub color: LinSrgb,
+	/// A multiplier for the brightness of the light.
+	pub intensity: f32,
+	/// The shadow map to use for this light, if any.
+	///
+	/// Only the first directional light with a shadow map in a [`Lighting`]
+	/// setup will cast shadows.
+	pub shadow_map: Option<ShadowMap>,
+}
+
+impl Default for DirectionalLight {
+	fn default() -> Self {
+		Self {
+			direction: Vec3::NEG_Y,
+			color: LinSrgb::WHITE,
+			intensity: 1.0,
+			shadow_map: None,
+		}
+	}
+}
+
+/// A light that shines in every direction from a single point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointLight {
+	/// The location of the light.
+	pub position: Vec3,
+	/// The color of the light.
+	pub color: LinSrgb,
+	/// A multiplier for the brightness of the light.
+	pub intensity: f32,
+	/// The distance at which the light fades out completely.
+	pub range: f32,
+}
+
+impl Default for PointLight {
+	fn default() -> Self {
+		Self {
+			position: Vec3::ZERO,
+			color: LinSrgb::WHITE,
+			intensity: 1.0,
+			range: 10.0,
+		}
+	}
+}
+
+/// A light that shines in a cone from a single point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpotLight {
+	/// The location of the light.
+	pub position: Vec3,
+	/// The direction the light is pointed.
+	pub direction: Vec3,
+	/// The color of the light.
+	pub color: LinSrgb,
+	/// A multiplier for the brightness of the light.
+	pub intensity: f32,
+	/// The distance at which the light fades out completely.
+	pub range: f32,
+	/// The angle (in radians) from the center of the cone at which the
+	/// light starts to fade out.
+	pub inner_cone_angle: f32,
+	/// The angle (in radians) from the center of the cone at which the
+	/// light fades out completely.
+	pub outer_cone_angle: f32,
+}
+
+impl Default for SpotLight {
+	fn default() -> Self {
+		Self {
+			position: Vec3::ZERO,
+			direction: Vec3::NEG_Y,
+			color: LinSrgb::WHITE,
+			intensity: 1.0,
+			range: 10.0,
+			inner_cone_angle: 0.3,
+			outer_cone_angle: 0.5,
+		}
+	}
+}
+
+/// How shiny a surface is. Can be passed to [`Shader::with_params`] on
+/// the shader returned by [`LightBuffer::shader`].
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Material {
+	/// How bright the specular highlights are.
+	pub specular_strength: f32,
+	/// How focused the specular highlights are. Higher values result in
+	/// smaller highlights.
+	pub shininess: f32,
+}
+
+impl Default for Material {
+	fn default() -> Self {
+		Self {
+			specular_strength: 0.5,
+			shininess: 32.0,
+		}
+	}
+}
+
+/// A [`Lighting`] setup uploaded to the GPU.
+///
+/// Custom shaders can read the lights by adding
+/// [`LightBuffer::storage_buffer`] as the first storage buffer. See
+/// the built-in lit shader for the layout of the data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightBuffer {
+	buffer: StorageBuffer,
+	capacity: usize,
+	shadow_map_texture: Option<Texture>,
+	/// The built-in lit shader with the default [`Material`].
+	lit_shader: Shader,
+}
+
+impl LightBuffer {
+	/// Creates a new [`LightBuffer`] with room for `capacity` lights.
+	///
+	/// The buffer will grow if more lights are added later.
+	pub fn new(ctx: &Context, capacity: usize) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			buffer: create_buffer(ctx, capacity),
+			capacity,
+			shadow_map_texture: None,
+			lit_shader: ctx
+				.graphics
+				.default_resources
+				.default_lit_shader
+				.with_params(ctx, Material::default()),
+		}
+	}
+
+	/// Returns the storage buffer holding the lights.
+	pub fn storage_buffer(&self) -> &StorageBuffer {
+		&self.buffer
+	}
+
+	/// Uploads the `lighting` setup to the GPU. `camera_position` is
+	/// used for calculating specular highlights.
+	pub fn set(&mut self, ctx: &Context, lighting: &Lighting, camera_position: Vec3) {
+		let _span = tracy_client::span!();
+		if lighting.num_lights() > self.capacity {
+			self.capacity = lighting.num_lights().next_power_of_two();
+			self.buffer = create_buffer(ctx, self.capacity);
+		}
+		let shadow_casting_light = lighting.shadow_casting_light();
+		let header = LightBufferHeader {
+			ambient_color: color_to_vec4(lighting.ambient_color, 1.0),
+			camera_position: camera_position.extend(1.0),
+			shadow_view_projection: shadow_casting_light
+				.map(|(_, light, shadow_map)| shadow_map.view_projection(light.direction))
+				.unwrap_or_default(),
+			shadow_params: match shadow_casting_light {
+				Some((_, _, shadow_map)) => vec4(
+					1.0,
+					shadow_map.bias,
+					1.0 / shadow_map.size().x.max(shadow_map.size().y) as f32,
+					0.0,
+				),
+				None => Vec4::ZERO,
+			},
+			num_lights: lighting.num_lights() as u32,
+			_padding: [0; 3],
+		};
+		let shadow_casting_light_index = shadow_casting_light.map(|(i, _, _)| i);
+		let directional_lights =
+			lighting
+				.directional_lights
+				.iter()
+				.enumerate()
+				.map(|(i, light)| RawLight {
+					position_and_kind: vec4(0.0, 0.0, 0.0, LIGHT_KIND_DIRECTIONAL),
+					direction_and_range: light.direction.extend(0.0),
+					color_and_intensity: color_to_vec4(light.color, light.intensity),
+					cone: vec4(
+						0.0,
+						0.0,
+						if shadow_casting_light_index == Some(i) {
+							1.0
+						} else {
+							0.0
+						},
+						0.0,
+					),
+				});
+		let point_lights = lighting.point_lights.iter().map(|light| RawLight {
+			position_and_kind: light.position.extend(LIGHT_KIND_POINT),
+			direction_and_range: vec4(0.0, 0.0, 0.0, light.range),
+			color_and_intensity: color_to_vec4(light.color, light.intensity),
+			cone: Vec4::ZERO,
+		});
+		let spot_lights = lighting.spot_lights.iter().map(|light| RawLight {
+			position_and_kind: light.position.extend(LIGHT_KIND_SPOT),
+			direction_and_range: light.direction.extend(light.range),
+			color_and_intensity: color_to_vec4(light.color, light.intensity),
+			cone: vec4(
+				light.inner_cone_angle.cos(),
+				light.outer_cone_angle.cos(),
+				0.0,
+				0.0,
+			),
+		});
+		let lights = directional_lights
+			.chain(point_lights)
+			.chain(spot_lights)
+			.collect::<Vec<_>>();
+		let mut data = bytemuck::bytes_of(&header).to_vec();
+		data.extend_from_slice(bytemuck::cast_slice(&lights));
+		self.buffer.write(ctx, 0, &data);
+		self.shadow_map_texture =
+			shadow_casting_light.map(|(_, _, shadow_map)| shadow_map.texture());
+	}
+
+	/// Returns the built-in lit shader set up to use this light buffer.
+	///
+	/// The shader uses the default [`Material`]. A different material can
+	/// be used by calling [`Shader::with_params`] on the returned shader.
+	pub fn shader(&self, ctx: &Context) -> Shader {
+		self.lit_shader
+			.with_storage_buffers(vec![self.buffer.clone()])
+			.with_textures(vec![self.shadow_map_texture.clone().unwrap_or_else(|| {
+				ctx.graphics.default_resources.default_texture.clone()
+			})])
+	}
+
+	/// Pushes the built-in lit shader for this light buffer to the
+	/// graphics stack.
+	pub fn push<'a>(&self, ctx: &'a mut Context) -> OnDrop<'a> {
+		let shader = self.shader(ctx);
+		ctx.push(Push {
+			shader: Some(shader),
+			..Default::default()
+		})
+	}
+}
+
+const LIGHT_KIND_DIRECTIONAL: f32 = 0.0;
+const LIGHT_KIND_POINT: f32 = 1.0;
+const LIGHT_KIND_SPOT: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct LightBufferHeader {
+	ambient_color: Vec4,
+	camera_position: Vec4,
+	shadow_view_projection: Mat4,
+	shadow_params: Vec4,
+	num_lights: u32,
+	_padding: [u32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct RawLight {
+	position_and_kind: Vec4,
+	direction_and_range: Vec4,
+	color_and_intensity: Vec4,
+	cone: Vec4,
+}
+
+fn create_buffer(ctx: &Context, capacity: usize) -> StorageBuffer {
+	let size = std::mem::size_of::<LightBufferHeader>()
+		+ std::mem::size_of::<RawLight>() * capacity.max(1);
+	StorageBuffer::new(ctx, "Light Buffer", &vec![0u8; size])
+}
+
+fn color_to_vec4(color: LinSrgb, alpha: f32) -> Vec4 {
+	vec4(color.red, color.green, color.blue, alpha)
+}
@@ -0,0 +1,129 @@
+use glam::{
+	Mat4, UVec2, Vec3,
+	camera::rh::{proj::directx::orthographic, view::look_at_mat4},
+};
+use palette::LinSrgba;
+use wgpu::{FilterMode, TextureFormat};
+
+use crate::{
+	Context, Push,
+	color::ColorConstants,
+	graphics::{
+		Camera3d, Canvas, CanvasSettings, RenderToCanvasSettings,
+		texture::{Texture, TextureSettings},
+	},
+};
+
+use super::DirectionalLight;
+
+/// An off-screen depth map used to cast shadows from a
+/// [`DirectionalLight`].
+///
+/// The shadow map covers a sphere around `center` with the specified
+/// `radius`. Objects outside of that area will not cast or receive shadows.
+///
+/// This can be cheaply cloned. Clones will share the same depth map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowMap {
+	canvas: Canvas,
+	/// The center of the area covered by the shadow map.
+	pub center: Vec3,
+	/// The radius of the area covered by the shadow map.
+	pub radius: f32,
+	/// How much to offset depths when checking if a surface is in shadow.
+	///
+	/// Increasing this reduces "shadow acne" (stripes of shadow on lit
+	/// surfaces), but makes shadows start further away from the
+	/// objects casting them.
+	pub bias: f32,
+}
+
+impl ShadowMap {
+	/// Creates a new [`ShadowMap`] with the specified `size` in pixels.
+	pub fn new(ctx: &Context, size: UVec2, center: Vec3, radius: f32) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			canvas: Canvas::new(
+				ctx,
+				size,
+				CanvasSettings {
+					label: "Shadow Map".into(),
+					texture_settings: TextureSettings {
+						label: "Shadow Map".into(),
+						minifying_filter: FilterMode::Nearest,
+						magnifying_filter: FilterMode::Nearest,
+						..Default::default()
+					},
+					format: TextureFormat::Rgba8Unorm,
+					..Default::default()
+				},
+			),
+			center,
+			radius,
+			bias: 0.005,
+		}
+	}
+
+	/// Returns the size of the shadow map in pixels.
+	pub fn size(&self) -> UVec2 {
+		self.canvas.size()
+	}
+
+	/// Returns the texture holding the packed depth values.
+	pub fn texture(&self) -> Texture {
+		self.canvas.drawable_texture()
+	}
+
+	/// Returns the transform from world space to the light's clip space
+	/// for a light shining in the specified `direction`.
+	pub fn view_projection(&self, direction: Vec3) -> Mat4 {
+		let direction = direction.normalize_or(Vec3::NEG_Y);
+		let up = if direction.y.abs() > 0.99 {
+			Vec3::Z
+		} else {
+			Vec3::Y
+		};
+		let projection = orthographic(
+			-self.radius,
+			self.radius,
+			-self.radius,
+			self.radius,
+			0.0,
+			self.radius * 2.0,
+		);
+		let view = look_at_mat4(self.center - direction * self.radius, self.center, up);
+		projection * view
+	}
+
+	/// Draws the shadow casters for the `light` into the shadow map.
+	///
+	/// Meshes drawn in `draw` should be made of
+	/// [`Vertex3d`](crate::graphics::Vertex3d)s, and they should be drawn
+	/// without any camera transform on the graphics stack.
+	pub fn render<T>(
+		&self,
+		ctx: &mut Context,
+		light: &DirectionalLight,
+		draw: impl FnOnce(&mut Context) -> T,
+	) -> T {
+		let _span = tracy_client::span!();
+		let ctx = &mut self.canvas.render_to(
+			ctx,
+			RenderToCanvasSettings {
+				clear_color: Some(LinSrgba::WHITE),
+				render_pass_label: "Shadow Map Render Pass".into(),
+				..Default::default()
+			},
+		);
+		let transform = Camera3d::undo_2d_coordinate_system_transform(ctx)
+			* self.view_projection(light.direction);
+		let shader = ctx.graphics.default_resources.shadow_depth_shader.clone();
+		let ctx = &mut ctx.push(Push {
+			transform: Some(transform),
+			shader: Some(shader),
+			enable_depth_testing: Some(true),
+			..Default::default()
+		});
+		draw(ctx)
+	}
+}
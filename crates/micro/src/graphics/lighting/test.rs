@@ -0,0 +1,27 @@
+use std::mem::{offset_of, size_of};
+
+use super::{LightBufferHeader, RawLight};
+
+// the light buffer is read as a std430 storage buffer by the lit shader,
+// so these have to match the layout of the `Lights` block and `Light`
+// struct there
+
+#[test]
+fn light_buffer_header_layout() {
+	assert_eq!(offset_of!(LightBufferHeader, ambient_color), 0);
+	assert_eq!(offset_of!(LightBufferHeader, camera_position), 16);
+	assert_eq!(offset_of!(LightBufferHeader, shadow_view_projection), 32);
+	assert_eq!(offset_of!(LightBufferHeader, shadow_params), 96);
+	assert_eq!(offset_of!(LightBufferHeader, num_lights), 112);
+	// the array of lights starts at the next multiple of 16 bytes
+	assert_eq!(size_of::<LightBufferHeader>(), 128);
+}
+
+#[test]
+fn raw_light_layout() {
+	assert_eq!(offset_of!(RawLight, position_and_kind), 0);
+	assert_eq!(offset_of!(RawLight, direction_and_range), 16);
+	assert_eq!(offset_of!(RawLight, color_and_intensity), 32);
+	assert_eq!(offset_of!(RawLight, cone), 48);
+	assert_eq!(size_of::<RawLight>(), 64);
+}
@@ -0,0 +1,13 @@
+/// Settings for a mask created with [`Context::with_mask_settings`](crate::Context::with_mask_settings).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct MaskSettings {
+	/// If `true`, content is only visible where the mask was *not* drawn.
+	pub inverted: bool,
+	/// If set, only the parts of the mask with an alpha above this value
+	/// count as part of the mask. Useful for masking with textures.
+	///
+	/// Only supported for 2D drawing operations.
+	pub alpha_threshold: Option<f32>,
+}
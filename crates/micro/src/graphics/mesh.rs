@@ -1,11 +1,14 @@
 //! Types for drawing shapes with the GPU.
 
 mod builder;
+mod dynamic_mesh;
 
 pub use builder::*;
+pub use dynamic_mesh::*;
 
 use std::marker::PhantomData;
 
+use bytemuck::Pod;
 use glam::{Mat4, Vec2};
 use palette::LinSrgba;
 use wgpu::{
@@ -16,8 +19,11 @@ use wgpu::{
 use crate::{
 	Context,
 	color::ColorConstants,
-	context::graphics::QueueDrawCommandSettings,
-	graphics::{BlendMode, IntoIndexRange, IntoInstanceRange, texture::Texture},
+	context::graphics::{InstanceLayout, QueueDrawCommandSettings},
+	graphics::{
+		BlendMode, HasVertexAttributes, InstanceBuffer, IntoIndexRange, IntoInstanceRange,
+		texture::Texture,
+	},
 	math::{Circle, Rect},
 	standard_draw_param_methods,
 };
@@ -34,6 +40,8 @@ pub struct Mesh<V: Vertex = Vertex2d> {
 	pub(crate) vertex_buffer: Buffer,
 	pub(crate) index_buffer: Buffer,
 	pub(crate) num_indices: u32,
+	/// The value added to each index before looking up a vertex.
+	pub(crate) base_vertex: i32,
 	_phantom_data: PhantomData<V>,
 
 	// draw params
@@ -45,6 +53,8 @@ pub struct Mesh<V: Vertex = Vertex2d> {
 	pub color: LinSrgba,
 	/// The blend mode to use when drawing this mesh.
 	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this mesh.
+	pub palette: Option<Texture>,
 	/// The min and max vertex index to use when drawing this mesh.
 	///
 	/// Setting this results in a portion of the mesh being drawn.
@@ -82,11 +92,13 @@ impl<V: Vertex> Mesh<V> {
 			vertex_buffer,
 			index_buffer,
 			num_indices,
+			base_vertex: 0,
 			_phantom_data: PhantomData,
 			texture: None,
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::default(),
+			palette: None,
 			range: None,
 			instances: (0, 1),
 		}
@@ -137,6 +149,39 @@ impl<V: Vertex> Mesh<V> {
 	/// Draws the mesh.
 	pub fn draw(&self, ctx: &mut Context) {
 		let _span = tracy_client::span!();
+		self.queue_draw_command(ctx, None, self.instances);
+	}
+
+	/// Draws one copy of the mesh for each instance in the
+	/// `instance_buffer`.
+	///
+	/// The instance data is available to the vertex shader as vertex
+	/// attributes that advance once per instance instead of once per
+	/// vertex. This ignores [`Mesh::instances`] in favor of
+	/// [`InstanceBuffer::range`].
+	pub fn draw_instanced<T: Pod + HasVertexAttributes>(
+		&self,
+		ctx: &mut Context,
+		instance_buffer: &InstanceBuffer<T>,
+	) {
+		let _span = tracy_client::span!();
+		let instances = instance_buffer.range.unwrap_or((0, instance_buffer.len()));
+		if instances.0 >= instances.1 {
+			return;
+		}
+		self.queue_draw_command(
+			ctx,
+			Some((instance_buffer.buffer.clone(), instance_buffer.layout())),
+			instances,
+		);
+	}
+
+	fn queue_draw_command(
+		&self,
+		ctx: &mut Context,
+		instance_buffer: Option<(Buffer, InstanceLayout)>,
+		instances: (u32, u32),
+	) {
 		if self.num_indices == 0 {
 			return;
 		}
@@ -144,8 +189,10 @@ impl<V: Vertex> Mesh<V> {
 			.queue_draw_command::<V>(QueueDrawCommandSettings {
 				vertex_buffer: self.vertex_buffer.clone(),
 				index_buffer: self.index_buffer.clone(),
+				instance_buffer,
 				range: self.range.unwrap_or((0, self.num_indices)),
-				instances: self.instances,
+				base_vertex: self.base_vertex,
+				instances,
 				texture: self
 					.texture
 					.as_ref()
@@ -154,6 +201,7 @@ impl<V: Vertex> Mesh<V> {
 				transform: self.transform,
 				color: self.color,
 				blend_mode: self.blend_mode,
+				palette: self.palette.clone(),
 			});
 	}
 }
@@ -266,4 +314,23 @@ impl Mesh<Vertex2d> {
 			.with_simple_polyline(stroke_width, points, LinSrgba::WHITE)
 			.build(ctx)
 	}
+
+	/// Creates a new mesh from a path made of lines and curves.
+	pub fn path(ctx: &Context, style: ShapeStyle, path: &PathBuilder) -> Self {
+		MeshBuilder::new()
+			.with_path(style, path, LinSrgba::WHITE)
+			.build(ctx)
+	}
+
+	/// Creates a new mesh from the shapes in an SVG file.
+	///
+	/// See [`MeshBuilder::from_svg_file`] for which parts of SVG
+	/// are supported.
+	pub fn from_svg_file(
+		ctx: &Context,
+		path: impl AsRef<std::path::Path>,
+		settings: &SvgSettings,
+	) -> Result<Self, LoadSvgError> {
+		Ok(MeshBuilder::from_svg_file(path, settings)?.build(ctx))
+	}
 }
@@ -1,16 +1,25 @@
+mod paint;
+mod path_builder;
+mod stroke;
+mod stroke_settings;
+mod svg;
 mod vertex_constructors;
 
-pub use lyon_tessellation::path::builder::BorderRadii;
+pub use lyon_tessellation::{FillRule, LineCap, LineJoin, path::builder::BorderRadii};
+pub use paint::*;
+pub use path_builder::*;
+pub use stroke_settings::*;
+pub use svg::*;
 
 use std::fmt::Debug;
 
 use glam::Vec2;
 use lyon_tessellation::{
 	BuffersBuilder, FillOptions, FillTessellator, StrokeOptions, StrokeTessellator, VertexBuffers,
-	geom::euclid::Point2D,
+	geom::{Angle, euclid::Point2D},
 	path::{
-		Winding,
-		traits::{Build, PathBuilder},
+		Path, Winding,
+		traits::{Build, PathBuilder as _},
 	},
 };
 use palette::LinSrgba;
@@ -23,11 +32,15 @@ use crate::{
 
 use super::Mesh;
 
+use stroke::{FlattenedPath, flatten_path, normalized_dash_pattern};
+
 /// Creates a [`Mesh`] out of various shapes.
 #[derive(Debug, Clone)]
 pub struct MeshBuilder {
 	pub(crate) buffers: VertexBuffers<Vertex2d, u32>,
 	pub tolerance: f32,
+	/// How strokes are drawn.
+	pub stroke_settings: StrokeSettings,
 }
 
 impl MeshBuilder {
@@ -36,12 +49,13 @@ impl MeshBuilder {
 		Self {
 			buffers: VertexBuffers::new(),
 			tolerance: FillOptions::DEFAULT_TOLERANCE,
+			stroke_settings: StrokeSettings::default(),
 		}
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds a rectangle to the mesh.
-	pub fn rectangle(style: ShapeStyle, rect: Rect, color: impl Into<LinSrgba>) -> Self {
-		Self::new().with_rectangle(style, rect, color)
+	pub fn rectangle(style: ShapeStyle, rect: Rect, paint: impl Into<Paint>) -> Self {
+		Self::new().with_rectangle(style, rect, paint)
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds a rounded rectangle to the mesh.
@@ -49,14 +63,14 @@ impl MeshBuilder {
 		style: ShapeStyle,
 		rect: Rect,
 		radii: BorderRadii,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		Self::new().with_rounded_rectangle(style, rect, radii, color)
+		Self::new().with_rounded_rectangle(style, rect, radii, paint)
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds a circle to the mesh.
-	pub fn circle(style: ShapeStyle, circle: Circle, color: impl Into<LinSrgba>) -> Self {
-		Self::new().with_circle(style, circle, color)
+	pub fn circle(style: ShapeStyle, circle: Circle, paint: impl Into<Paint>) -> Self {
+		Self::new().with_circle(style, circle, paint)
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds an ellipse to the mesh.
@@ -65,9 +79,9 @@ impl MeshBuilder {
 		center: impl Into<Vec2>,
 		radii: impl Into<Vec2>,
 		rotation: f32,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		Self::new().with_ellipse(style, center, radii, rotation, color)
+		Self::new().with_ellipse(style, center, radii, rotation, paint)
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds a filled polygon to the mesh.
@@ -88,9 +102,9 @@ impl MeshBuilder {
 	pub fn simple_polygon(
 		style: ShapeStyle,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		Self::new().with_simple_polygon(style, points, color)
+		Self::new().with_simple_polygon(style, points, paint)
 	}
 
 	/// Creates a new [`MeshBuilder`] and adds a polyline to the mesh where all of the
@@ -98,18 +112,58 @@ impl MeshBuilder {
 	pub fn simple_polyline(
 		stroke_width: f32,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		Self::new().with_simple_polyline(stroke_width, points, color)
+		Self::new().with_simple_polyline(stroke_width, points, paint)
+	}
+
+	/// Creates a new [`MeshBuilder`] and adds a path to the mesh.
+	pub fn path(style: ShapeStyle, path: &PathBuilder, paint: impl Into<Paint>) -> Self {
+		Self::new().with_path(style, path, paint)
+	}
+
+	/// Creates a new [`MeshBuilder`] from the shapes in an SVG file.
+	///
+	/// Paths, basic shapes (`rect`, `circle`, `ellipse`, `line`, `polyline`,
+	/// and `polygon`), groups, transforms, fills, strokes, and linear and
+	/// radial gradients are supported. Other elements, like text, images,
+	/// masks, and clip paths, are skipped, and attributes with values
+	/// that aren't supported are ignored with a warning.
+	///
+	/// The SVG's view box is scaled to fit its `width` and `height`,
+	/// so one unit in the mesh is one pixel of the SVG.
+	pub fn from_svg_file(
+		path: impl AsRef<std::path::Path>,
+		settings: &SvgSettings,
+	) -> Result<Self, LoadSvgError> {
+		let _span = tracy_client::span!();
+		let source = std::fs::read_to_string(path)?;
+		svg::load_svg(&source, settings)
+	}
+
+	/// Creates a new [`MeshBuilder`] from the contents of an SVG file.
+	///
+	/// See [`from_svg_file`](Self::from_svg_file) for which parts of SVG
+	/// are supported.
+	pub fn from_svg_str(source: &str, settings: &SvgSettings) -> Result<Self, LoadSvgError> {
+		svg::load_svg(source, settings)
 	}
 
 	pub fn with_tolerance(self, tolerance: f32) -> Self {
 		Self { tolerance, ..self }
 	}
 
+	/// Sets how strokes added after this are drawn.
+	pub fn with_stroke_settings(self, stroke_settings: StrokeSettings) -> Self {
+		Self {
+			stroke_settings,
+			..self
+		}
+	}
+
 	/// Adds a rectangle to the mesh.
-	pub fn add_rectangle(&mut self, style: ShapeStyle, rect: Rect, color: impl Into<LinSrgba>) {
-		self.add_rectangle_inner(style, rect, color.into())
+	pub fn add_rectangle(&mut self, style: ShapeStyle, rect: Rect, paint: impl Into<Paint>) {
+		self.add_rectangle_inner(style, rect, &paint.into())
 	}
 
 	/// Adds a rectangle to the mesh and returns the [`MeshBuilder`].
@@ -117,9 +171,9 @@ impl MeshBuilder {
 		mut self,
 		style: ShapeStyle,
 		rect: Rect,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_rectangle(style, rect, color);
+		self.add_rectangle(style, rect, paint);
 		self
 	}
 
@@ -128,9 +182,9 @@ impl MeshBuilder {
 		style: ShapeStyle,
 		rect: Rect,
 		radii: BorderRadii,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) {
-		self.add_rounded_rectangle_inner(style, rect, radii, color.into());
+		self.add_rounded_rectangle_inner(style, rect, radii, &paint.into());
 	}
 
 	pub fn with_rounded_rectangle(
@@ -138,15 +192,15 @@ impl MeshBuilder {
 		style: ShapeStyle,
 		rect: Rect,
 		radii: BorderRadii,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_rounded_rectangle(style, rect, radii, color);
+		self.add_rounded_rectangle(style, rect, radii, paint);
 		self
 	}
 
 	/// Adds a circle to the mesh.
-	pub fn add_circle(&mut self, style: ShapeStyle, circle: Circle, color: impl Into<LinSrgba>) {
-		self.add_circle_inner(style, circle, color.into())
+	pub fn add_circle(&mut self, style: ShapeStyle, circle: Circle, paint: impl Into<Paint>) {
+		self.add_circle_inner(style, circle, &paint.into())
 	}
 
 	/// Adds a circle to the mesh and returns the [`MeshBuilder`].
@@ -154,9 +208,9 @@ impl MeshBuilder {
 		mut self,
 		style: ShapeStyle,
 		circle: Circle,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_circle(style, circle, color);
+		self.add_circle(style, circle, paint);
 		self
 	}
 
@@ -167,9 +221,9 @@ impl MeshBuilder {
 		center: impl Into<Vec2>,
 		radii: impl Into<Vec2>,
 		rotation: f32,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) {
-		self.add_ellipse_inner(style, center.into(), radii.into(), rotation, color.into())
+		self.add_ellipse_inner(style, center.into(), radii.into(), rotation, &paint.into())
 	}
 
 	/// Adds an ellipse to the mesh and returns the [`MeshBuilder`].
@@ -179,9 +233,31 @@ impl MeshBuilder {
 		center: impl Into<Vec2>,
 		radii: impl Into<Vec2>,
 		rotation: f32,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_ellipse(style, center, radii, rotation, color);
+		self.add_ellipse(style, center, radii, rotation, paint);
+		self
+	}
+
+	/// Adds a path made of lines and curves to the mesh.
+	///
+	/// When filled, the path's [`fill_rule`](PathBuilder::fill_rule)
+	/// decides which parts are inside the shape. Open subpaths are closed
+	/// automatically when filling.
+	pub fn add_path(&mut self, style: ShapeStyle, path: &PathBuilder, paint: impl Into<Paint>) {
+		let _span = tracy_client::span!();
+		self.add_lyon_path(style, &path.to_lyon_path(), path.fill_rule, &paint.into());
+	}
+
+	/// Adds a path made of lines and curves to the mesh and returns the
+	/// [`MeshBuilder`].
+	pub fn with_path(
+		mut self,
+		style: ShapeStyle,
+		path: &PathBuilder,
+		paint: impl Into<Paint>,
+	) -> Self {
+		self.add_path(style, path, paint);
 		self
 	}
 
@@ -245,46 +321,12 @@ impl MeshBuilder {
 		closed: bool,
 	) {
 		let _span = tracy_client::span!();
-		let mut stroke_tessellator = StrokeTessellator::new();
-		let mut buffers_builder = BuffersBuilder::new(
-			&mut self.buffers,
-			vertex_constructors::PointWithColorToVertex,
-		);
-		let options = StrokeOptions::default()
-			.with_tolerance(self.tolerance)
-			.with_variable_line_width(4);
-		let mut builder =
-			stroke_tessellator.builder_with_attributes(5, &options, &mut buffers_builder);
-		let mut points = points.into_iter();
-		let point: StrokePoint = points
-			.next()
-			.expect("cannot build a polyline with no points")
-			.into();
-		builder.begin(
-			Point2D::new(point.position.x, point.position.y),
-			&[
-				point.color.red,
-				point.color.green,
-				point.color.blue,
-				point.color.alpha,
-				point.stroke_width,
-			],
+		let path = FlattenedPath::new(points.into_iter().map(Into::into), closed);
+		assert!(
+			!path.points.is_empty(),
+			"cannot build a polyline with no points"
 		);
-		for point in points {
-			let point: StrokePoint = point.into();
-			builder.line_to(
-				Point2D::new(point.position.x, point.position.y),
-				&[
-					point.color.red,
-					point.color.green,
-					point.color.blue,
-					point.color.alpha,
-					point.stroke_width,
-				],
-			);
-		}
-		builder.end(closed);
-		builder.build().unwrap();
+		self.add_stroke(vec![path], &Paint::Solid(LinSrgba::new(1.0, 1.0, 1.0, 1.0)));
 	}
 
 	/// Adds a polyline to the mesh and returns the [`MeshBuilder`].
@@ -303,9 +345,9 @@ impl MeshBuilder {
 		&mut self,
 		style: ShapeStyle,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) {
-		self.add_simple_polygon_inner(style, points, color.into())
+		self.add_simple_polygon_inner(style, points, &paint.into())
 	}
 
 	/// Adds a polygon to the mesh where all of the vertices have
@@ -315,9 +357,9 @@ impl MeshBuilder {
 		mut self,
 		style: ShapeStyle,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_simple_polygon(style, points, color);
+		self.add_simple_polygon(style, points, paint);
 		self
 	}
 
@@ -327,9 +369,9 @@ impl MeshBuilder {
 		&mut self,
 		stroke_width: f32,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) {
-		self.add_simple_polyline_inner(stroke_width, points, color.into())
+		self.add_simple_polyline_inner(stroke_width, points, &paint.into())
 	}
 
 	/// Adds a polyline to the mesh where all of the vertices have
@@ -338,9 +380,9 @@ impl MeshBuilder {
 		mut self,
 		stroke_width: f32,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: impl Into<LinSrgba>,
+		paint: impl Into<Paint>,
 	) -> Self {
-		self.add_simple_polyline(stroke_width, points, color);
+		self.add_simple_polyline(stroke_width, points, paint);
 		self
 	}
 
@@ -371,30 +413,11 @@ impl MeshBuilder {
 		Mesh::new(ctx, &self.buffers.vertices, &self.buffers.indices)
 	}
 
-	fn add_rectangle_inner(&mut self, style: ShapeStyle, rect: Rect, color: LinSrgba) {
+	fn add_rectangle_inner(&mut self, style: ShapeStyle, rect: Rect, paint: &Paint) {
 		let _span = tracy_client::span!();
-		let mut buffers_builder = BuffersBuilder::new(
-			&mut self.buffers,
-			vertex_constructors::PointWithoutColorToVertex { color },
-		);
-		match style {
-			ShapeStyle::Fill => FillTessellator::new()
-				.tessellate_rectangle(
-					&rect.into(),
-					&FillOptions::default().with_tolerance(self.tolerance),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-			ShapeStyle::Stroke(width) => StrokeTessellator::new()
-				.tessellate_rectangle(
-					&rect.into(),
-					&StrokeOptions::default()
-						.with_tolerance(self.tolerance)
-						.with_line_width(width),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-		};
+		let mut builder = Path::builder();
+		builder.add_rectangle(&rect.into(), Winding::Positive);
+		self.add_lyon_path(style, &builder.build(), FillRule::NonZero, paint);
 	}
 
 	fn add_rounded_rectangle_inner(
@@ -402,59 +425,23 @@ impl MeshBuilder {
 		style: ShapeStyle,
 		rect: Rect,
 		radii: BorderRadii,
-		color: LinSrgba,
+		paint: &Paint,
 	) {
 		let _span = tracy_client::span!();
-		let mut buffers_builder = BuffersBuilder::new(
-			&mut self.buffers,
-			vertex_constructors::PointWithoutColorToVertex { color },
-		);
-		match style {
-			ShapeStyle::Fill => {
-				let mut fill_tessellator = FillTessellator::new();
-				let options = FillOptions::default().with_tolerance(self.tolerance);
-				let mut builder = fill_tessellator.builder(&options, &mut buffers_builder);
-				builder.add_rounded_rectangle(&rect.into(), &radii, Winding::Positive);
-				builder.build().unwrap()
-			}
-			ShapeStyle::Stroke(width) => {
-				let mut stroke_tessellator = StrokeTessellator::new();
-				let options = StrokeOptions::default()
-					.with_line_width(width)
-					.with_tolerance(self.tolerance);
-				let mut builder = stroke_tessellator.builder(&options, &mut buffers_builder);
-				builder.add_rounded_rectangle(&rect.into(), &radii, Winding::Positive);
-				builder.build().unwrap()
-			}
-		};
+		let mut builder = Path::builder();
+		builder.add_rounded_rectangle(&rect.into(), &radii, Winding::Positive);
+		self.add_lyon_path(style, &builder.build(), FillRule::NonZero, paint);
 	}
 
-	fn add_circle_inner(&mut self, style: ShapeStyle, circle: Circle, color: LinSrgba) {
+	fn add_circle_inner(&mut self, style: ShapeStyle, circle: Circle, paint: &Paint) {
 		let _span = tracy_client::span!();
-		let mut buffers_builder = BuffersBuilder::new(
-			&mut self.buffers,
-			vertex_constructors::PointWithoutColorToVertex { color },
+		let mut builder = Path::builder();
+		builder.add_circle(
+			Point2D::new(circle.center.x, circle.center.y),
+			circle.radius,
+			Winding::Positive,
 		);
-		match style {
-			ShapeStyle::Fill => FillTessellator::new()
-				.tessellate_circle(
-					lyon_tessellation::math::point(circle.center.x, circle.center.y),
-					circle.radius,
-					&FillOptions::default().with_tolerance(self.tolerance),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-			ShapeStyle::Stroke(width) => StrokeTessellator::new()
-				.tessellate_circle(
-					lyon_tessellation::math::point(circle.center.x, circle.center.y),
-					circle.radius,
-					&StrokeOptions::default()
-						.with_tolerance(self.tolerance)
-						.with_line_width(width),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-		};
+		self.add_lyon_path(style, &builder.build(), FillRule::NonZero, paint);
 	}
 
 	fn add_ellipse_inner(
@@ -463,79 +450,144 @@ impl MeshBuilder {
 		center: Vec2,
 		radii: Vec2,
 		rotation: f32,
-		color: LinSrgba,
+		paint: &Paint,
 	) {
 		let _span = tracy_client::span!();
-		let mut buffers_builder = BuffersBuilder::new(
-			&mut self.buffers,
-			vertex_constructors::PointWithoutColorToVertex { color },
+		let mut builder = Path::builder();
+		builder.add_ellipse(
+			Point2D::new(center.x, center.y),
+			lyon_tessellation::math::vector(radii.x, radii.y),
+			Angle::radians(rotation),
+			Winding::Positive,
 		);
-		match style {
-			ShapeStyle::Fill => FillTessellator::new()
-				.tessellate_ellipse(
-					lyon_tessellation::math::point(center.x, center.y),
-					lyon_tessellation::math::vector(radii.x, radii.y),
-					lyon_tessellation::math::Angle::radians(rotation),
-					Winding::Positive,
-					&FillOptions::default().with_tolerance(self.tolerance),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-			ShapeStyle::Stroke(width) => StrokeTessellator::new()
-				.tessellate_ellipse(
-					lyon_tessellation::math::point(center.x, center.y),
-					lyon_tessellation::math::vector(radii.x, radii.y),
-					lyon_tessellation::math::Angle::radians(rotation),
-					Winding::Positive,
-					&StrokeOptions::default()
-						.with_tolerance(self.tolerance)
-						.with_line_width(width),
-					&mut buffers_builder,
-				)
-				.unwrap(),
-		};
+		self.add_lyon_path(style, &builder.build(), FillRule::NonZero, paint);
 	}
 
 	fn add_simple_polygon_inner(
 		&mut self,
 		style: ShapeStyle,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: LinSrgba,
+		paint: &Paint,
 	) {
 		let _span = tracy_client::span!();
-		match style {
-			ShapeStyle::Fill => {
-				self.add_filled_polygon(points.into_iter().map(|position| FilledPolygonPoint {
-					position: position.into(),
-					color,
-				}))
-			}
-			ShapeStyle::Stroke(stroke_width) => self.add_polyline(
-				points.into_iter().map(|position| StrokePoint {
-					position: position.into(),
-					color,
-					stroke_width,
-				}),
-				true,
-			),
-		}
+		let mut builder = Path::builder();
+		builder.add_polygon(lyon_tessellation::path::Polygon {
+			points: &points
+				.into_iter()
+				.map(|point| {
+					let point = point.into();
+					Point2D::new(point.x, point.y)
+				})
+				.collect::<Vec<_>>(),
+			closed: true,
+		});
+		self.add_lyon_path(style, &builder.build(), FillRule::NonZero, paint);
 	}
 
 	fn add_simple_polyline_inner(
 		&mut self,
 		stroke_width: f32,
 		points: impl IntoIterator<Item = impl Into<Vec2>>,
-		color: LinSrgba,
+		paint: &Paint,
 	) {
 		let _span = tracy_client::span!();
-		self.add_polyline(
+		let path = FlattenedPath::new(
 			points.into_iter().map(|position| StrokePoint {
 				position: position.into(),
-				color,
+				color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
 				stroke_width,
 			}),
 			false,
-		)
+		);
+		self.add_stroke(vec![path], paint);
+	}
+
+	fn add_lyon_path(
+		&mut self,
+		style: ShapeStyle,
+		path: &Path,
+		fill_rule: FillRule,
+		paint: &Paint,
+	) {
+		match style {
+			ShapeStyle::Fill => {
+				let first_vertex = self.buffers.vertices.len();
+				let first_index = self.buffers.indices.len();
+				FillTessellator::new()
+					.tessellate_path(
+						path,
+						&FillOptions::default()
+							.with_tolerance(self.tolerance)
+							.with_fill_rule(fill_rule),
+						&mut BuffersBuilder::new(
+							&mut self.buffers,
+							vertex_constructors::PointWithoutColorToVertex {
+								color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+							},
+						),
+					)
+					.unwrap();
+				paint::apply_paint(&mut self.buffers, first_vertex, first_index, paint);
+			}
+			ShapeStyle::Stroke(width) => {
+				let paths = flatten_path(path, self.tolerance, width);
+				self.add_stroke(paths, paint);
+			}
+		}
+	}
+
+	fn add_stroke(&mut self, paths: Vec<FlattenedPath>, paint: &Paint) {
+		let first_vertex = self.buffers.vertices.len();
+		let first_index = self.buffers.indices.len();
+		let settings = &self.stroke_settings;
+		let paths = match normalized_dash_pattern(&settings.dash_pattern) {
+			Some(pattern) => paths
+				.iter()
+				.flat_map(|path| path.dashed(&pattern, settings.dash_offset))
+				.collect(),
+			None => paths,
+		};
+		let options = StrokeOptions::default()
+			.with_tolerance(self.tolerance)
+			.with_line_cap(settings.line_cap)
+			.with_line_join(settings.line_join)
+			.with_miter_limit(settings.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT))
+			.with_variable_line_width(4);
+		let mut stroke_tessellator = StrokeTessellator::new();
+		let mut buffers_builder = BuffersBuilder::new(
+			&mut self.buffers,
+			vertex_constructors::StrokeAttributesToVertex,
+		);
+		let mut builder =
+			stroke_tessellator.builder_with_attributes(6, &options, &mut buffers_builder);
+		for path in &paths {
+			let attributes = |point: &stroke::PathPoint| {
+				[
+					point.color.red,
+					point.color.green,
+					point.color.blue,
+					point.color.alpha,
+					point.stroke_width,
+					path.texture_coord_x(point, settings.texture_coords),
+				]
+			};
+			let Some((first, rest)) = path.points.split_first() else {
+				continue;
+			};
+			builder.begin(
+				Point2D::new(first.position.x, first.position.y),
+				&attributes(first),
+			);
+			for point in rest {
+				builder.line_to(
+					Point2D::new(point.position.x, point.position.y),
+					&attributes(point),
+				);
+			}
+			builder.end(path.closed);
+		}
+		builder.build().unwrap();
+		paint::apply_paint(&mut self.buffers, first_vertex, first_index, paint);
 	}
 }
 
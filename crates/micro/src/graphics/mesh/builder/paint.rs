@@ -0,0 +1,291 @@
+#[cfg(test)]
+mod test;
+
+use std::{collections::HashMap, f32::consts::TAU};
+
+use glam::Vec2;
+use lyon_tessellation::VertexBuffers;
+use palette::{LinSrgba, Mix};
+
+use crate::graphics::Vertex2d;
+
+/// The maximum number of times a triangle is split in half to make
+/// a gradient look smooth.
+const MAX_SUBDIVISION_DEPTH: u32 = 8;
+/// How far the gradient's color can be from the interpolated vertex
+/// colors before a triangle is split.
+const COLOR_TOLERANCE: f32 = 1.0 / 255.0;
+
+/// How the inside of a shape or a stroke is colored.
+///
+/// Any color can be converted into a solid [`Paint`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+	/// A single color.
+	Solid(LinSrgba),
+	/// A gradient that changes color along the line from `start` to `end`.
+	LinearGradient {
+		/// The point where the gradient's offset is `0.0`.
+		start: Vec2,
+		/// The point where the gradient's offset is `1.0`.
+		end: Vec2,
+		/// The colors of the gradient, sorted by offset.
+		stops: Vec<GradientStop>,
+	},
+	/// A gradient that changes color with the distance from `center`.
+	RadialGradient {
+		/// The point where the gradient's offset is `0.0`.
+		center: Vec2,
+		/// The distance from the center where the gradient's offset is `1.0`.
+		radius: f32,
+		/// The colors of the gradient, sorted by offset.
+		stops: Vec<GradientStop>,
+	},
+	/// A gradient that changes color going clockwise around `center`.
+	ConicGradient {
+		/// The point the gradient sweeps around.
+		center: Vec2,
+		/// The angle (in radians) where the gradient's offset is `0.0`.
+		angle: f32,
+		/// The colors of the gradient, sorted by offset.
+		stops: Vec<GradientStop>,
+	},
+}
+
+impl Paint {
+	/// Creates a [`Paint::LinearGradient`].
+	pub fn linear_gradient(
+		start: impl Into<Vec2>,
+		end: impl Into<Vec2>,
+		stops: impl IntoIterator<Item = impl Into<GradientStop>>,
+	) -> Self {
+		Self::LinearGradient {
+			start: start.into(),
+			end: end.into(),
+			stops: sorted_stops(stops),
+		}
+	}
+
+	/// Creates a [`Paint::RadialGradient`].
+	pub fn radial_gradient(
+		center: impl Into<Vec2>,
+		radius: f32,
+		stops: impl IntoIterator<Item = impl Into<GradientStop>>,
+	) -> Self {
+		Self::RadialGradient {
+			center: center.into(),
+			radius,
+			stops: sorted_stops(stops),
+		}
+	}
+
+	/// Creates a [`Paint::ConicGradient`].
+	pub fn conic_gradient(
+		center: impl Into<Vec2>,
+		angle: f32,
+		stops: impl IntoIterator<Item = impl Into<GradientStop>>,
+	) -> Self {
+		Self::ConicGradient {
+			center: center.into(),
+			angle,
+			stops: sorted_stops(stops),
+		}
+	}
+
+	/// Returns the color of the paint at the specified `position`.
+	pub fn color_at(&self, position: impl Into<Vec2>) -> LinSrgba {
+		let position = position.into();
+		match self {
+			Paint::Solid(color) => *color,
+			Paint::LinearGradient { start, end, stops } => {
+				let direction = *end - *start;
+				let offset = (position - *start).dot(direction) / direction.length_squared();
+				gradient_color(stops, offset)
+			}
+			Paint::RadialGradient {
+				center,
+				radius,
+				stops,
+			} => gradient_color(stops, position.distance(*center) / radius),
+			Paint::ConicGradient {
+				center,
+				angle,
+				stops,
+			} => {
+				let relative = position - *center;
+				let offset = ((relative.y.atan2(relative.x) - angle) / TAU).rem_euclid(1.0);
+				gradient_color(stops, offset)
+			}
+		}
+	}
+}
+
+impl<C: Into<LinSrgba>> From<C> for Paint {
+	fn from(color: C) -> Self {
+		Self::Solid(color.into())
+	}
+}
+
+/// A color at a certain point along a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+	/// How far along the gradient the color is, from `0.0` to `1.0`.
+	pub offset: f32,
+	/// The color of the gradient at this point.
+	pub color: LinSrgba,
+}
+
+impl GradientStop {
+	/// Creates a new [`GradientStop`].
+	pub fn new(offset: f32, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			offset,
+			color: color.into(),
+		}
+	}
+}
+
+impl<C: Into<LinSrgba>> From<(f32, C)> for GradientStop {
+	fn from((offset, color): (f32, C)) -> Self {
+		Self::new(offset, color)
+	}
+}
+
+/// Multiplies the color of every vertex added since `first_vertex` with the
+/// `paint`.
+///
+/// For gradients, the triangles added since `first_index` are split up
+/// until the gradient can be represented by the vertex colors.
+pub(super) fn apply_paint(
+	buffers: &mut VertexBuffers<Vertex2d, u32>,
+	first_vertex: usize,
+	first_index: usize,
+	paint: &Paint,
+) {
+	if let Paint::Solid(color) = paint {
+		for vertex in &mut buffers.vertices[first_vertex..] {
+			vertex.color *= *color;
+		}
+		return;
+	}
+	let _span = tracy_client::span!();
+	let triangles = buffers.indices.split_off(first_index);
+	let mut subdivider = Subdivider {
+		vertices: &mut buffers.vertices,
+		indices: &mut buffers.indices,
+		midpoints: HashMap::new(),
+		paint,
+	};
+	for triangle in triangles.chunks_exact(3) {
+		subdivider.subdivide([triangle[0], triangle[1], triangle[2]], 0);
+	}
+	for vertex in &mut buffers.vertices[first_vertex..] {
+		vertex.color *= paint.color_at(vertex.position);
+	}
+}
+
+struct Subdivider<'a> {
+	vertices: &'a mut Vec<Vertex2d>,
+	indices: &'a mut Vec<u32>,
+	/// Vertices that have already been added in the middle of an edge,
+	/// so triangles sharing the edge also share the vertex.
+	midpoints: HashMap<(u32, u32), u32>,
+	paint: &'a Paint,
+}
+
+impl Subdivider<'_> {
+	fn subdivide(&mut self, triangle: [u32; 3], depth: u32) {
+		if depth >= MAX_SUBDIVISION_DEPTH || !self.needs_subdivision(triangle) {
+			self.indices.extend_from_slice(&triangle);
+			return;
+		}
+		// split the longest edge in half
+		let position = |index: u32| self.vertices[index as usize].position;
+		let longest_edge = (0..3)
+			.max_by(|&a, &b| {
+				let length =
+					|i: usize| position(triangle[i]).distance(position(triangle[(i + 1) % 3]));
+				length(a).total_cmp(&length(b))
+			})
+			.unwrap();
+		let a = triangle[longest_edge];
+		let b = triangle[(longest_edge + 1) % 3];
+		let c = triangle[(longest_edge + 2) % 3];
+		let midpoint = self.midpoint(a, b);
+		self.subdivide([a, midpoint, c], depth + 1);
+		self.subdivide([midpoint, b, c], depth + 1);
+	}
+
+	fn needs_subdivision(&self, triangle: [u32; 3]) -> bool {
+		let positions = triangle.map(|index| self.vertices[index as usize].position);
+		let colors = positions.map(|position| self.paint.color_at(position));
+		let differs = |position: Vec2, interpolated: LinSrgba| {
+			let actual = self.paint.color_at(position);
+			(actual.red - interpolated.red).abs() > COLOR_TOLERANCE
+				|| (actual.green - interpolated.green).abs() > COLOR_TOLERANCE
+				|| (actual.blue - interpolated.blue).abs() > COLOR_TOLERANCE
+				|| (actual.alpha - interpolated.alpha).abs() > COLOR_TOLERANCE
+		};
+		let edge_differs = (0..3).any(|i| {
+			let j = (i + 1) % 3;
+			differs(
+				(positions[i] + positions[j]) / 2.0,
+				colors[i].mix(colors[j], 0.5),
+			)
+		});
+		let center_differs = differs(
+			(positions[0] + positions[1] + positions[2]) / 3.0,
+			colors[0].mix(colors[1], 0.5).mix(colors[2], 1.0 / 3.0),
+		);
+		edge_differs || center_differs
+	}
+
+	fn midpoint(&mut self, a: u32, b: u32) -> u32 {
+		let key = (a.min(b), a.max(b));
+		if let Some(&index) = self.midpoints.get(&key) {
+			return index;
+		}
+		let vertex_a = self.vertices[a as usize];
+		let vertex_b = self.vertices[b as usize];
+		let index = self.vertices.len() as u32;
+		self.vertices.push(Vertex2d {
+			position: (vertex_a.position + vertex_b.position) / 2.0,
+			texture_coords: (vertex_a.texture_coords + vertex_b.texture_coords) / 2.0,
+			color: vertex_a.color.mix(vertex_b.color, 0.5),
+		});
+		self.midpoints.insert(key, index);
+		index
+	}
+}
+
+fn sorted_stops(stops: impl IntoIterator<Item = impl Into<GradientStop>>) -> Vec<GradientStop> {
+	let mut stops = stops
+		.into_iter()
+		.map(Into::into)
+		.filter(|stop: &GradientStop| stop.offset.is_finite())
+		.collect::<Vec<_>>();
+	stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+	stops
+}
+
+/// Returns the color at `offset` along a gradient with the given stops.
+///
+/// Degenerate gradients, like linear gradients whose start and end are
+/// the same point or radial gradients with a radius of zero, produce
+/// offsets that aren't finite. Those use the color of the first stop.
+fn gradient_color(stops: &[GradientStop], offset: f32) -> LinSrgba {
+	let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+		return LinSrgba::new(0.0, 0.0, 0.0, 0.0);
+	};
+	if !offset.is_finite() || offset <= first.offset {
+		return first.color;
+	}
+	if offset >= last.offset {
+		return last.color;
+	}
+	let next_index = stops.partition_point(|stop| stop.offset <= offset);
+	let previous = stops[next_index - 1];
+	let next = stops[next_index];
+	let t = (offset - previous.offset) / (next.offset - previous.offset);
+	previous.color.mix(next.color, t)
+}
@@ -0,0 +1,56 @@
+use glam::vec2;
+use palette::LinSrgba;
+
+use super::{GradientStop, Paint, gradient_color};
+
+const RED: LinSrgba = LinSrgba::new(1.0, 0.0, 0.0, 1.0);
+const BLUE: LinSrgba = LinSrgba::new(0.0, 0.0, 1.0, 1.0);
+
+fn stops() -> Vec<GradientStop> {
+	vec![GradientStop::new(0.25, RED), GradientStop::new(0.75, BLUE)]
+}
+
+#[test]
+fn interpolates_between_stops() {
+	assert_eq!(
+		gradient_color(&stops(), 0.5),
+		LinSrgba::new(0.5, 0.0, 0.5, 1.0)
+	);
+}
+
+#[test]
+fn uses_the_nearest_stop_outside_the_stops() {
+	assert_eq!(gradient_color(&stops(), 0.0), RED);
+	assert_eq!(gradient_color(&stops(), 1.0), BLUE);
+	assert_eq!(gradient_color(&[], 0.5), LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn non_finite_offsets_use_the_first_stop() {
+	for offset in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+		assert_eq!(gradient_color(&stops(), offset), RED);
+	}
+}
+
+#[test]
+fn degenerate_gradients_use_the_first_stop() {
+	let linear = Paint::linear_gradient((1.0, 1.0), (1.0, 1.0), stops());
+	assert_eq!(linear.color_at((1.0, 1.0)), RED);
+	assert_eq!(linear.color_at((5.0, 0.0)), RED);
+	let radial = Paint::radial_gradient((0.0, 0.0), 0.0, stops());
+	assert_eq!(radial.color_at((0.0, 0.0)), RED);
+	assert_eq!(radial.color_at(vec2(3.0, 4.0)), RED);
+}
+
+#[test]
+fn ignores_stops_with_non_finite_offsets() {
+	let paint = Paint::linear_gradient(
+		(0.0, 0.0),
+		(1.0, 0.0),
+		[(-f32::NAN, BLUE), (0.0, RED), (1.0, BLUE), (f32::NAN, RED)],
+	);
+	assert_eq!(
+		paint.color_at((0.5, 0.0)),
+		LinSrgba::new(0.5, 0.0, 0.5, 1.0)
+	);
+}
@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod test;
+
+mod svg;
+
+pub use svg::*;
+
+use glam::Vec2;
+use lyon_tessellation::{
+	FillRule,
+	geom::{Angle, ArcFlags},
+	math::{point, vector},
+	path::traits::SvgPathBuilder,
+};
+
+/// Describes a shape made of lines and curves that can be added to a
+/// [`MeshBuilder`](super::MeshBuilder) with
+/// [`add_path`](super::MeshBuilder::add_path).
+///
+/// A path is made of one or more subpaths, each of which starts with
+/// [`move_to`](Self::move_to) and can be closed with [`close`](Self::close).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathBuilder {
+	/// How overlapping and self-intersecting parts of the path are filled.
+	pub fill_rule: FillRule,
+	commands: Vec<PathCommand>,
+}
+
+impl PathBuilder {
+	/// Creates a new, empty [`PathBuilder`].
+	pub fn new() -> Self {
+		Self {
+			fill_rule: FillRule::NonZero,
+			commands: vec![],
+		}
+	}
+
+	/// Creates a new [`PathBuilder`] from SVG path data, like the `d`
+	/// attribute of a `<path>` element.
+	pub fn from_svg(data: &str) -> Result<Self, ParseSvgPathError> {
+		Self::new().svg(data)
+	}
+
+	/// Sets how overlapping and self-intersecting parts of the path
+	/// are filled.
+	pub fn fill_rule(self, fill_rule: FillRule) -> Self {
+		Self { fill_rule, ..self }
+	}
+
+	/// Starts a new subpath at the specified point.
+	pub fn move_to(self, to: impl Into<Vec2>) -> Self {
+		self.command(PathCommand::MoveTo(to.into()))
+	}
+
+	/// Adds a straight line from the current point to the specified point.
+	pub fn line_to(self, to: impl Into<Vec2>) -> Self {
+		self.command(PathCommand::LineTo(to.into()))
+	}
+
+	/// Adds a quadratic bezier curve from the current point to `to`
+	/// with the control point `control`.
+	pub fn quadratic_bezier_to(self, control: impl Into<Vec2>, to: impl Into<Vec2>) -> Self {
+		self.command(PathCommand::QuadraticBezierTo {
+			control: control.into(),
+			to: to.into(),
+		})
+	}
+
+	/// Adds a cubic bezier curve from the current point to `to`
+	/// with the control points `control_1` and `control_2`.
+	pub fn cubic_bezier_to(
+		self,
+		control_1: impl Into<Vec2>,
+		control_2: impl Into<Vec2>,
+		to: impl Into<Vec2>,
+	) -> Self {
+		self.command(PathCommand::CubicBezierTo {
+			control_1: control_1.into(),
+			control_2: control_2.into(),
+			to: to.into(),
+		})
+	}
+
+	/// Adds an elliptical arc around `center`, starting from the current
+	/// point and sweeping `sweep_angle` radians.
+	///
+	/// `x_rotation` is the rotation of the ellipse in radians.
+	pub fn arc(
+		self,
+		center: impl Into<Vec2>,
+		radii: impl Into<Vec2>,
+		sweep_angle: f32,
+		x_rotation: f32,
+	) -> Self {
+		self.command(PathCommand::Arc {
+			center: center.into(),
+			radii: radii.into(),
+			sweep_angle,
+			x_rotation,
+		})
+	}
+
+	/// Adds an elliptical arc from the current point to `to`, like the
+	/// `A` command in SVG path data.
+	///
+	/// Of the four possible arcs between the two points, `large_arc`
+	/// chooses between the ones sweeping more or less than 180 degrees,
+	/// and `sweep` chooses between the ones going clockwise or
+	/// counterclockwise.
+	pub fn arc_to(
+		self,
+		radii: impl Into<Vec2>,
+		x_rotation: f32,
+		large_arc: bool,
+		sweep: bool,
+		to: impl Into<Vec2>,
+	) -> Self {
+		self.command(PathCommand::ArcTo {
+			radii: radii.into(),
+			x_rotation,
+			large_arc,
+			sweep,
+			to: to.into(),
+		})
+	}
+
+	/// Closes the current subpath with a straight line back to its
+	/// starting point.
+	pub fn close(self) -> Self {
+		self.command(PathCommand::Close)
+	}
+
+	/// Parses SVG path data and adds the resulting subpaths to the path.
+	///
+	/// All commands from the SVG specification are supported, including
+	/// relative and shorthand commands.
+	pub fn svg(mut self, data: &str) -> Result<Self, ParseSvgPathError> {
+		let _span = tracy_client::span!();
+		self.commands.extend(parse_svg_path(data)?);
+		Ok(self)
+	}
+
+	/// Returns `true` if nothing has been added to the path.
+	pub fn is_empty(&self) -> bool {
+		self.commands.is_empty()
+	}
+
+	pub(super) fn to_lyon_path(&self) -> lyon_tessellation::path::Path {
+		let mut builder = lyon_tessellation::path::Path::svg_builder();
+		for command in &self.commands {
+			match *command {
+				PathCommand::MoveTo(to) => {
+					builder.move_to(point(to.x, to.y));
+				}
+				PathCommand::LineTo(to) => {
+					builder.line_to(point(to.x, to.y));
+				}
+				PathCommand::QuadraticBezierTo { control, to } => {
+					builder.quadratic_bezier_to(point(control.x, control.y), point(to.x, to.y));
+				}
+				PathCommand::CubicBezierTo {
+					control_1,
+					control_2,
+					to,
+				} => {
+					builder.cubic_bezier_to(
+						point(control_1.x, control_1.y),
+						point(control_2.x, control_2.y),
+						point(to.x, to.y),
+					);
+				}
+				PathCommand::Arc {
+					center,
+					radii,
+					sweep_angle,
+					x_rotation,
+				} => builder.arc(
+					point(center.x, center.y),
+					vector(radii.x, radii.y),
+					Angle::radians(sweep_angle),
+					Angle::radians(x_rotation),
+				),
+				PathCommand::ArcTo {
+					radii,
+					x_rotation,
+					large_arc,
+					sweep,
+					to,
+				} => builder.arc_to(
+					vector(radii.x, radii.y),
+					Angle::radians(x_rotation),
+					ArcFlags { large_arc, sweep },
+					point(to.x, to.y),
+				),
+				PathCommand::Close => builder.close(),
+			}
+		}
+		builder.build()
+	}
+
+	fn command(mut self, command: PathCommand) -> Self {
+		self.commands.push(command);
+		self
+	}
+}
+
+impl Default for PathBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathCommand {
+	MoveTo(Vec2),
+	LineTo(Vec2),
+	QuadraticBezierTo {
+		control: Vec2,
+		to: Vec2,
+	},
+	CubicBezierTo {
+		control_1: Vec2,
+		control_2: Vec2,
+		to: Vec2,
+	},
+	Arc {
+		center: Vec2,
+		radii: Vec2,
+		sweep_angle: f32,
+		x_rotation: f32,
+	},
+	ArcTo {
+		radii: Vec2,
+		x_rotation: f32,
+		large_arc: bool,
+		sweep: bool,
+		to: Vec2,
+	},
+	Close,
+}
@@ -0,0 +1,285 @@
+use derive_more::derive::{Display, Error};
+use glam::Vec2;
+
+use super::PathCommand;
+
+/// Parses SVG path data into absolute path commands.
+pub(super) fn parse_svg_path(data: &str) -> Result<Vec<PathCommand>, ParseSvgPathError> {
+	let mut parser = Parser { data, position: 0 };
+	let mut commands = vec![];
+	let mut current = Vec2::ZERO;
+	let mut subpath_start = Vec2::ZERO;
+	// the control point of the previous curve, used by the `S` and `T`
+	// commands to continue the curve smoothly
+	let mut previous_cubic_control = None;
+	let mut previous_quadratic_control = None;
+	let mut previous_command = None;
+	loop {
+		parser.skip_whitespace();
+		let Some(character) = parser.peek() else {
+			break;
+		};
+		let position = parser.position;
+		let command = if character.is_ascii_alphabetic() {
+			parser.position += 1;
+			character
+		} else {
+			match previous_command {
+				// numbers after a command repeat it, except that coordinates
+				// after a move command are treated as lines
+				Some(b'M') if parser.at_number() => b'L',
+				Some(b'm') if parser.at_number() => b'l',
+				Some(previous) if !matches!(previous, b'Z' | b'z') && parser.at_number() => {
+					previous
+				}
+				_ => {
+					parser.position = position;
+					return Err(parser.unexpected_character());
+				}
+			}
+		};
+		if previous_command.is_none() && !matches!(command, b'M' | b'm') {
+			return Err(ParseSvgPathError::MissingMoveTo { position });
+		}
+		let relative = command.is_ascii_lowercase();
+		let origin = if relative { current } else { Vec2::ZERO };
+		let mut cubic_control = None;
+		let mut quadratic_control = None;
+		match command.to_ascii_uppercase() {
+			b'M' => {
+				current = origin + parser.point()?;
+				subpath_start = current;
+				commands.push(PathCommand::MoveTo(current));
+			}
+			b'L' => {
+				current = origin + parser.point()?;
+				commands.push(PathCommand::LineTo(current));
+			}
+			b'H' => {
+				current.x = origin.x + parser.number()?;
+				commands.push(PathCommand::LineTo(current));
+			}
+			b'V' => {
+				current.y = origin.y + parser.number()?;
+				commands.push(PathCommand::LineTo(current));
+			}
+			b'C' => {
+				let control_1 = origin + parser.point()?;
+				let control_2 = origin + parser.point()?;
+				current = origin + parser.point()?;
+				cubic_control = Some(control_2);
+				commands.push(PathCommand::CubicBezierTo {
+					control_1,
+					control_2,
+					to: current,
+				});
+			}
+			b'S' => {
+				let control_1 = reflect(previous_cubic_control, current);
+				let control_2 = origin + parser.point()?;
+				current = origin + parser.point()?;
+				cubic_control = Some(control_2);
+				commands.push(PathCommand::CubicBezierTo {
+					control_1,
+					control_2,
+					to: current,
+				});
+			}
+			b'Q' => {
+				let control = origin + parser.point()?;
+				current = origin + parser.point()?;
+				quadratic_control = Some(control);
+				commands.push(PathCommand::QuadraticBezierTo {
+					control,
+					to: current,
+				});
+			}
+			b'T' => {
+				let control = reflect(previous_quadratic_control, current);
+				current = origin + parser.point()?;
+				quadratic_control = Some(control);
+				commands.push(PathCommand::QuadraticBezierTo {
+					control,
+					to: current,
+				});
+			}
+			b'A' => {
+				let radii = parser.point()?;
+				let x_rotation = parser.number()?.to_radians();
+				let large_arc = parser.flag()?;
+				let sweep = parser.flag()?;
+				current = origin + parser.point()?;
+				commands.push(PathCommand::ArcTo {
+					radii,
+					x_rotation,
+					large_arc,
+					sweep,
+					to: current,
+				});
+			}
+			b'Z' => {
+				current = subpath_start;
+				commands.push(PathCommand::Close);
+			}
+			_ => {
+				parser.position = position;
+				return Err(parser.unexpected_character());
+			}
+		}
+		previous_cubic_control = cubic_control;
+		previous_quadratic_control = quadratic_control;
+		previous_command = Some(command);
+	}
+	Ok(commands)
+}
+
+/// An error that can occur when parsing SVG path data.
+#[derive(Debug, Clone, PartialEq, Eq, Error, Display)]
+pub enum ParseSvgPathError {
+	/// A character was found that isn't a valid command or part of
+	/// a number.
+	#[display("Unexpected character '{character}' at byte {position}")]
+	UnexpectedCharacter {
+		/// The unexpected character.
+		character: char,
+		/// The byte index of the character.
+		position: usize,
+	},
+	/// A command didn't have enough numbers after it.
+	#[display("Expected a number at byte {position}")]
+	ExpectedNumber {
+		/// The byte index where the number should be.
+		position: usize,
+	},
+	/// An arc command had a flag that wasn't `0` or `1`.
+	#[display("Expected a flag (0 or 1) at byte {position}")]
+	ExpectedFlag {
+		/// The byte index where the flag should be.
+		position: usize,
+	},
+	/// The path data didn't start with a move command.
+	#[display("Expected path data to start with 'M' or 'm' at byte {position}")]
+	MissingMoveTo {
+		/// The byte index of the first command.
+		position: usize,
+	},
+}
+
+struct Parser<'a> {
+	data: &'a str,
+	position: usize,
+}
+
+impl Parser<'_> {
+	fn peek(&self) -> Option<u8> {
+		self.data.as_bytes().get(self.position).copied()
+	}
+
+	fn peek_at(&self, offset: usize) -> Option<u8> {
+		self.data.as_bytes().get(self.position + offset).copied()
+	}
+
+	fn skip_whitespace(&mut self) {
+		while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+			self.position += 1;
+		}
+	}
+
+	/// Skips whitespace and at most one comma between arguments.
+	fn skip_separator(&mut self) {
+		self.skip_whitespace();
+		if self.peek() == Some(b',') {
+			self.position += 1;
+			self.skip_whitespace();
+		}
+	}
+
+	/// Returns `true` if the next argument looks like the start of
+	/// a number.
+	fn at_number(&mut self) -> bool {
+		self.skip_separator();
+		self.peek()
+			.is_some_and(|c| c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.'))
+	}
+
+	fn point(&mut self) -> Result<Vec2, ParseSvgPathError> {
+		Ok(Vec2::new(self.number()?, self.number()?))
+	}
+
+	fn number(&mut self) -> Result<f32, ParseSvgPathError> {
+		self.skip_separator();
+		let start = self.position;
+		if matches!(self.peek(), Some(b'+' | b'-')) {
+			self.position += 1;
+		}
+		let integer_digits = self.skip_digits();
+		let mut fraction_digits = 0;
+		if self.peek() == Some(b'.') {
+			self.position += 1;
+			fraction_digits = self.skip_digits();
+		}
+		if integer_digits == 0 && fraction_digits == 0 {
+			self.position = start;
+			return Err(ParseSvgPathError::ExpectedNumber { position: start });
+		}
+		// only treat an `e` as an exponent if digits follow it
+		if matches!(self.peek(), Some(b'e' | b'E')) {
+			let sign_length = matches!(self.peek_at(1), Some(b'+' | b'-')) as usize;
+			if self
+				.peek_at(1 + sign_length)
+				.is_some_and(|c| c.is_ascii_digit())
+			{
+				self.position += 1 + sign_length;
+				self.skip_digits();
+			}
+		}
+		self.data[start..self.position]
+			.parse()
+			.map_err(|_| ParseSvgPathError::ExpectedNumber { position: start })
+	}
+
+	/// Parses an arc flag, which is always a single character, so flags
+	/// can be written without separators (like `a1 1 0 00 1 1`).
+	fn flag(&mut self) -> Result<bool, ParseSvgPathError> {
+		self.skip_separator();
+		let flag = match self.peek() {
+			Some(b'0') => false,
+			Some(b'1') => true,
+			_ => {
+				return Err(ParseSvgPathError::ExpectedFlag {
+					position: self.position,
+				});
+			}
+		};
+		self.position += 1;
+		Ok(flag)
+	}
+
+	fn skip_digits(&mut self) -> usize {
+		let start = self.position;
+		while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+			self.position += 1;
+		}
+		self.position - start
+	}
+
+	fn unexpected_character(&self) -> ParseSvgPathError {
+		ParseSvgPathError::UnexpectedCharacter {
+			character: self.data[self.position..]
+				.chars()
+				.next()
+				.unwrap_or_default(),
+			position: self.position,
+		}
+	}
+}
+
+/// Reflects the previous curve's control point around the current point,
+/// or returns the current point if the previous command wasn't a curve
+/// of the same kind.
+fn reflect(control: Option<Vec2>, current: Vec2) -> Vec2 {
+	match control {
+		Some(control) => current * 2.0 - control,
+		None => current,
+	}
+}
@@ -0,0 +1,89 @@
+use glam::vec2;
+
+use crate::graphics::mesh::{ParseSvgPathError, PathBuilder};
+
+#[test]
+fn absolute_and_relative_commands() {
+	assert_eq!(
+		PathBuilder::from_svg("M 10 10 L 20 10 l 0 10 H 0 v -5 Z"),
+		Ok(PathBuilder::new()
+			.move_to((10.0, 10.0))
+			.line_to((20.0, 10.0))
+			.line_to((20.0, 20.0))
+			.line_to((0.0, 20.0))
+			.line_to((0.0, 15.0))
+			.close())
+	);
+}
+
+#[test]
+fn implicit_repetition() {
+	assert_eq!(
+		PathBuilder::from_svg("m1,1 2,2 3-3"),
+		Ok(PathBuilder::new()
+			.move_to((1.0, 1.0))
+			.line_to((3.0, 3.0))
+			.line_to((6.0, 0.0)))
+	);
+}
+
+#[test]
+fn packed_numbers() {
+	assert_eq!(
+		PathBuilder::from_svg("M.5.5L-1-2e1 1e-1-.5"),
+		Ok(PathBuilder::new()
+			.move_to((0.5, 0.5))
+			.line_to((-1.0, -20.0))
+			.line_to((0.1, -0.5)))
+	);
+}
+
+#[test]
+fn smooth_curves() {
+	assert_eq!(
+		PathBuilder::from_svg("M0 0 C 0 1 1 1 1 0 S 2 -1 2 0 Q 3 1 4 0 T 6 0"),
+		Ok(PathBuilder::new()
+			.move_to((0.0, 0.0))
+			.cubic_bezier_to((0.0, 1.0), (1.0, 1.0), (1.0, 0.0))
+			.cubic_bezier_to((1.0, -1.0), (2.0, -1.0), (2.0, 0.0))
+			.quadratic_bezier_to((3.0, 1.0), (4.0, 0.0))
+			.quadratic_bezier_to((5.0, -1.0), (6.0, 0.0)))
+	);
+}
+
+#[test]
+fn arcs() {
+	assert_eq!(
+		PathBuilder::from_svg("M0 0 a5 5 90 1015 5"),
+		Ok(PathBuilder::new().move_to((0.0, 0.0)).arc_to(
+			vec2(5.0, 5.0),
+			90.0f32.to_radians(),
+			true,
+			false,
+			(15.0, 5.0)
+		))
+	);
+}
+
+#[test]
+fn errors() {
+	assert_eq!(
+		PathBuilder::from_svg("L 1 1"),
+		Err(ParseSvgPathError::MissingMoveTo { position: 0 })
+	);
+	assert_eq!(
+		PathBuilder::from_svg("M 1"),
+		Err(ParseSvgPathError::ExpectedNumber { position: 3 })
+	);
+	assert_eq!(
+		PathBuilder::from_svg("M 0 0 A 1 1 0 2 0 1 1"),
+		Err(ParseSvgPathError::ExpectedFlag { position: 14 })
+	);
+	assert_eq!(
+		PathBuilder::from_svg("M 0 0 X"),
+		Err(ParseSvgPathError::UnexpectedCharacter {
+			character: 'X',
+			position: 6
+		})
+	);
+}
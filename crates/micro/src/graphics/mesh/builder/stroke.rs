@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod test;
+
+use glam::Vec2;
+use lyon_tessellation::path::{Path, PathEvent, iterator::PathIterator};
+use palette::{LinSrgba, Mix};
+
+use super::{StrokePoint, StrokeTextureCoords};
+
+/// A polyline that's about to be stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct FlattenedPath {
+	/// The points of the polyline. For closed polylines, the first point
+	/// is repeated at the end so the last segment has the right distances.
+	pub points: Vec<PathPoint>,
+	pub closed: bool,
+	/// The length of the polyline this came from, before it was split
+	/// into dashes.
+	pub total_length: f32,
+}
+
+impl FlattenedPath {
+	pub fn new(points: impl IntoIterator<Item = StrokePoint>, closed: bool) -> Self {
+		let mut points = points
+			.into_iter()
+			.map(|point| PathPoint {
+				position: point.position,
+				color: point.color,
+				stroke_width: point.stroke_width,
+				distance: 0.0,
+			})
+			.collect::<Vec<_>>();
+		if closed && let Some(&first) = points.first() {
+			points.push(first);
+		}
+		let mut distance = 0.0;
+		for i in 1..points.len() {
+			distance += points[i].position.distance(points[i - 1].position);
+			points[i].distance = distance;
+		}
+		Self {
+			points,
+			closed,
+			total_length: distance,
+		}
+	}
+
+	/// Returns the texture coordinate along the length of the stroke
+	/// at the `point`.
+	pub fn texture_coord_x(&self, point: &PathPoint, texture_coords: StrokeTextureCoords) -> f32 {
+		match texture_coords {
+			StrokeTextureCoords::None => 0.0,
+			StrokeTextureCoords::Stretch if self.total_length > 0.0 => {
+				point.distance / self.total_length
+			}
+			StrokeTextureCoords::Stretch => 0.0,
+			StrokeTextureCoords::Repeat(length) if length > 0.0 => point.distance / length,
+			StrokeTextureCoords::Repeat(_) => 0.0,
+		}
+	}
+
+	/// Splits the polyline into dashes.
+	///
+	/// `pattern` must be normalized with [`normalized_dash_pattern`].
+	pub fn dashed(&self, pattern: &[f32], offset: f32) -> Vec<Self> {
+		let pattern_length = pattern.iter().sum::<f32>();
+		let mut index = 0;
+		let offset = if offset.is_finite() { offset } else { 0.0 };
+		let mut phase = offset.rem_euclid(pattern_length);
+		while phase > 0.0 && phase >= pattern[index] {
+			phase -= pattern[index];
+			index = (index + 1) % pattern.len();
+		}
+		let mut remaining = pattern[index] - phase;
+		let mut dashes = vec![];
+		let mut current = vec![];
+		if index % 2 == 0 {
+			current.extend(self.points.first().copied());
+		}
+		for segment in self.points.windows(2) {
+			let (start, end) = (segment[0], segment[1]);
+			let length = end.distance - start.distance;
+			let mut traveled = 0.0;
+			while length - traveled > remaining {
+				traveled += remaining;
+				let point = start.lerp(end, traveled / length);
+				if index % 2 == 0 {
+					push_point(&mut current, point);
+					dashes.push(self.dash(std::mem::take(&mut current)));
+				} else {
+					current = vec![point];
+				}
+				index = (index + 1) % pattern.len();
+				remaining = pattern[index];
+			}
+			remaining -= length - traveled;
+			if index % 2 == 0 {
+				push_point(&mut current, end);
+			}
+		}
+		if index % 2 == 0 && !current.is_empty() {
+			dashes.push(self.dash(current));
+		}
+		dashes
+	}
+
+	fn dash(&self, points: Vec<PathPoint>) -> Self {
+		Self {
+			points,
+			closed: false,
+			total_length: self.total_length,
+		}
+	}
+}
+
+/// Adds a point to a dash, skipping it if it's in the same place as the
+/// previous point.
+fn push_point(points: &mut Vec<PathPoint>, point: PathPoint) {
+	if points
+		.last()
+		.is_some_and(|last| last.position == point.position)
+	{
+		return;
+	}
+	points.push(point);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PathPoint {
+	pub position: Vec2,
+	pub color: LinSrgba,
+	pub stroke_width: f32,
+	/// The distance along the polyline to this point.
+	pub distance: f32,
+}
+
+impl PathPoint {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		Self {
+			position: self.position.lerp(other.position, t),
+			color: self.color.mix(other.color, t),
+			stroke_width: self.stroke_width + (other.stroke_width - self.stroke_width) * t,
+			distance: self.distance + (other.distance - self.distance) * t,
+		}
+	}
+}
+
+/// Approximates the curves of a path with straight lines.
+pub(super) fn flatten_path(path: &Path, tolerance: f32, stroke_width: f32) -> Vec<FlattenedPath> {
+	let mut paths = vec![];
+	let mut points: Vec<Vec2> = vec![];
+	for event in path.iter().flattened(tolerance) {
+		match event {
+			PathEvent::Begin { at } => points = vec![Vec2::new(at.x, at.y)],
+			PathEvent::Line { to, .. } => points.push(Vec2::new(to.x, to.y)),
+			PathEvent::End { close, .. } => {
+				// curves often end exactly where the path started, which
+				// would leave a zero-length closing segment
+				if close && points.len() > 1 && points.first() == points.last() {
+					points.pop();
+				}
+				paths.push(FlattenedPath::new(
+					points.drain(..).map(|position| StrokePoint {
+						position,
+						color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+						stroke_width,
+					}),
+					close,
+				));
+			}
+			PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {
+				unreachable!("flattened paths only contain lines")
+			}
+		}
+	}
+	paths
+}
+
+/// Returns the dash pattern to use, or `None` if strokes should be solid.
+///
+/// Patterns with negative or non-finite lengths, or that add up to zero,
+/// are ignored.
+pub(super) fn normalized_dash_pattern(pattern: &[f32]) -> Option<Vec<f32>> {
+	if pattern.is_empty()
+		|| pattern
+			.iter()
+			.any(|length| !length.is_finite() || *length < 0.0)
+		|| pattern.iter().sum::<f32>() <= 0.0
+	{
+		return None;
+	}
+	if pattern.len() % 2 == 1 {
+		Some(pattern.repeat(2))
+	} else {
+		Some(pattern.to_vec())
+	}
+}
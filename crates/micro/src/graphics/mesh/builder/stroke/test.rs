@@ -0,0 +1,98 @@
+use glam::{Vec2, vec2};
+use palette::LinSrgba;
+
+use crate::graphics::mesh::{StrokePoint, StrokeTextureCoords};
+
+use super::{FlattenedPath, normalized_dash_pattern};
+
+fn line(length: f32) -> FlattenedPath {
+	FlattenedPath::new(
+		[Vec2::ZERO, vec2(length, 0.0)].map(|position| StrokePoint {
+			position,
+			color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+			stroke_width: 1.0,
+		}),
+		false,
+	)
+}
+
+/// Returns the start and end X coordinates of each dash.
+fn dash_ranges(dashes: &[FlattenedPath]) -> Vec<(f32, f32)> {
+	dashes
+		.iter()
+		.map(|dash| {
+			(
+				dash.points.first().unwrap().position.x,
+				dash.points.last().unwrap().position.x,
+			)
+		})
+		.collect()
+}
+
+#[test]
+fn normalizes_dash_patterns() {
+	assert_eq!(normalized_dash_pattern(&[2.0, 1.0]), Some(vec![2.0, 1.0]));
+	assert_eq!(
+		normalized_dash_pattern(&[2.0]),
+		Some(vec![2.0, 2.0]),
+		"odd patterns are repeated"
+	);
+	assert_eq!(
+		normalized_dash_pattern(&[1.0, 0.0, 2.0]),
+		Some(vec![1.0, 0.0, 2.0, 1.0, 0.0, 2.0])
+	);
+}
+
+#[test]
+fn ignores_invalid_dash_patterns() {
+	for pattern in [
+		&[][..],
+		&[0.0],
+		&[0.0, 0.0],
+		&[1.0, -1.0],
+		&[1.0, f32::NAN],
+		&[f32::INFINITY, 1.0],
+	] {
+		assert_eq!(normalized_dash_pattern(pattern), None, "{pattern:?}");
+	}
+}
+
+#[test]
+fn dashes() {
+	let pattern = normalized_dash_pattern(&[2.0, 1.0]).unwrap();
+	assert_eq!(
+		dash_ranges(&line(7.0).dashed(&pattern, 0.0)),
+		[(0.0, 2.0), (3.0, 5.0), (6.0, 7.0)]
+	);
+}
+
+#[test]
+fn dash_offset() {
+	let pattern = normalized_dash_pattern(&[2.0, 1.0]).unwrap();
+	assert_eq!(
+		dash_ranges(&line(5.0).dashed(&pattern, 1.0)),
+		[(0.0, 1.0), (2.0, 4.0)]
+	);
+	assert_eq!(
+		dash_ranges(&line(5.0).dashed(&pattern, -1.0)),
+		[(1.0, 3.0), (4.0, 5.0)]
+	);
+	assert_eq!(
+		dash_ranges(&line(3.0).dashed(&pattern, f32::NAN)),
+		[(0.0, 2.0)]
+	);
+}
+
+#[test]
+fn repeating_texture_coords_with_zero_length() {
+	let line = line(4.0);
+	let end = line.points[1];
+	assert_eq!(
+		line.texture_coord_x(&end, StrokeTextureCoords::Repeat(2.0)),
+		2.0
+	);
+	assert_eq!(
+		line.texture_coord_x(&end, StrokeTextureCoords::Repeat(0.0)),
+		0.0
+	);
+}
@@ -0,0 +1,100 @@
+use lyon_tessellation::{LineCap, LineJoin, StrokeOptions};
+
+/// Settings for how a [`MeshBuilder`](super::MeshBuilder) draws strokes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeSettings {
+	/// The shape of the ends of open polylines and dashes.
+	pub line_cap: LineCap,
+	/// The shape of the corners where segments meet.
+	pub line_join: LineJoin,
+	/// How far a [`LineJoin::Miter`] corner can extend, relative to the
+	/// stroke width, before it's cut off. Must be at least `1.0`.
+	pub miter_limit: f32,
+	/// Alternating lengths of dashes and gaps. When empty, strokes
+	/// are solid.
+	///
+	/// If there's an odd number of lengths, the pattern is repeated
+	/// to make an even number.
+	pub dash_pattern: Vec<f32>,
+	/// How far into the dash pattern strokes start.
+	pub dash_offset: f32,
+	/// How texture coordinates are generated for strokes.
+	pub texture_coords: StrokeTextureCoords,
+}
+
+impl StrokeSettings {
+	/// Sets the shape of the ends of open polylines and dashes.
+	pub fn line_cap(self, line_cap: LineCap) -> Self {
+		Self { line_cap, ..self }
+	}
+
+	/// Sets the shape of the corners where segments meet.
+	pub fn line_join(self, line_join: LineJoin) -> Self {
+		Self { line_join, ..self }
+	}
+
+	/// Sets how far a [`LineJoin::Miter`] corner can extend before
+	/// it's cut off.
+	pub fn miter_limit(self, miter_limit: f32) -> Self {
+		Self {
+			miter_limit,
+			..self
+		}
+	}
+
+	/// Sets the alternating lengths of dashes and gaps.
+	pub fn dash_pattern(self, dash_pattern: impl Into<Vec<f32>>) -> Self {
+		Self {
+			dash_pattern: dash_pattern.into(),
+			..self
+		}
+	}
+
+	/// Sets how far into the dash pattern strokes start.
+	pub fn dash_offset(self, dash_offset: f32) -> Self {
+		Self {
+			dash_offset,
+			..self
+		}
+	}
+
+	/// Sets how texture coordinates are generated for strokes.
+	pub fn texture_coords(self, texture_coords: StrokeTextureCoords) -> Self {
+		Self {
+			texture_coords,
+			..self
+		}
+	}
+}
+
+impl Default for StrokeSettings {
+	fn default() -> Self {
+		Self {
+			line_cap: StrokeOptions::DEFAULT_LINE_CAP,
+			line_join: StrokeOptions::DEFAULT_LINE_JOIN,
+			miter_limit: StrokeOptions::DEFAULT_MITER_LIMIT,
+			dash_pattern: vec![],
+			dash_offset: 0.0,
+			texture_coords: StrokeTextureCoords::default(),
+		}
+	}
+}
+
+/// How texture coordinates are generated for strokes.
+///
+/// The Y texture coordinate goes from `0.0` on one side of the stroke
+/// to `1.0` on the other side. The X texture coordinate goes along
+/// the length of the stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum StrokeTextureCoords {
+	/// All texture coordinates are `0.0`.
+	#[default]
+	None,
+	/// The X texture coordinate goes from `0.0` at the start of each
+	/// polyline or shape outline to `1.0` at the end.
+	Stretch,
+	/// The X texture coordinate increases by `1.0` every time the stroke
+	/// travels the specified distance. Use with a texture whose address
+	/// mode is set to repeat.
+	Repeat(f32),
+}
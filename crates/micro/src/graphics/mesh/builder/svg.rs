@@ -0,0 +1,903 @@
+#[cfg(test)]
+mod test;
+
+mod named_colors;
+
+use std::f32::consts::SQRT_2;
+
+use derive_more::derive::{Display, Error, From};
+use glam::{Affine2, Vec2, vec2};
+use lyon_tessellation::{
+	FillOptions, FillRule, LineCap, LineJoin, StrokeOptions,
+	math::Transform,
+	path::{Path, iterator::PathIterator},
+};
+use palette::{FromColor, Hsl, LinSrgba, Srgb, WithAlpha};
+use roxmltree::{Document, Node, ParsingOptions};
+use tracing::warn;
+
+use super::{GradientStop, MeshBuilder, Paint, ParseSvgPathError, PathBuilder, ShapeStyle};
+
+use named_colors::named_color;
+
+/// The maximum number of `href` links followed when looking up
+/// the attributes of a gradient.
+const MAX_GRADIENT_LINKS: usize = 16;
+/// The size of a pixel in each supported absolute length unit. Since
+/// text isn't supported, font-relative units use the default font size
+/// of 16 pixels.
+const LENGTH_UNITS: &[(&str, f32)] = &[
+	("px", 1.0),
+	("in", 96.0),
+	("cm", 96.0 / 2.54),
+	("mm", 96.0 / 25.4),
+	("pt", 96.0 / 72.0),
+	("pc", 16.0),
+	// rem has to come before em, since it ends with em
+	("rem", 16.0),
+	("em", 16.0),
+	("ex", 8.0),
+];
+
+/// Settings for loading SVG files into meshes.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct SvgSettings {
+	/// The maximum distance between a curve and the straight lines used
+	/// to approximate it, in pixels of the loaded mesh.
+	pub tolerance: f32,
+}
+
+impl Default for SvgSettings {
+	fn default() -> Self {
+		Self {
+			tolerance: FillOptions::DEFAULT_TOLERANCE,
+		}
+	}
+}
+
+/// An error that can occur when loading an SVG file.
+#[derive(Debug, Error, Display, From)]
+pub enum LoadSvgError {
+	/// An error reading the SVG file.
+	IoError(std::io::Error),
+	/// The file is not valid XML.
+	XmlError(roxmltree::Error),
+	/// The `d` attribute of a `<path>` element couldn't be parsed.
+	PathDataError(ParseSvgPathError),
+	/// The root element of the file is not `<svg>`.
+	#[display("The root element is not <svg>")]
+	#[from(ignore)]
+	NotSvg,
+}
+
+/// Loads an SVG document into a [`MeshBuilder`].
+///
+/// Attributes and style properties with values that aren't supported
+/// are ignored with a warning.
+pub(super) fn load_svg(source: &str, settings: &SvgSettings) -> Result<MeshBuilder, LoadSvgError> {
+	let _span = tracy_client::span!();
+	let document = Document::parse_with_options(
+		source,
+		ParsingOptions {
+			allow_dtd: true,
+			..Default::default()
+		},
+	)?;
+	let root = document.root_element();
+	if root.tag_name().name() != "svg" {
+		return Err(LoadSvgError::NotSvg);
+	}
+	let view_box = parse_attribute(root, "viewBox", |value| {
+		match parse_numbers(value).as_deref() {
+			Some(&[x, y, width, height]) if width > 0.0 && height > 0.0 => {
+				Some((vec2(x, y), vec2(width, height)))
+			}
+			_ => None,
+		}
+	});
+	let mut loader = SvgLoader {
+		document: &document,
+		builder: MeshBuilder::new().with_tolerance(settings.tolerance),
+		viewport_size: view_box.map(|(_, size)| size).unwrap_or_default(),
+	};
+	// percentages are relative to whatever the SVG is embedded in,
+	// so they're treated as if the size wasn't specified
+	let root_length = |name: &str| match root.attribute(name) {
+		Some(value) if value.trim_end().ends_with('%') => None,
+		_ => loader.length(root, name, 0.0),
+	};
+	let width = root_length("width");
+	let height = root_length("height");
+	let transform = match view_box {
+		// scale the view box to fit the size of the image, keeping it
+		// centered (`preserveAspectRatio="xMidYMid meet"`)
+		Some((position, size)) => {
+			let image_size = vec2(width.unwrap_or(size.x), height.unwrap_or(size.y));
+			let scale = (image_size / size).min_element();
+			let offset = (image_size - size * scale) / 2.0;
+			Affine2::from_translation(offset)
+				* Affine2::from_scale(Vec2::splat(scale))
+				* Affine2::from_translation(-position)
+		}
+		None => {
+			loader.viewport_size = vec2(width.unwrap_or(0.0), height.unwrap_or(0.0));
+			Affine2::IDENTITY
+		}
+	};
+	loader.add_children(root, &Style::default(), transform)?;
+	Ok(loader.builder)
+}
+
+struct SvgLoader<'a, 'input> {
+	document: &'a Document<'input>,
+	builder: MeshBuilder,
+	/// The size of the view box, used to resolve percentages.
+	viewport_size: Vec2,
+}
+
+impl<'a, 'input> SvgLoader<'a, 'input> {
+	fn add_children(
+		&mut self,
+		node: Node,
+		style: &Style,
+		transform: Affine2,
+	) -> Result<(), LoadSvgError> {
+		for child in node.children().filter(Node::is_element) {
+			self.add_element(child, style, transform)?;
+		}
+		Ok(())
+	}
+
+	fn add_element(
+		&mut self,
+		node: Node,
+		parent_style: &Style,
+		parent_transform: Affine2,
+	) -> Result<(), LoadSvgError> {
+		if property(node, "display") == Some("none") {
+			return Ok(());
+		}
+		let style = self.style(node, parent_style);
+		let transform = match parse_attribute(node, "transform", parse_transform) {
+			Some(transform) => parent_transform * transform,
+			None => parent_transform,
+		};
+		let path = match node.tag_name().name() {
+			"svg" | "g" | "a" | "switch" => {
+				return self.add_children(node, &style, transform);
+			}
+			"path" => match node.attribute("d") {
+				Some(data) => PathBuilder::from_svg(data)?,
+				None => return Ok(()),
+			},
+			"rect" => {
+				let x = self.length(node, "x", self.viewport_size.x).unwrap_or(0.0);
+				let y = self.length(node, "y", self.viewport_size.y).unwrap_or(0.0);
+				let width = self
+					.length(node, "width", self.viewport_size.x)
+					.unwrap_or(0.0);
+				let height = self
+					.length(node, "height", self.viewport_size.y)
+					.unwrap_or(0.0);
+				if width <= 0.0 || height <= 0.0 {
+					return Ok(());
+				}
+				let rx = self.length(node, "rx", self.viewport_size.x);
+				let ry = self.length(node, "ry", self.viewport_size.y);
+				let radii = vec2(
+					rx.or(ry).unwrap_or(0.0).clamp(0.0, width / 2.0),
+					ry.or(rx).unwrap_or(0.0).clamp(0.0, height / 2.0),
+				);
+				rect_path(vec2(x, y), vec2(width, height), radii)
+			}
+			"circle" => {
+				let center = vec2(
+					self.length(node, "cx", self.viewport_size.x).unwrap_or(0.0),
+					self.length(node, "cy", self.viewport_size.y).unwrap_or(0.0),
+				);
+				let radius = self
+					.length(node, "r", self.viewport_diagonal())
+					.unwrap_or(0.0);
+				if radius <= 0.0 {
+					return Ok(());
+				}
+				ellipse_path(center, Vec2::splat(radius))
+			}
+			"ellipse" => {
+				let center = vec2(
+					self.length(node, "cx", self.viewport_size.x).unwrap_or(0.0),
+					self.length(node, "cy", self.viewport_size.y).unwrap_or(0.0),
+				);
+				let rx = self.length(node, "rx", self.viewport_size.x);
+				let ry = self.length(node, "ry", self.viewport_size.y);
+				let radii = vec2(rx.or(ry).unwrap_or(0.0), ry.or(rx).unwrap_or(0.0));
+				if radii.x <= 0.0 || radii.y <= 0.0 {
+					return Ok(());
+				}
+				ellipse_path(center, radii)
+			}
+			"line" => PathBuilder::new()
+				.move_to((
+					self.length(node, "x1", self.viewport_size.x).unwrap_or(0.0),
+					self.length(node, "y1", self.viewport_size.y).unwrap_or(0.0),
+				))
+				.line_to((
+					self.length(node, "x2", self.viewport_size.x).unwrap_or(0.0),
+					self.length(node, "y2", self.viewport_size.y).unwrap_or(0.0),
+				)),
+			name @ ("polyline" | "polygon") => {
+				let Some(numbers) = parse_attribute(node, "points", parse_numbers) else {
+					return Ok(());
+				};
+				let mut points = numbers
+					.chunks_exact(2)
+					.map(|point| vec2(point[0], point[1]));
+				let Some(first) = points.next() else {
+					return Ok(());
+				};
+				let path = points.fold(PathBuilder::new().move_to(first), PathBuilder::line_to);
+				if name == "polygon" {
+					path.close()
+				} else {
+					path
+				}
+			}
+			// other elements, like text, gradients, and other definitions,
+			// don't draw anything by themselves
+			_ => return Ok(()),
+		};
+		if property(node, "visibility").is_some_and(|value| value != "visible") {
+			return Ok(());
+		}
+		self.add_shape(&path.fill_rule(style.fill_rule), &style, transform);
+		Ok(())
+	}
+
+	fn add_shape(&mut self, path: &PathBuilder, style: &Style, transform: Affine2) {
+		let local_path = path.to_lyon_path();
+		let path = local_path
+			.clone()
+			.transformed(&to_lyon_transform(transform));
+		if let Some(paint) = self.paint(
+			&style.fill,
+			style.color,
+			style.fill_opacity * style.opacity,
+			&local_path,
+			transform,
+		) {
+			self.builder
+				.add_lyon_path(ShapeStyle::Fill, &path, style.fill_rule, &paint);
+		}
+		if style.stroke_width <= 0.0 {
+			return;
+		}
+		if let Some(paint) = self.paint(
+			&style.stroke,
+			style.color,
+			style.stroke_opacity * style.opacity,
+			&local_path,
+			transform,
+		) {
+			// strokes can't be skewed or stretched, so use the average scale
+			let scale = transform.matrix2.determinant().abs().sqrt();
+			let stroke_settings = self.builder.stroke_settings.clone();
+			self.builder.stroke_settings = stroke_settings
+				.clone()
+				.line_cap(style.line_cap)
+				.line_join(style.line_join)
+				.miter_limit(style.miter_limit)
+				.dash_pattern(
+					style
+						.dash_pattern
+						.iter()
+						.map(|length| length * scale)
+						.collect::<Vec<_>>(),
+				)
+				.dash_offset(style.dash_offset * scale);
+			self.builder.add_lyon_path(
+				ShapeStyle::Stroke(style.stroke_width * scale),
+				&path,
+				FillRule::NonZero,
+				&paint,
+			);
+			self.builder.stroke_settings = stroke_settings;
+		}
+	}
+
+	fn style(&self, node: Node, parent: &Style) -> Style {
+		let mut style = parent.clone();
+		if let Some(color) = parse_property(node, "color", parse_color) {
+			style.color = color;
+		}
+		if let Some(fill) = parse_property(node, "fill", SvgPaint::parse) {
+			style.fill = fill;
+		}
+		if let Some(stroke) = parse_property(node, "stroke", SvgPaint::parse) {
+			style.stroke = stroke;
+		}
+		if let Some(opacity) = parse_property(node, "fill-opacity", parse_opacity) {
+			style.fill_opacity = opacity;
+		}
+		if let Some(opacity) = parse_property(node, "stroke-opacity", parse_opacity) {
+			style.stroke_opacity = opacity;
+		}
+		// opacity should apply to groups as a whole, but multiplying it into
+		// each shape is close enough when shapes don't overlap
+		if let Some(opacity) = parse_property(node, "opacity", parse_opacity) {
+			style.opacity *= opacity;
+		}
+		if let Some(width) = parse_property(node, "stroke-width", |value| {
+			self.parse_length(value, self.viewport_diagonal())
+		}) {
+			style.stroke_width = width;
+		}
+		if let Some(fill_rule) = parse_property(node, "fill-rule", |value| match value {
+			"nonzero" => Some(FillRule::NonZero),
+			"evenodd" => Some(FillRule::EvenOdd),
+			_ => None,
+		}) {
+			style.fill_rule = fill_rule;
+		}
+		if let Some(line_cap) = parse_property(node, "stroke-linecap", |value| match value {
+			"butt" => Some(LineCap::Butt),
+			"round" => Some(LineCap::Round),
+			"square" => Some(LineCap::Square),
+			_ => None,
+		}) {
+			style.line_cap = line_cap;
+		}
+		if let Some(line_join) = parse_property(node, "stroke-linejoin", |value| match value {
+			"miter" | "miter-clip" => Some(LineJoin::Miter),
+			"round" => Some(LineJoin::Round),
+			"bevel" => Some(LineJoin::Bevel),
+			_ => None,
+		}) {
+			style.line_join = line_join;
+		}
+		if let Some(miter_limit) = parse_property(node, "stroke-miterlimit", parse_number) {
+			style.miter_limit = miter_limit;
+		}
+		if let Some(dash_pattern) = parse_property(node, "stroke-dasharray", |value| match value {
+			"none" => Some(vec![]),
+			_ => value
+				.split(|c: char| c.is_whitespace() || c == ',')
+				.filter(|length| !length.is_empty())
+				.map(|length| self.parse_length(length, self.viewport_diagonal()))
+				.collect(),
+		}) {
+			style.dash_pattern = dash_pattern;
+		}
+		if let Some(dash_offset) = parse_property(node, "stroke-dashoffset", |value| {
+			self.parse_length(value, self.viewport_diagonal())
+		}) {
+			style.dash_offset = dash_offset;
+		}
+		style
+	}
+
+	/// Converts an SVG paint into a [`Paint`], or returns `None` if
+	/// nothing should be drawn.
+	fn paint(
+		&self,
+		paint: &SvgPaint,
+		current_color: LinSrgba,
+		opacity: f32,
+		path: &Path,
+		transform: Affine2,
+	) -> Option<Paint> {
+		let paint = match paint {
+			SvgPaint::None => return None,
+			SvgPaint::Color(color) => Paint::Solid(*color),
+			SvgPaint::CurrentColor => Paint::Solid(current_color),
+			SvgPaint::Url(id) => self.gradient(id, path, transform)?,
+		};
+		Some(with_opacity(paint, opacity))
+	}
+
+	/// Converts a `<linearGradient>` or `<radialGradient>` into a [`Paint`].
+	///
+	/// Gradients are mapped into the shape's coordinate space by their
+	/// start, end, and center points, so gradients that are skewed or
+	/// stretched in one direction won't look exactly right.
+	fn gradient(&self, id: &str, path: &Path, transform: Affine2) -> Option<Paint> {
+		let node = self.element_by_id(id)?;
+		let kind = node.tag_name().name();
+		if kind != "linearGradient" && kind != "radialGradient" {
+			return None;
+		}
+		// gradients can inherit attributes and stops from other gradients
+		let mut links = vec![node];
+		while links.len() < MAX_GRADIENT_LINKS
+			&& let Some(linked) = href(*links.last().unwrap())
+				.and_then(|href| href.strip_prefix('#'))
+				.and_then(|id| self.element_by_id(id))
+		{
+			links.push(linked);
+		}
+		let attribute = |name: &str| {
+			links
+				.iter()
+				.find_map(|node| node.attribute(name).map(|value| (*node, value)))
+		};
+		let stops = links
+			.iter()
+			.find(|node| node.children().any(|child| child.has_tag_name("stop")))
+			.map(|node| gradient_stops(*node))
+			.unwrap_or_default();
+		match stops.as_slice() {
+			[] => return None,
+			[stop] => return Some(Paint::Solid(stop.color)),
+			_ => {}
+		}
+		let bounding_box_units =
+			attribute("gradientUnits").is_none_or(|(_, units)| units != "userSpaceOnUse");
+		let mut gradient_transform = transform;
+		if bounding_box_units {
+			let (min, max) = bounding_box(path)?;
+			if min.x >= max.x || min.y >= max.y {
+				return None;
+			}
+			gradient_transform *= Affine2::from_translation(min) * Affine2::from_scale(max - min);
+		}
+		if let Some((node, _)) = attribute("gradientTransform")
+			&& let Some(transform) = parse_attribute(node, "gradientTransform", parse_transform)
+		{
+			gradient_transform *= transform;
+		}
+		let coordinate = |name: &str, default: f32, reference: f32| {
+			let Some((node, _)) = attribute(name) else {
+				return default;
+			};
+			parse_attribute(node, name, |value| {
+				if bounding_box_units {
+					parse_number_or_percentage(value)
+				} else {
+					self.parse_length(value, reference)
+				}
+			})
+			.unwrap_or(default)
+		};
+		let (width, height) = if bounding_box_units {
+			(1.0, 1.0)
+		} else {
+			(self.viewport_size.x, self.viewport_size.y)
+		};
+		let paint = if kind == "linearGradient" {
+			let start = vec2(coordinate("x1", 0.0, width), coordinate("y1", 0.0, height));
+			let end = vec2(
+				coordinate("x2", width, width),
+				coordinate("y2", 0.0, height),
+			);
+			Paint::linear_gradient(
+				gradient_transform.transform_point2(start),
+				gradient_transform.transform_point2(end),
+				stops,
+			)
+		} else {
+			let diagonal = if bounding_box_units {
+				1.0
+			} else {
+				self.viewport_diagonal()
+			};
+			let center = vec2(
+				coordinate("cx", width / 2.0, width),
+				coordinate("cy", height / 2.0, height),
+			);
+			let radius = coordinate("r", diagonal / 2.0, diagonal);
+			Paint::radial_gradient(
+				gradient_transform.transform_point2(center),
+				radius * gradient_transform.matrix2.determinant().abs().sqrt(),
+				stops,
+			)
+		};
+		Some(paint)
+	}
+
+	fn element_by_id(&self, id: &str) -> Option<Node<'a, 'input>> {
+		self.document
+			.descendants()
+			.find(|node| node.attribute("id") == Some(id))
+	}
+
+	/// Returns the value of a length attribute in pixels.
+	///
+	/// Percentages are relative to `reference`.
+	fn length(&self, node: Node, name: &str, reference: f32) -> Option<f32> {
+		parse_attribute(node, name, |value| self.parse_length(value, reference))
+	}
+
+	fn parse_length(&self, value: &str, reference: f32) -> Option<f32> {
+		let value = value.trim();
+		if let Some(percentage) = value.strip_suffix('%') {
+			return parse_number(percentage)
+				.map(|percentage| percentage / 100.0 * reference)
+				.filter(|length| length.is_finite());
+		}
+		let (number, scale) = LENGTH_UNITS
+			.iter()
+			.find_map(|(unit, scale)| Some((value.strip_suffix(unit)?, *scale)))
+			.unwrap_or((value, 1.0));
+		parse_number(number)
+			.map(|length| length * scale)
+			.filter(|length| length.is_finite())
+	}
+
+	/// Returns the length percentages are relative to when they aren't
+	/// horizontal or vertical, like the radius of a circle.
+	fn viewport_diagonal(&self) -> f32 {
+		self.viewport_size.length() / SQRT_2
+	}
+}
+
+/// The presentation properties of an element, including the ones
+/// inherited from its ancestors.
+#[derive(Debug, Clone, PartialEq)]
+struct Style {
+	fill: SvgPaint,
+	stroke: SvgPaint,
+	/// The color used by `currentColor`.
+	color: LinSrgba,
+	fill_opacity: f32,
+	stroke_opacity: f32,
+	opacity: f32,
+	stroke_width: f32,
+	fill_rule: FillRule,
+	line_cap: LineCap,
+	line_join: LineJoin,
+	miter_limit: f32,
+	dash_pattern: Vec<f32>,
+	dash_offset: f32,
+}
+
+impl Default for Style {
+	fn default() -> Self {
+		Self {
+			fill: SvgPaint::Color(LinSrgba::new(0.0, 0.0, 0.0, 1.0)),
+			stroke: SvgPaint::None,
+			color: LinSrgba::new(0.0, 0.0, 0.0, 1.0),
+			fill_opacity: 1.0,
+			stroke_opacity: 1.0,
+			opacity: 1.0,
+			stroke_width: 1.0,
+			fill_rule: FillRule::NonZero,
+			line_cap: StrokeOptions::DEFAULT_LINE_CAP,
+			line_join: StrokeOptions::DEFAULT_LINE_JOIN,
+			miter_limit: StrokeOptions::DEFAULT_MITER_LIMIT,
+			dash_pattern: vec![],
+			dash_offset: 0.0,
+		}
+	}
+}
+
+/// The value of a `fill` or `stroke` property.
+#[derive(Debug, Clone, PartialEq)]
+enum SvgPaint {
+	None,
+	Color(LinSrgba),
+	CurrentColor,
+	/// A reference to a gradient with the specified ID.
+	Url(String),
+}
+
+impl SvgPaint {
+	fn parse(value: &str) -> Option<Self> {
+		match value {
+			"none" => Some(Self::None),
+			"currentColor" => Some(Self::CurrentColor),
+			_ => {
+				if let Some(url) = value.strip_prefix("url(") {
+					let (url, _fallback) = url.split_once(')')?;
+					let id = url.trim().trim_matches(['"', '\'']).strip_prefix('#')?;
+					return Some(Self::Url(id.to_string()));
+				}
+				parse_color(value).map(Self::Color)
+			}
+		}
+	}
+}
+
+/// Returns the value of a presentation property, either from the `style`
+/// attribute or from an attribute with the same name.
+fn property<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+	let from_style = node.attribute("style").and_then(|style| {
+		style
+			.split(';')
+			.filter_map(|declaration| declaration.split_once(':'))
+			.filter(|(property, _)| property.trim() == name)
+			.map(|(_, value)| value.trim())
+			.next_back()
+	});
+	from_style
+		.or_else(|| node.attribute(name).map(str::trim))
+		.filter(|value| *value != "inherit")
+}
+
+/// Parses the value of a presentation property, logging a warning if
+/// the value isn't supported.
+fn parse_property<T>(node: Node, name: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+	let value = property(node, name)?;
+	let parsed = parse(value);
+	if parsed.is_none() {
+		warn_invalid_value(node, name, value);
+	}
+	parsed
+}
+
+/// Parses the value of an attribute, logging a warning if the value
+/// isn't supported.
+fn parse_attribute<T>(node: Node, name: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+	let value = node.attribute(name)?;
+	let parsed = parse(value);
+	if parsed.is_none() {
+		warn_invalid_value(node, name, value);
+	}
+	parsed
+}
+
+fn warn_invalid_value(node: Node, name: &str, value: &str) {
+	warn!(
+		"ignoring unsupported value '{value}' for '{name}' on <{}>",
+		node.tag_name().name()
+	);
+}
+
+fn gradient_stops(node: Node) -> Vec<GradientStop> {
+	let mut stops: Vec<GradientStop> = vec![];
+	for stop in node.children().filter(|child| child.has_tag_name("stop")) {
+		let offset = parse_attribute(stop, "offset", parse_number_or_percentage)
+			.unwrap_or(0.0)
+			.clamp(0.0, 1.0);
+		// stops can't go backwards
+		let offset = stops.last().map_or(offset, |last| offset.max(last.offset));
+		let mut color = parse_property(stop, "stop-color", parse_color)
+			.unwrap_or(LinSrgba::new(0.0, 0.0, 0.0, 1.0));
+		if let Some(opacity) = parse_property(stop, "stop-opacity", parse_opacity) {
+			color.alpha *= opacity;
+		}
+		stops.push(GradientStop::new(offset, color));
+	}
+	stops
+}
+
+fn href<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+	node.attribute(("http://www.w3.org/1999/xlink", "href"))
+		.or_else(|| node.attribute("href"))
+}
+
+fn rect_path(position: Vec2, size: Vec2, radii: Vec2) -> PathBuilder {
+	let (left, top) = (position.x, position.y);
+	let (right, bottom) = (position.x + size.x, position.y + size.y);
+	if radii.x <= 0.0 || radii.y <= 0.0 {
+		return PathBuilder::new()
+			.move_to((left, top))
+			.line_to((right, top))
+			.line_to((right, bottom))
+			.line_to((left, bottom))
+			.close();
+	}
+	let (rx, ry) = (radii.x, radii.y);
+	PathBuilder::new()
+		.move_to((left + rx, top))
+		.line_to((right - rx, top))
+		.arc_to(radii, 0.0, false, true, (right, top + ry))
+		.line_to((right, bottom - ry))
+		.arc_to(radii, 0.0, false, true, (right - rx, bottom))
+		.line_to((left + rx, bottom))
+		.arc_to(radii, 0.0, false, true, (left, bottom - ry))
+		.line_to((left, top + ry))
+		.arc_to(radii, 0.0, false, true, (left + rx, top))
+		.close()
+}
+
+fn ellipse_path(center: Vec2, radii: Vec2) -> PathBuilder {
+	PathBuilder::new()
+		.move_to((center.x + radii.x, center.y))
+		.arc_to(radii, 0.0, false, true, (center.x - radii.x, center.y))
+		.arc_to(radii, 0.0, false, true, (center.x + radii.x, center.y))
+		.close()
+}
+
+/// Returns the top-left and bottom-right corners of the box around the path.
+fn bounding_box(path: &Path) -> Option<(Vec2, Vec2)> {
+	path.iter()
+		.flattened(FillOptions::DEFAULT_TOLERANCE)
+		.map(|event| {
+			let point = event.to();
+			vec2(point.x, point.y)
+		})
+		.fold(None, |bounds, point| match bounds {
+			Some((min, max)) => Some((point.min(min), point.max(max))),
+			None => Some((point, point)),
+		})
+}
+
+fn with_opacity(paint: Paint, opacity: f32) -> Paint {
+	let fade = |mut color: LinSrgba| {
+		color.alpha *= opacity;
+		color
+	};
+	let fade_stops = |stops: Vec<GradientStop>| {
+		stops
+			.into_iter()
+			.map(|stop| GradientStop::new(stop.offset, fade(stop.color)))
+			.collect()
+	};
+	match paint {
+		Paint::Solid(color) => Paint::Solid(fade(color)),
+		Paint::LinearGradient { start, end, stops } => Paint::LinearGradient {
+			start,
+			end,
+			stops: fade_stops(stops),
+		},
+		Paint::RadialGradient {
+			center,
+			radius,
+			stops,
+		} => Paint::RadialGradient {
+			center,
+			radius,
+			stops: fade_stops(stops),
+		},
+		Paint::ConicGradient {
+			center,
+			angle,
+			stops,
+		} => Paint::ConicGradient {
+			center,
+			angle,
+			stops: fade_stops(stops),
+		},
+	}
+}
+
+fn to_lyon_transform(transform: Affine2) -> Transform {
+	Transform::new(
+		transform.matrix2.x_axis.x,
+		transform.matrix2.x_axis.y,
+		transform.matrix2.y_axis.x,
+		transform.matrix2.y_axis.y,
+		transform.translation.x,
+		transform.translation.y,
+	)
+}
+
+/// Parses a `transform` attribute, like `translate(10 20) rotate(45)`.
+fn parse_transform(value: &str) -> Option<Affine2> {
+	let mut transform = Affine2::IDENTITY;
+	let mut remaining = value.trim();
+	while !remaining.is_empty() {
+		let (name, rest) = remaining.split_once('(')?;
+		let (arguments, rest) = rest.split_once(')')?;
+		transform *= match (name.trim(), parse_numbers(arguments)?.as_slice()) {
+			("matrix", &[a, b, c, d, e, f]) => Affine2::from_cols_array(&[a, b, c, d, e, f]),
+			("translate", &[x]) => Affine2::from_translation(vec2(x, 0.0)),
+			("translate", &[x, y]) => Affine2::from_translation(vec2(x, y)),
+			("scale", &[scale]) => Affine2::from_scale(Vec2::splat(scale)),
+			("scale", &[x, y]) => Affine2::from_scale(vec2(x, y)),
+			("rotate", &[angle]) => Affine2::from_angle(angle.to_radians()),
+			("rotate", &[angle, x, y]) => {
+				Affine2::from_translation(vec2(x, y))
+					* Affine2::from_angle(angle.to_radians())
+					* Affine2::from_translation(vec2(-x, -y))
+			}
+			("skewX", &[angle]) => {
+				Affine2::from_cols(Vec2::X, vec2(angle.to_radians().tan(), 1.0), Vec2::ZERO)
+			}
+			("skewY", &[angle]) => {
+				Affine2::from_cols(vec2(1.0, angle.to_radians().tan()), Vec2::Y, Vec2::ZERO)
+			}
+			_ => return None,
+		};
+		remaining = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+	}
+	Some(transform)
+}
+
+/// Parses a number, rejecting infinities and NaN.
+fn parse_number(value: &str) -> Option<f32> {
+	value
+		.trim()
+		.parse::<f32>()
+		.ok()
+		.filter(|number| number.is_finite())
+}
+
+fn parse_numbers(value: &str) -> Option<Vec<f32>> {
+	value
+		.split(|c: char| c.is_whitespace() || c == ',')
+		.filter(|number| !number.is_empty())
+		.map(parse_number)
+		.collect()
+}
+
+fn parse_number_or_percentage(value: &str) -> Option<f32> {
+	let value = value.trim();
+	match value.strip_suffix('%') {
+		Some(percentage) => parse_number(percentage).map(|value| value / 100.0),
+		None => parse_number(value),
+	}
+}
+
+fn parse_opacity(value: &str) -> Option<f32> {
+	parse_number_or_percentage(value).map(|opacity| opacity.clamp(0.0, 1.0))
+}
+
+fn parse_color(value: &str) -> Option<LinSrgba> {
+	let value = value.trim();
+	if let Some(hex) = value.strip_prefix('#') {
+		return parse_hex_color(hex);
+	}
+	if let Some((function, arguments)) = value.split_once('(') {
+		let arguments = arguments.strip_suffix(')')?;
+		let arguments = arguments
+			.split(|c: char| c.is_whitespace() || c == ',' || c == '/')
+			.filter(|argument| !argument.is_empty())
+			.collect::<Vec<_>>();
+		let (color, alpha) = match (function.trim(), arguments.as_slice()) {
+			("rgb" | "rgba", [red, green, blue, alpha @ ..]) => {
+				let channel = |value: &str| match value.strip_suffix('%') {
+					Some(percentage) => parse_number(percentage).map(|value| value / 100.0),
+					None => parse_number(value).map(|value| value / 255.0),
+				};
+				let color = Srgb::new(
+					channel(red)?.clamp(0.0, 1.0),
+					channel(green)?.clamp(0.0, 1.0),
+					channel(blue)?.clamp(0.0, 1.0),
+				);
+				(color, alpha)
+			}
+			("hsl" | "hsla", [hue, saturation, lightness, alpha @ ..]) => {
+				let hue = match hue.strip_suffix("turn") {
+					Some(turns) => parse_number(turns)? * 360.0,
+					None => parse_number(hue.strip_suffix("deg").unwrap_or(hue))?,
+				};
+				let percentage = |value: &str| {
+					parse_number(value.strip_suffix('%')?)
+						.map(|value| (value / 100.0).clamp(0.0, 1.0))
+				};
+				let color = Srgb::from_color(Hsl::new_srgb(
+					hue,
+					percentage(saturation)?,
+					percentage(lightness)?,
+				));
+				(color, alpha)
+			}
+			_ => return None,
+		};
+		let alpha = match alpha {
+			[] => 1.0,
+			[alpha] => parse_opacity(alpha)?,
+			_ => return None,
+		};
+		return Some(color.into_linear().with_alpha(alpha));
+	}
+	if value.eq_ignore_ascii_case("transparent") {
+		return Some(LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+	}
+	named_color(value).map(|color| color.into_linear().into())
+}
+
+/// Parses a `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa` color
+/// (without the `#`).
+fn parse_hex_color(hex: &str) -> Option<LinSrgba> {
+	if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	let digit = |index: usize| u8::from_str_radix(&hex[index..index + 1], 16).ok();
+	let byte = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+	let [red, green, blue, alpha] = match hex.len() {
+		3 | 4 => {
+			let alpha = if hex.len() == 4 { digit(3)? } else { 0xf };
+			[digit(0)?, digit(1)?, digit(2)?, alpha].map(|digit| digit * 0x11)
+		}
+		6 | 8 => {
+			let alpha = if hex.len() == 8 { byte(6)? } else { 0xff };
+			[byte(0)?, byte(2)?, byte(4)?, alpha]
+		}
+		_ => return None,
+	};
+	let color = Srgb::new(red, green, blue).into_linear();
+	Some(color.with_alpha(alpha as f32 / 255.0))
+}
@@ -0,0 +1,183 @@
+use glam::{Vec2, vec2};
+use palette::{LinSrgba, Srgb, WithAlpha};
+
+use crate::graphics::mesh::{LoadSvgError, MeshBuilder, SvgSettings};
+
+use super::{parse_color, parse_transform};
+
+fn load(body: &str) -> Result<MeshBuilder, LoadSvgError> {
+	MeshBuilder::from_svg_str(
+		&format!(
+			r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">{body}</svg>"#
+		),
+		&SvgSettings::default(),
+	)
+}
+
+/// Returns the top-left and bottom-right corners of the box around
+/// the vertices of the mesh.
+fn bounds(builder: &MeshBuilder) -> Option<(Vec2, Vec2)> {
+	builder
+		.buffers
+		.vertices
+		.iter()
+		.map(|vertex| vertex.position)
+		.fold(None, |bounds, point| match bounds {
+			Some((min, max)) => Some((point.min(min), point.max(max))),
+			None => Some((point, point)),
+		})
+}
+
+fn assert_bounds(builder: &MeshBuilder, min: Vec2, max: Vec2) {
+	let (actual_min, actual_max) = bounds(builder).expect("mesh is empty");
+	assert!(
+		actual_min.abs_diff_eq(min, 0.01) && actual_max.abs_diff_eq(max, 0.01),
+		"expected bounds {min}..{max}, got {actual_min}..{actual_max}"
+	);
+}
+
+fn assert_color(builder: &MeshBuilder, color: LinSrgba) {
+	assert!(!builder.buffers.vertices.is_empty(), "mesh is empty");
+	for vertex in &builder.buffers.vertices {
+		assert_eq!(vertex.color, color);
+	}
+}
+
+fn srgb(red: u8, green: u8, blue: u8) -> LinSrgba {
+	Srgb::new(red, green, blue).into_linear().into()
+}
+
+#[test]
+fn rect() {
+	let builder = load(r#"<rect x="2" y="3" width="4" height="5" rx="1" fill="red"/>"#).unwrap();
+	assert_bounds(&builder, vec2(2.0, 3.0), vec2(6.0, 8.0));
+	assert_color(&builder, srgb(255, 0, 0));
+}
+
+#[test]
+fn rect_with_invalid_size() {
+	for size in [
+		r#"width="-1" height="5""#,
+		r#"width="NaN" height="5""#,
+		r#"width="5""#,
+	] {
+		let builder = load(&format!(r#"<rect {size} rx="2"/>"#)).unwrap();
+		assert!(builder.buffers.vertices.is_empty(), "{size}");
+	}
+}
+
+#[test]
+fn circle() {
+	let builder = load(r#"<circle cx="10" cy="20" r="5"/>"#).unwrap();
+	assert_bounds(&builder, vec2(5.0, 15.0), vec2(15.0, 25.0));
+}
+
+#[test]
+fn path_with_transform() {
+	let builder =
+		load(r#"<g transform="translate(10 20) scale(2)"><path d="M0 0 L1 0 L1 1 Z"/></g>"#)
+			.unwrap();
+	assert_bounds(&builder, vec2(10.0, 20.0), vec2(12.0, 22.0));
+}
+
+#[test]
+fn transforms() {
+	let transform = parse_transform("rotate(90, 1 1) translate(1,0)").unwrap();
+	assert!(
+		transform
+			.transform_point2(Vec2::ZERO)
+			.abs_diff_eq(vec2(2.0, 1.0), 0.0001)
+	);
+	assert!(parse_transform("matrix(1 0 0 1 5 6)").is_some());
+	assert!(parse_transform("rotate(90").is_none());
+	assert!(parse_transform("spin(90)").is_none());
+	assert!(parse_transform("translate(inf)").is_none());
+}
+
+#[test]
+fn fill_and_stroke() {
+	let builder =
+		load(r#"<g fill="none" stroke="blue"><rect width="10" height="10" stroke-width="2"/></g>"#)
+			.unwrap();
+	assert_bounds(&builder, vec2(-1.0, -1.0), vec2(11.0, 11.0));
+	assert_color(&builder, srgb(0, 0, 255));
+	let builder =
+		load(r#"<rect width="10" height="10" style="fill: #0f0; fill-opacity: 50%"/>"#).unwrap();
+	assert_color(&builder, srgb(0, 255, 0).with_alpha(0.5));
+}
+
+#[test]
+fn absolute_units() {
+	let builder = MeshBuilder::from_svg_str(
+		r#"<svg xmlns="http://www.w3.org/2000/svg" width="210mm" height="297mm" viewBox="0 0 210 297">
+			<rect width="210" height="297"/>
+		</svg>"#,
+		&SvgSettings::default(),
+	)
+	.unwrap();
+	assert_bounds(&builder, Vec2::ZERO, vec2(793.7008, 1122.5197));
+	let builder = load(r#"<rect width="1in" height="12pt"/>"#).unwrap();
+	assert_bounds(&builder, Vec2::ZERO, vec2(96.0, 16.0));
+}
+
+#[test]
+fn colors() {
+	assert_eq!(parse_color("#f00"), Some(srgb(255, 0, 0)));
+	assert_eq!(
+		parse_color("#ff000080"),
+		Some(srgb(255, 0, 0).with_alpha(128.0 / 255.0))
+	);
+	assert_eq!(
+		parse_color("#0f08"),
+		Some(srgb(0, 255, 0).with_alpha(136.0 / 255.0))
+	);
+	assert_eq!(parse_color("rgb(0, 0, 255)"), Some(srgb(0, 0, 255)));
+	assert_eq!(
+		parse_color("rgba(0 0 255 / 0.5)"),
+		Some(srgb(0, 0, 255).with_alpha(0.5))
+	);
+	assert_eq!(parse_color("hsl(120, 100%, 50%)"), Some(srgb(0, 255, 0)));
+	assert_eq!(
+		parse_color("hsla(240deg 100% 50% / 25%)"),
+		Some(srgb(0, 0, 255).with_alpha(0.25))
+	);
+	assert_eq!(parse_color("CornflowerBlue"), Some(srgb(100, 149, 237)));
+	assert_eq!(parse_color("rebeccapurple"), Some(srgb(102, 51, 153)));
+	assert_eq!(
+		parse_color("transparent"),
+		Some(LinSrgba::new(0.0, 0.0, 0.0, 0.0))
+	);
+	for invalid in [
+		"#12345",
+		"#ggg",
+		"rgb(1, 2)",
+		"hsl(1, 2, 3)",
+		"notacolor",
+		"#ŧŧŧ",
+	] {
+		assert_eq!(parse_color(invalid), None, "{invalid}");
+	}
+}
+
+#[test]
+fn unsupported_values_are_ignored() {
+	let builder = load(
+		r#"<rect width="10" height="10" fill="bogus" transform="wobble(1)" stroke-width="lots"/>"#,
+	)
+	.unwrap();
+	assert_bounds(&builder, Vec2::ZERO, vec2(10.0, 10.0));
+	assert_color(&builder, srgb(0, 0, 0));
+}
+
+#[test]
+fn malformed_documents() {
+	assert!(matches!(load("<rect"), Err(LoadSvgError::XmlError(_))));
+	assert!(matches!(
+		MeshBuilder::from_svg_str("<html/>", &SvgSettings::default()),
+		Err(LoadSvgError::NotSvg)
+	));
+	assert!(matches!(
+		load(r#"<path d="M 0 0 L"/>"#),
+		Err(LoadSvgError::PathDataError(_))
+	));
+}
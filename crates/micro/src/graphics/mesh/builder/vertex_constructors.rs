@@ -1,5 +1,7 @@
 use glam::Vec2;
-use lyon_tessellation::{FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor};
+use lyon_tessellation::{
+	FillVertex, FillVertexConstructor, Side, StrokeVertex, StrokeVertexConstructor,
+};
 use palette::LinSrgba;
 
 use crate::graphics::Vertex2d;
@@ -42,13 +44,24 @@ impl FillVertexConstructor<Vertex2d> for PointWithColorToVertex {
 	}
 }
 
-impl StrokeVertexConstructor<Vertex2d> for PointWithColorToVertex {
+/// Creates stroke vertices with the color, stroke width, and X texture
+/// coordinate stored in the attributes.
+pub(super) struct StrokeAttributesToVertex;
+
+impl StrokeVertexConstructor<Vertex2d> for StrokeAttributesToVertex {
 	fn new_vertex(&mut self, mut vertex: StrokeVertex) -> Vertex2d {
 		let position = Vec2::new(vertex.position().x, vertex.position().y);
+		let side = vertex.side();
 		let attributes = vertex.interpolated_attributes();
 		Vertex2d {
 			position,
-			texture_coords: Vec2::ZERO,
+			texture_coords: Vec2::new(
+				attributes[5],
+				match side {
+					Side::Negative => 0.0,
+					Side::Positive => 1.0,
+				},
+			),
 			color: LinSrgba::new(attributes[0], attributes[1], attributes[2], attributes[3]),
 		}
 	}
@@ -0,0 +1,372 @@
+#[cfg(test)]
+mod test;
+
+use std::{
+	marker::PhantomData,
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
+};
+
+use bytemuck::Pod;
+use glam::Mat4;
+use palette::LinSrgba;
+use wgpu::{Buffer, BufferDescriptor, BufferUsages};
+
+use crate::{
+	Context,
+	color::ColorConstants,
+	graphics::{
+		BlendMode, HasVertexAttributes, InstanceBuffer, IntoIndexRange, IntoInstanceRange, Vertex,
+		Vertex2d, texture::Texture,
+	},
+	standard_draw_param_methods,
+};
+
+use super::Mesh;
+
+/// A mesh whose vertices and indices can be replaced every frame.
+///
+/// Unlike a [`Mesh`], the number of vertices and indices can change after
+/// creation. The GPU buffers grow automatically when more room is needed
+/// and are reused otherwise, so streaming new geometry every frame
+/// doesn't allocate once the mesh has reached its largest size.
+///
+/// Each draw uses the geometry the mesh had at the time, even if the
+/// geometry is replaced later in the same frame. Geometry replaced after
+/// being drawn is written to an unused part of the buffers, so the buffers
+/// grow to fit all of the geometry drawn in one frame.
+///
+/// This can be cheaply cloned. Clones will point to the same vertex and
+/// index data on the GPU until one of them has to grow its buffers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicMesh<V: Vertex = Vertex2d> {
+	vertex_buffer: Buffer,
+	index_buffer: Buffer,
+	vertex_region: BufferRegion,
+	index_region: BufferRegion,
+	num_vertices: usize,
+	num_indices: usize,
+	_phantom_data: PhantomData<V>,
+
+	// draw params
+	/// The texture to use when drawing this mesh.
+	pub texture: Option<Texture>,
+	/// The transform to use when drawing this mesh.
+	pub transform: Mat4,
+	/// The blend color to use when drawing this mesh.
+	pub color: LinSrgba,
+	/// The blend mode to use when drawing this mesh.
+	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this mesh.
+	pub palette: Option<Texture>,
+	/// The min and max vertex index to use when drawing this mesh.
+	///
+	/// Setting this results in a portion of the mesh being drawn.
+	/// When `None`, all the vertices are drawn.
+	pub range: Option<(u32, u32)>,
+	/// The min and max instance indices to use when drawing this mesh.
+	///
+	/// Only useful when drawn with a shader that does something with
+	/// the instance index.
+	pub instances: (u32, u32),
+}
+
+impl<V: Vertex> DynamicMesh<V> {
+	/// Creates a new [`DynamicMesh`] with the specified vertices and indices.
+	pub fn new(ctx: &Context, vertices: &[V], indices: &[u32]) -> Self {
+		let mut mesh = Self::with_capacity(ctx, vertices.len(), indices.len());
+		mesh.set(ctx, vertices, indices);
+		mesh
+	}
+
+	/// Creates a new empty [`DynamicMesh`] with room for the specified
+	/// number of vertices and indices.
+	pub fn with_capacity(ctx: &Context, vertex_capacity: usize, index_capacity: usize) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			vertex_buffer: create_vertex_buffer::<V>(ctx, vertex_capacity),
+			index_buffer: create_index_buffer(ctx, index_capacity),
+			vertex_region: BufferRegion::new(vertex_capacity),
+			index_region: BufferRegion::new(index_capacity),
+			num_vertices: 0,
+			num_indices: 0,
+			_phantom_data: PhantomData,
+			texture: None,
+			transform: Mat4::IDENTITY,
+			color: LinSrgba::WHITE,
+			blend_mode: BlendMode::default(),
+			palette: None,
+			range: None,
+			instances: (0, 1),
+		}
+	}
+
+	standard_draw_param_methods!();
+
+	/// Sets the texture used for drawing.
+	pub fn texture<'a>(&self, texture: impl Into<Option<&'a Texture>>) -> Self {
+		Self {
+			texture: texture.into().cloned(),
+			..self.clone()
+		}
+	}
+
+	/// Sets the range of vertex indices used for drawing.
+	///
+	/// Setting this results in a portion of the mesh being drawn.
+	/// When `None`, all the vertices are drawn.
+	pub fn range(&self, range: impl IntoIndexRange) -> Self {
+		let mut new = self.clone();
+		new.range = range.into_index_range(self.num_indices as u32);
+		new
+	}
+
+	/// Sets the range of instance indices used for drawing.
+	///
+	/// Only useful when drawn with a shader that does something with
+	/// the instance index.
+	pub fn instances(&self, instances: impl IntoInstanceRange) -> Self {
+		let mut new = self.clone();
+		new.instances = instances.into_instance_range();
+		new
+	}
+
+	/// Returns the number of vertices in the mesh.
+	pub fn num_vertices(&self) -> usize {
+		self.num_vertices
+	}
+
+	/// Returns the number of indices in the mesh.
+	pub fn num_indices(&self) -> usize {
+		self.num_indices
+	}
+
+	/// Returns the number of vertices the mesh can hold without
+	/// reallocating its vertex buffer.
+	pub fn vertex_capacity(&self) -> usize {
+		self.vertex_region.capacity
+	}
+
+	/// Returns the number of indices the mesh can hold without
+	/// reallocating its index buffer.
+	pub fn index_capacity(&self) -> usize {
+		self.index_region.capacity
+	}
+
+	/// Replaces all of the vertices and indices of the mesh.
+	pub fn set(&mut self, ctx: &Context, vertices: &[V], indices: &[u32]) {
+		self.set_vertices(ctx, vertices);
+		self.set_indices(ctx, indices);
+	}
+
+	/// Replaces all of the vertices of the mesh, keeping the existing indices.
+	pub fn set_vertices(&mut self, ctx: &Context, vertices: &[V]) {
+		let _span = tracy_client::span!();
+		if self
+			.vertex_region
+			.replace(vertices.len(), ctx.graphics.frame)
+		{
+			self.vertex_buffer = create_vertex_buffer::<V>(ctx, self.vertex_region.capacity);
+		}
+		self.num_vertices = vertices.len();
+		if !vertices.is_empty() {
+			ctx.graphics.queue.write_buffer(
+				&self.vertex_buffer,
+				(self.vertex_region.offset * std::mem::size_of::<V>()) as u64,
+				bytemuck::cast_slice(vertices),
+			);
+		}
+	}
+
+	/// Replaces all of the indices of the mesh, keeping the existing vertices.
+	pub fn set_indices(&mut self, ctx: &Context, indices: &[u32]) {
+		let _span = tracy_client::span!();
+		if self.index_region.replace(indices.len(), ctx.graphics.frame) {
+			self.index_buffer = create_index_buffer(ctx, self.index_region.capacity);
+		}
+		self.num_indices = indices.len();
+		if !indices.is_empty() {
+			ctx.graphics.queue.write_buffer(
+				&self.index_buffer,
+				(self.index_region.offset * std::mem::size_of::<u32>()) as u64,
+				bytemuck::cast_slice(indices),
+			);
+		}
+	}
+
+	/// Overwrites vertex data starting at the specified `index` without
+	/// changing the number of vertices.
+	///
+	/// Panics if the vertices would extend past the end of the mesh, or if
+	/// the mesh has already been drawn this frame (use
+	/// [`DynamicMesh::set_vertices`] instead).
+	pub fn overwrite_vertices(&self, ctx: &Context, index: usize, vertices: &[V]) {
+		let _span = tracy_client::span!();
+		assert!(
+			index + vertices.len() <= self.num_vertices,
+			"cannot write past the end of the mesh's vertices"
+		);
+		assert!(
+			!self.vertex_region.drawn_in(ctx.graphics.frame),
+			"cannot overwrite vertices that were already drawn this frame"
+		);
+		ctx.graphics.queue.write_buffer(
+			&self.vertex_buffer,
+			((self.vertex_region.offset + index) * std::mem::size_of::<V>()) as u64,
+			bytemuck::cast_slice(vertices),
+		);
+	}
+
+	/// Removes all of the vertices and indices from the mesh. The GPU
+	/// memory is kept for reuse.
+	///
+	/// Draws of the mesh earlier in the frame still use the old geometry.
+	pub fn clear(&mut self) {
+		self.num_vertices = 0;
+		self.num_indices = 0;
+	}
+
+	/// Draws the mesh.
+	pub fn draw(&self, ctx: &mut Context) {
+		self.as_mesh(ctx).draw(ctx);
+	}
+
+	/// Draws one copy of the mesh for each instance in the
+	/// `instance_buffer`.
+	///
+	/// See [`Mesh::draw_instanced`] for more details.
+	pub fn draw_instanced<T: Pod + HasVertexAttributes>(
+		&self,
+		ctx: &mut Context,
+		instance_buffer: &InstanceBuffer<T>,
+	) {
+		self.as_mesh(ctx).draw_instanced(ctx, instance_buffer);
+	}
+
+	/// Returns a [`Mesh`] that draws the current geometry and marks the
+	/// geometry as drawn this frame.
+	fn as_mesh(&self, ctx: &Context) -> Mesh<V> {
+		self.vertex_region.mark_drawn(ctx.graphics.frame);
+		self.index_region.mark_drawn(ctx.graphics.frame);
+		// the range may have been set before the geometry was replaced
+		// with fewer indices
+		let num_indices = self.num_indices as u32;
+		let (start, end) = self.range.unwrap_or((0, num_indices));
+		let end = end.min(num_indices);
+		let start = start.min(end);
+		let index_offset = self.index_region.offset as u32;
+		Mesh {
+			vertex_buffer: self.vertex_buffer.clone(),
+			index_buffer: self.index_buffer.clone(),
+			num_indices,
+			base_vertex: self.vertex_region.offset as i32,
+			_phantom_data: PhantomData,
+			texture: self.texture.clone(),
+			transform: self.transform,
+			color: self.color,
+			blend_mode: self.blend_mode,
+			palette: self.palette.clone(),
+			range: Some((index_offset + start, index_offset + end)),
+			instances: self.instances,
+		}
+	}
+}
+
+/// The part of one of a [`DynamicMesh`]'s buffers that holds the
+/// current geometry.
+#[derive(Debug, Clone)]
+struct BufferRegion {
+	offset: usize,
+	/// The number of elements written to the region.
+	len: usize,
+	/// The number of elements the buffer can hold.
+	capacity: usize,
+	/// The frame the region was last written in.
+	written_frame: Option<u64>,
+	/// The frame the region was last drawn in. Shared between clones of
+	/// the mesh, since any of them can draw the region.
+	drawn_frame: Arc<AtomicU64>,
+}
+
+impl BufferRegion {
+	const NOT_DRAWN: u64 = u64::MAX;
+
+	fn new(capacity: usize) -> Self {
+		Self {
+			offset: 0,
+			len: 0,
+			capacity,
+			written_frame: None,
+			drawn_frame: Arc::new(AtomicU64::new(Self::NOT_DRAWN)),
+		}
+	}
+
+	fn drawn_in(&self, frame: u64) -> bool {
+		self.drawn_frame.load(Ordering::Relaxed) == frame
+	}
+
+	fn mark_drawn(&self, frame: u64) {
+		self.drawn_frame.store(frame, Ordering::Relaxed);
+	}
+
+	/// Moves the region to where `len` elements can be written during
+	/// `frame` without changing geometry that was already drawn in that
+	/// frame.
+	///
+	/// Returns `true` if the buffer has to be replaced with a new one
+	/// with the region's new capacity.
+	fn replace(&mut self, len: usize, frame: u64) -> bool {
+		let offset = if self.drawn_in(frame) {
+			// draws aren't submitted until the end of the frame, so
+			// geometry that's been drawn can't be overwritten yet
+			self.drawn_frame = Arc::new(AtomicU64::new(Self::NOT_DRAWN));
+			self.offset + self.len
+		} else if self.written_frame == Some(frame) {
+			self.offset
+		} else {
+			0
+		};
+		self.len = len;
+		self.written_frame = Some(frame);
+		if offset + len <= self.capacity {
+			self.offset = offset;
+			return false;
+		}
+		// draws earlier in the frame keep using the old buffer, so the new
+		// one is made big enough to hold all of the geometry written this
+		// frame, which keeps later frames from having to grow it again
+		self.capacity = (offset + len).next_power_of_two();
+		self.offset = 0;
+		true
+	}
+}
+
+impl PartialEq for BufferRegion {
+	fn eq(&self, other: &Self) -> bool {
+		self.offset == other.offset
+			&& self.len == other.len
+			&& self.capacity == other.capacity
+			&& self.written_frame == other.written_frame
+			&& Arc::ptr_eq(&self.drawn_frame, &other.drawn_frame)
+	}
+}
+
+fn create_vertex_buffer<V: Vertex>(ctx: &Context, capacity: usize) -> Buffer {
+	ctx.graphics.device.create_buffer(&BufferDescriptor {
+		label: Some("Dynamic Mesh Vertex Buffer"),
+		size: (capacity.max(1) * std::mem::size_of::<V>()) as u64,
+		usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	})
+}
+
+fn create_index_buffer(ctx: &Context, capacity: usize) -> Buffer {
+	ctx.graphics.device.create_buffer(&BufferDescriptor {
+		label: Some("Dynamic Mesh Index Buffer"),
+		size: (capacity.max(1) * std::mem::size_of::<u32>()) as u64,
+		usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
+		mapped_at_creation: false,
+	})
+}
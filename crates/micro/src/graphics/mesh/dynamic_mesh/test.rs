@@ -0,0 +1,64 @@
+use super::BufferRegion;
+
+#[test]
+fn overwrites_geometry_that_hasnt_been_drawn() {
+	let mut region = BufferRegion::new(8);
+	assert!(!region.replace(4, 0));
+	assert_eq!(region.offset, 0);
+	assert!(!region.replace(6, 0));
+	assert_eq!(region.offset, 0);
+}
+
+#[test]
+fn writes_after_geometry_drawn_this_frame() {
+	let mut region = BufferRegion::new(8);
+	region.replace(3, 0);
+	region.mark_drawn(0);
+	assert!(!region.replace(2, 0));
+	assert_eq!(region.offset, 3);
+	// the new geometry hasn't been drawn yet, so it can be overwritten
+	assert!(!region.replace(4, 0));
+	assert_eq!(region.offset, 3);
+	region.mark_drawn(0);
+	assert!(!region.replace(1, 0));
+	assert_eq!(region.offset, 7);
+}
+
+#[test]
+fn reuses_the_buffer_in_later_frames() {
+	let mut region = BufferRegion::new(8);
+	region.replace(3, 0);
+	region.mark_drawn(0);
+	region.replace(2, 0);
+	region.mark_drawn(0);
+	assert!(!region.replace(4, 1));
+	assert_eq!(region.offset, 0);
+	// drawn in an earlier frame, so the geometry is safe to overwrite
+	region.mark_drawn(1);
+	assert!(!region.replace(2, 2));
+	assert_eq!(region.offset, 0);
+}
+
+#[test]
+fn grows_to_fit_the_geometry_of_a_whole_frame() {
+	let mut region = BufferRegion::new(4);
+	region.replace(3, 0);
+	region.mark_drawn(0);
+	assert!(region.replace(3, 0));
+	assert_eq!(region.offset, 0);
+	assert_eq!(region.capacity, 8);
+	region.mark_drawn(0);
+	assert!(!region.replace(3, 1));
+	region.mark_drawn(1);
+	assert!(!region.replace(3, 1));
+	assert_eq!(region.offset, 3);
+}
+
+#[test]
+fn clones_share_whether_geometry_was_drawn() {
+	let mut region = BufferRegion::new(8);
+	region.replace(4, 0);
+	region.clone().mark_drawn(0);
+	assert!(!region.replace(4, 0));
+	assert_eq!(region.offset, 4);
+}
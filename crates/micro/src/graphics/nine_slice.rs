@@ -0,0 +1,281 @@
+use glam::{Mat4, Vec2, vec2};
+use palette::LinSrgba;
+
+use crate::{
+	Context,
+	color::ColorConstants,
+	graphics::{BlendMode, Vertex2d, mesh::Mesh, texture::Texture},
+	math::Rect,
+	standard_draw_param_methods,
+};
+
+/// Draws a texture at any size while keeping its corners undistorted.
+///
+/// The texture is split into a 3x3 grid by the [`NineSliceInsets`]. The
+/// corners are drawn at their original size, the edges are stretched or
+/// tiled along one axis, and the center is stretched or tiled along
+/// both axes. This is useful for panels, buttons, and dialog boxes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NineSlice {
+	texture: Texture,
+	/// The portion of the texture to use, in pixels.
+	pub region: Rect,
+	/// The distances from each side of the region to the slice lines,
+	/// in pixels.
+	pub insets: NineSliceInsets,
+	/// The size to draw the nine-slice at.
+	pub size: Vec2,
+	/// How the edges fill the space between the corners.
+	pub edge_mode: NineSliceMode,
+	/// How the center fills the space between the edges.
+	pub center_mode: NineSliceMode,
+	/// The transform to use when drawing this nine-slice.
+	pub transform: Mat4,
+	/// The blend color to use when drawing this nine-slice.
+	pub color: LinSrgba,
+	/// The blend mode to use when drawing this nine-slice.
+	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this nine-slice.
+	pub palette: Option<Texture>,
+}
+
+impl NineSlice {
+	/// Creates a new [`NineSlice`] from the region of the `texture` that
+	/// would be drawn by [`Texture::draw`].
+	///
+	/// The nine-slice is initially the same size as the region.
+	pub fn new(texture: &Texture, insets: impl Into<NineSliceInsets>) -> Self {
+		Self {
+			texture: texture.clone(),
+			region: texture.region,
+			insets: insets.into(),
+			size: texture.region.size,
+			edge_mode: NineSliceMode::default(),
+			center_mode: NineSliceMode::default(),
+			transform: Mat4::IDENTITY,
+			color: LinSrgba::WHITE,
+			blend_mode: BlendMode::default(),
+			palette: None,
+		}
+	}
+
+	/// Returns the texture used by the nine-slice.
+	pub fn texture(&self) -> &Texture {
+		&self.texture
+	}
+
+	/// Sets the portion of the texture to use, in pixels.
+	pub fn region(&self, region: Rect) -> Self {
+		let mut new = self.clone();
+		new.region = region;
+		new
+	}
+
+	/// Sets the size to draw the nine-slice at.
+	///
+	/// Sizes smaller than the combined insets will cause the corners to
+	/// overlap.
+	pub fn size(&self, size: impl Into<Vec2>) -> Self {
+		let mut new = self.clone();
+		new.size = size.into();
+		new
+	}
+
+	/// Sets how the edges fill the space between the corners.
+	pub fn edge_mode(&self, edge_mode: NineSliceMode) -> Self {
+		let mut new = self.clone();
+		new.edge_mode = edge_mode;
+		new
+	}
+
+	/// Sets how the center fills the space between the edges.
+	pub fn center_mode(&self, center_mode: NineSliceMode) -> Self {
+		let mut new = self.clone();
+		new.center_mode = center_mode;
+		new
+	}
+
+	standard_draw_param_methods!();
+
+	/// Draws the nine-slice.
+	pub fn draw(&self, ctx: &mut Context) {
+		let _span = tracy_client::span!();
+		let (vertices, indices) = self.geometry();
+		if indices.is_empty() {
+			return;
+		}
+		Mesh::new(ctx, &vertices, &indices)
+			.texture(&self.texture)
+			.transformed(self.transform)
+			.color(self.color)
+			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
+			.draw(ctx)
+	}
+
+	fn geometry(&self) -> (Vec<Vertex2d>, Vec<u32>) {
+		let insets = self.insets;
+		let source_xs = [
+			self.region.top_left.x,
+			self.region.top_left.x + insets.left,
+			self.region.right() - insets.right,
+			self.region.right(),
+		];
+		let source_ys = [
+			self.region.top_left.y,
+			self.region.top_left.y + insets.top,
+			self.region.bottom() - insets.bottom,
+			self.region.bottom(),
+		];
+		let display_xs = [0.0, insets.left, self.size.x - insets.right, self.size.x];
+		let display_ys = [0.0, insets.top, self.size.y - insets.bottom, self.size.y];
+		let mut vertices = vec![];
+		let mut indices = vec![];
+		for row in 0..3 {
+			for column in 0..3 {
+				let source = Rect::from_corners(
+					vec2(source_xs[column], source_ys[row]),
+					vec2(source_xs[column + 1], source_ys[row + 1]),
+				);
+				let display = Rect::from_corners(
+					vec2(display_xs[column], display_ys[row]),
+					vec2(display_xs[column + 1], display_ys[row + 1]),
+				);
+				let mode = match (row, column) {
+					(1, 1) => self.center_mode,
+					(1, _) | (_, 1) => self.edge_mode,
+					_ => NineSliceMode::Stretch,
+				};
+				let tile_x = mode == NineSliceMode::Tile && column == 1;
+				let tile_y = mode == NineSliceMode::Tile && row == 1;
+				self.add_slice(&mut vertices, &mut indices, source, display, tile_x, tile_y);
+			}
+		}
+		(vertices, indices)
+	}
+
+	fn add_slice(
+		&self,
+		vertices: &mut Vec<Vertex2d>,
+		indices: &mut Vec<u32>,
+		source: Rect,
+		display: Rect,
+		tile_x: bool,
+		tile_y: bool,
+	) {
+		if source.size.x <= 0.0 || source.size.y <= 0.0 {
+			return;
+		}
+		if display.size.x <= 0.0 || display.size.y <= 0.0 {
+			return;
+		}
+		let tile_size = vec2(
+			if tile_x {
+				source.size.x
+			} else {
+				display.size.x
+			},
+			if tile_y {
+				source.size.y
+			} else {
+				display.size.y
+			},
+		);
+		let mut y = display.top_left.y;
+		while y < display.bottom() {
+			let height = tile_size.y.min(display.bottom() - y);
+			let mut x = display.top_left.x;
+			while x < display.right() {
+				let width = tile_size.x.min(display.right() - x);
+				// partial tiles at the end of a row or column only use
+				// part of the source rect instead of being squished
+				let source_size = vec2(
+					if tile_x { width } else { source.size.x },
+					if tile_y { height } else { source.size.y },
+				);
+				let texture_region = self
+					.texture
+					.relative_rect(Rect::new(source.top_left, source_size));
+				let start_index = vertices.len() as u32;
+				vertices.extend(
+					Rect::new(vec2(x, y), vec2(width, height))
+						.corners()
+						.into_iter()
+						.zip(texture_region.corners())
+						.map(|(position, texture_coords)| Vertex2d {
+							position,
+							texture_coords,
+							color: LinSrgba::WHITE,
+						}),
+				);
+				indices.extend_from_slice(&[
+					start_index,
+					start_index + 1,
+					start_index + 3,
+					start_index + 1,
+					start_index + 2,
+					start_index + 3,
+				]);
+				x += width;
+			}
+			y += height;
+		}
+	}
+}
+
+/// The distances from each side of a [`NineSlice`]'s texture region to the
+/// lines that split it into nine slices.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub struct NineSliceInsets {
+	/// The width of the left column.
+	pub left: f32,
+	/// The height of the top row.
+	pub top: f32,
+	/// The width of the right column.
+	pub right: f32,
+	/// The height of the bottom row.
+	pub bottom: f32,
+}
+
+impl NineSliceInsets {
+	/// Creates a new [`NineSliceInsets`].
+	pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+		Self {
+			left,
+			top,
+			right,
+			bottom,
+		}
+	}
+
+	/// Creates a new [`NineSliceInsets`] with the same distance on
+	/// every side.
+	pub fn uniform(inset: f32) -> Self {
+		Self::new(inset, inset, inset, inset)
+	}
+
+	/// Creates a new [`NineSliceInsets`] with the same distance on the left
+	/// and right (`x`) and on the top and bottom (`y`).
+	pub fn symmetric(inset: impl Into<Vec2>) -> Self {
+		let inset = inset.into();
+		Self::new(inset.x, inset.y, inset.x, inset.y)
+	}
+}
+
+impl From<f32> for NineSliceInsets {
+	fn from(inset: f32) -> Self {
+		Self::uniform(inset)
+	}
+}
+
+/// How a slice of a [`NineSlice`] fills the space it's given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub enum NineSliceMode {
+	/// The slice is stretched to fill the space.
+	#[default]
+	Stretch,
+	/// The slice is repeated at its original size to fill the space.
+	Tile,
+}
@@ -0,0 +1,10 @@
+//! Types for simulating and drawing 2D particle effects.
+
+mod emitter_shape;
+mod particle_emitter;
+mod particle_emitter_settings;
+mod rng;
+
+pub use emitter_shape::*;
+pub use particle_emitter::*;
+pub use particle_emitter_settings::*;
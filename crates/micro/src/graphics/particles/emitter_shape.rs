@@ -0,0 +1,50 @@
+use glam::{Vec2, vec2};
+
+use crate::math::{Circle, Polygon, Rect};
+
+use super::rng::Rng;
+
+const MAX_POLYGON_SAMPLE_ATTEMPTS: usize = 32;
+
+/// The area new particles are spawned in, relative to the position of
+/// the [`ParticleEmitter`](super::ParticleEmitter).
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmitterShape {
+	/// All particles spawn at the emitter's position.
+	#[default]
+	Point,
+	/// Particles spawn anywhere inside the circle.
+	Circle(Circle),
+	/// Particles spawn anywhere inside the rectangle.
+	Rect(Rect),
+	/// Particles spawn anywhere inside the polygon.
+	Polygon(Polygon),
+}
+
+impl EmitterShape {
+	pub(super) fn sample(&self, rng: &mut Rng) -> Vec2 {
+		match self {
+			EmitterShape::Point => Vec2::ZERO,
+			EmitterShape::Circle(circle) => {
+				let distance = circle.radius * rng.next_f32().sqrt();
+				let angle = rng.next_f32() * std::f32::consts::TAU;
+				circle.center + Vec2::from_angle(angle) * distance
+			}
+			EmitterShape::Rect(rect) => rect.fractional_point(vec2(rng.next_f32(), rng.next_f32())),
+			EmitterShape::Polygon(polygon) => {
+				let Some(bounding_rect) = polygon.bounding_rect() else {
+					return Vec2::ZERO;
+				};
+				let mut point = bounding_rect.center();
+				for _ in 0..MAX_POLYGON_SAMPLE_ATTEMPTS {
+					point = bounding_rect.fractional_point(vec2(rng.next_f32(), rng.next_f32()));
+					if polygon.contains_point(point) {
+						break;
+					}
+				}
+				point
+			}
+		}
+	}
+}
@@ -0,0 +1,304 @@
+#[cfg(test)]
+mod test;
+
+use std::time::Duration;
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat2, Vec2};
+use palette::LinSrgba;
+
+use crate::{
+	Context,
+	graphics::{
+		BlendMode, HasVertexAttributes, InstanceBuffer, VertexAttribute, mesh::Mesh,
+		texture::Texture, vertex_attr_array,
+	},
+	math::Rect,
+};
+
+use super::{ParticleEmitterSettings, rng::Rng};
+
+const MIN_INSTANCE_CAPACITY: usize = 16;
+
+/// Spawns, simulates, and draws particles.
+///
+/// New particles are spawned at the emitter's `position`. Once spawned,
+/// particles move independently of the emitter.
+///
+/// Particles are drawn with instancing using a built-in shader, so any
+/// shader pushed to the graphics stack is ignored when drawing the emitter.
+#[derive(Debug)]
+pub struct ParticleEmitter {
+	/// Describes how particles are spawned and animated.
+	pub settings: ParticleEmitterSettings,
+	/// Where new particles are spawned.
+	pub position: Vec2,
+	/// Whether the emitter is spawning new particles. Existing particles
+	/// keep updating either way.
+	pub emitting: bool,
+	/// The texture to draw each particle with.
+	pub texture: Option<Texture>,
+	/// The blend mode to use when drawing the particles.
+	pub blend_mode: BlendMode,
+	simulation: ParticleSimulation,
+	mesh: Mesh,
+	instance_buffer: InstanceBuffer<ParticleInstance>,
+	/// The number of particles written to the instance buffer by the
+	/// last update.
+	num_uploaded_instances: usize,
+}
+
+impl ParticleEmitter {
+	/// Creates a new [`ParticleEmitter`] at the specified `position`.
+	pub fn new(
+		ctx: &Context,
+		settings: ParticleEmitterSettings,
+		position: impl Into<Vec2>,
+	) -> Self {
+		let _span = tracy_client::span!();
+		let instance_capacity = settings
+			.max_particles
+			.clamp(MIN_INSTANCE_CAPACITY, MIN_INSTANCE_CAPACITY * 64);
+		Self {
+			settings,
+			position: position.into(),
+			emitting: true,
+			texture: None,
+			blend_mode: BlendMode::default(),
+			simulation: ParticleSimulation::new(),
+			mesh: Mesh::rectangle(ctx, Rect::centered_around_zero(Vec2::ONE)),
+			instance_buffer: InstanceBuffer::new(
+				ctx,
+				&vec![ParticleInstance::zeroed(); instance_capacity],
+			),
+			num_uploaded_instances: 0,
+		}
+	}
+
+	/// Sets the texture to draw each particle with.
+	pub fn with_texture(self, texture: &Texture) -> Self {
+		Self {
+			texture: Some(texture.clone()),
+			..self
+		}
+	}
+
+	/// Returns the number of particles that are currently alive.
+	pub fn num_particles(&self) -> usize {
+		self.simulation.particles.len()
+	}
+
+	/// Returns how long the emitter has been running for.
+	pub fn time(&self) -> Duration {
+		self.simulation.time
+	}
+
+	/// Returns `true` if the emitter has a `duration` that has passed and
+	/// all of its particles have died.
+	pub fn finished(&self) -> bool {
+		self.settings
+			.duration
+			.is_some_and(|duration| self.simulation.time >= duration)
+			&& self.simulation.particles.is_empty()
+	}
+
+	/// Starts the emitter over from the beginning, removing all existing
+	/// particles.
+	pub fn restart(&mut self) {
+		self.simulation.restart();
+		self.num_uploaded_instances = 0;
+		self.emitting = true;
+	}
+
+	/// Immediately spawns `count` particles, up to the emitter's
+	/// maximum number of particles.
+	///
+	/// The new particles are drawn after the next [`update`](Self::update).
+	pub fn burst(&mut self, count: usize) {
+		self.simulation.burst(&self.settings, self.position, count);
+	}
+
+	/// Simulates the particles and spawns new ones.
+	pub fn update(&mut self, ctx: &Context, delta_time: Duration) {
+		let _span = tracy_client::span!();
+		self.simulation
+			.update(&self.settings, self.position, self.emitting, delta_time);
+		self.upload_instances(ctx);
+	}
+
+	/// Draws the particles.
+	pub fn draw(&self, ctx: &mut Context) {
+		let _span = tracy_client::span!();
+		// particles spawned since the last update haven't been uploaded
+		// yet, so only the uploaded ones are drawn
+		if self.num_uploaded_instances == 0 {
+			return;
+		}
+		let shader = ctx.graphics.default_resources.particle_shader.clone();
+		let ctx = &mut ctx.push(&shader);
+		self.mesh
+			.texture(self.texture.as_ref())
+			.blend_mode(self.blend_mode)
+			.draw_instanced(
+				ctx,
+				&self
+					.instance_buffer
+					.range(..self.num_uploaded_instances as u32),
+			);
+	}
+
+	fn upload_instances(&mut self, ctx: &Context) {
+		let _span = tracy_client::span!();
+		self.num_uploaded_instances = 0;
+		if self.simulation.particles.is_empty() {
+			return;
+		}
+		let texture_size = self
+			.texture
+			.as_ref()
+			.map_or(Vec2::ONE, |texture| texture.size().as_vec2());
+		let instances = self
+			.simulation
+			.particles
+			.iter()
+			.map(|particle| {
+				let f = particle.lifetime_fraction();
+				let rotation = particle.initial_rotation + self.settings.rotation.get(f);
+				let scale = texture_size * self.settings.size.get(f);
+				ParticleInstance {
+					transform: Mat2::from_scale_angle(scale, rotation).to_cols_array(),
+					translation: particle.position,
+					color: self.settings.color.get(f),
+				}
+			})
+			.collect::<Vec<_>>();
+		if instances.len() > self.instance_buffer.len() as usize {
+			self.instance_buffer = InstanceBuffer::new(
+				ctx,
+				&vec![ParticleInstance::zeroed(); instances.len().next_power_of_two()],
+			);
+		}
+		self.instance_buffer.set_instances(ctx, 0, &instances);
+		self.num_uploaded_instances = instances.len();
+	}
+}
+
+/// The particles of a [`ParticleEmitter`] and the state used to
+/// spawn them.
+#[derive(Debug, Clone)]
+struct ParticleSimulation {
+	particles: Vec<Particle>,
+	time: Duration,
+	emission_accumulator: f32,
+	rng: Rng,
+}
+
+impl ParticleSimulation {
+	fn new() -> Self {
+		Self {
+			particles: vec![],
+			time: Duration::ZERO,
+			emission_accumulator: 0.0,
+			rng: Rng::new(),
+		}
+	}
+
+	fn restart(&mut self) {
+		self.particles.clear();
+		self.time = Duration::ZERO;
+		self.emission_accumulator = 0.0;
+	}
+
+	fn burst(&mut self, settings: &ParticleEmitterSettings, position: Vec2, count: usize) {
+		let count = count.min(settings.max_particles.saturating_sub(self.particles.len()));
+		for _ in 0..count {
+			let particle = self.spawn_particle(settings, position);
+			self.particles.push(particle);
+		}
+	}
+
+	fn update(
+		&mut self,
+		settings: &ParticleEmitterSettings,
+		position: Vec2,
+		emitting: bool,
+		delta_time: Duration,
+	) {
+		let dt = delta_time.as_secs_f32();
+		let damping = 1.0 / (1.0 + settings.damping * dt);
+		for particle in &mut self.particles {
+			particle.age += delta_time;
+			particle.velocity += settings.gravity * dt;
+			particle.velocity *= damping;
+			particle.position += particle.velocity * dt;
+		}
+		self.particles
+			.retain(|particle| particle.age < particle.lifetime);
+
+		let previous_time = self.time;
+		self.time += delta_time;
+		if emitting {
+			let emission_end = settings
+				.duration
+				.map_or(self.time, |duration| duration.min(self.time));
+			if emission_end > previous_time {
+				self.emission_accumulator +=
+					settings.rate * (emission_end - previous_time).as_secs_f32();
+				let count = self.emission_accumulator.floor();
+				self.emission_accumulator -= count;
+				self.burst(settings, position, count as usize);
+			}
+			let burst_count = settings
+				.bursts
+				.iter()
+				.filter(|burst| burst.time >= previous_time && burst.time < self.time)
+				.map(|burst| burst.count)
+				.sum();
+			self.burst(settings, position, burst_count);
+		}
+	}
+
+	fn spawn_particle(&mut self, settings: &ParticleEmitterSettings, position: Vec2) -> Particle {
+		let angle = settings.direction + (self.rng.next_f32() * 2.0 - 1.0) * settings.spread;
+		let speed = self.rng.range_f32(&settings.speed);
+		Particle {
+			position: position + settings.shape.sample(&mut self.rng),
+			velocity: Vec2::from_angle(angle) * speed,
+			age: Duration::ZERO,
+			lifetime: self.rng.range_duration(&settings.lifetime),
+			initial_rotation: self.rng.range_f32(&settings.initial_rotation),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Particle {
+	position: Vec2,
+	velocity: Vec2,
+	age: Duration,
+	lifetime: Duration,
+	initial_rotation: f32,
+}
+
+impl Particle {
+	fn lifetime_fraction(&self) -> f32 {
+		if self.lifetime.is_zero() {
+			return 1.0;
+		}
+		(self.age.as_secs_f32() / self.lifetime.as_secs_f32()).min(1.0)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct ParticleInstance {
+	transform: [f32; 4],
+	translation: Vec2,
+	color: LinSrgba,
+}
+
+impl HasVertexAttributes for ParticleInstance {
+	fn attributes() -> Vec<VertexAttribute> {
+		vertex_attr_array![3 => Float32x4, 4 => Float32x2, 5 => Float32x4].into()
+	}
+}
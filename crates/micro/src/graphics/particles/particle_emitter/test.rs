@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use glam::Vec2;
+
+use crate::graphics::particles::{ParticleBurst, ParticleEmitterSettings};
+
+use super::ParticleSimulation;
+
+fn update(
+	simulation: &mut ParticleSimulation,
+	settings: &ParticleEmitterSettings,
+	delta_time: Duration,
+) {
+	simulation.update(settings, Vec2::ZERO, true, delta_time);
+}
+
+#[test]
+fn rate_accumulates_between_updates() {
+	let settings = ParticleEmitterSettings {
+		rate: 10.0,
+		..Default::default()
+	};
+	let mut simulation = ParticleSimulation::new();
+	// 0.04 seconds at 10 particles per second isn't enough for a particle
+	update(&mut simulation, &settings, Duration::from_millis(40));
+	assert_eq!(simulation.particles.len(), 0);
+	// but the leftover time carries over to the next update
+	update(&mut simulation, &settings, Duration::from_millis(80));
+	assert_eq!(simulation.particles.len(), 1);
+	update(&mut simulation, &settings, Duration::from_millis(300));
+	assert_eq!(simulation.particles.len(), 4);
+}
+
+#[test]
+fn rate_stops_after_duration() {
+	let settings = ParticleEmitterSettings {
+		rate: 10.0,
+		duration: Some(Duration::from_millis(500)),
+		lifetime: Duration::from_secs(10)..=Duration::from_secs(10),
+		..Default::default()
+	};
+	let mut simulation = ParticleSimulation::new();
+	update(&mut simulation, &settings, Duration::from_secs(2));
+	assert_eq!(simulation.particles.len(), 5);
+}
+
+#[test]
+fn bursts_happen_once_at_their_time() {
+	let settings = ParticleEmitterSettings {
+		rate: 0.0,
+		bursts: vec![
+			ParticleBurst {
+				time: Duration::ZERO,
+				count: 3,
+			},
+			ParticleBurst {
+				time: Duration::from_millis(250),
+				count: 5,
+			},
+		],
+		..Default::default()
+	};
+	let mut simulation = ParticleSimulation::new();
+	update(&mut simulation, &settings, Duration::from_millis(100));
+	assert_eq!(simulation.particles.len(), 3);
+	update(&mut simulation, &settings, Duration::from_millis(100));
+	assert_eq!(simulation.particles.len(), 3);
+	update(&mut simulation, &settings, Duration::from_millis(100));
+	assert_eq!(simulation.particles.len(), 8);
+	update(&mut simulation, &settings, Duration::from_millis(100));
+	assert_eq!(simulation.particles.len(), 8);
+}
+
+#[test]
+fn particles_die_after_their_lifetime() {
+	let settings = ParticleEmitterSettings {
+		rate: 0.0,
+		lifetime: Duration::from_millis(500)..=Duration::from_millis(500),
+		..Default::default()
+	};
+	let mut simulation = ParticleSimulation::new();
+	simulation.burst(&settings, Vec2::ZERO, 4);
+	update(&mut simulation, &settings, Duration::from_millis(400));
+	assert_eq!(simulation.particles.len(), 4);
+	update(&mut simulation, &settings, Duration::from_millis(100));
+	assert_eq!(simulation.particles.len(), 0);
+}
+
+#[test]
+fn max_particles() {
+	let settings = ParticleEmitterSettings {
+		rate: 100.0,
+		max_particles: 10,
+		lifetime: Duration::from_secs(10)..=Duration::from_secs(10),
+		..Default::default()
+	};
+	let mut simulation = ParticleSimulation::new();
+	simulation.burst(&settings, Vec2::ZERO, 8);
+	assert_eq!(simulation.particles.len(), 8);
+	simulation.burst(&settings, Vec2::ZERO, 8);
+	assert_eq!(simulation.particles.len(), 10);
+	update(&mut simulation, &settings, Duration::from_secs(1));
+	assert_eq!(simulation.particles.len(), 10);
+}
+
+#[test]
+fn restart() {
+	let settings = ParticleEmitterSettings::default();
+	let mut simulation = ParticleSimulation::new();
+	update(&mut simulation, &settings, Duration::from_millis(550));
+	assert_eq!(simulation.particles.len(), 5);
+	simulation.restart();
+	assert_eq!(simulation.particles.len(), 0);
+	assert_eq!(simulation.time, Duration::ZERO);
+	update(&mut simulation, &settings, Duration::from_millis(50));
+	assert_eq!(simulation.particles.len(), 0);
+}
@@ -0,0 +1,91 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use glam::Vec2;
+use palette::LinSrgba;
+
+use crate::{color::ColorConstants, tween::TweenSequence};
+
+use super::EmitterShape;
+
+/// Describes how a [`ParticleEmitter`](super::ParticleEmitter) spawns
+/// and animates particles.
+///
+/// The tweens for color, size, and rotation are sampled by the fraction of
+/// a particle's lifetime that has passed, so their keyframe times should
+/// go from `0.0` to `1.0`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct ParticleEmitterSettings {
+	/// The area new particles are spawned in.
+	pub shape: EmitterShape,
+	/// How many particles to spawn per second.
+	pub rate: f32,
+	/// Groups of particles to spawn all at once at specific times.
+	pub bursts: Vec<ParticleBurst>,
+	/// How long the emitter spawns particles for, or `None` to keep
+	/// spawning particles forever.
+	pub duration: Option<Duration>,
+	/// The maximum number of particles that can be alive at once.
+	pub max_particles: usize,
+	/// How long each particle lives for. Each particle picks a random
+	/// lifetime in this range.
+	pub lifetime: RangeInclusive<Duration>,
+	/// The initial speed of each particle in pixels per second.
+	pub speed: RangeInclusive<f32>,
+	/// The angle (in radians) particles move in when they spawn.
+	pub direction: f32,
+	/// How far (in radians) to either side of `direction` particles
+	/// can move in.
+	pub spread: f32,
+	/// The acceleration applied to every particle in pixels per second
+	/// squared.
+	pub gravity: Vec2,
+	/// How quickly particles slow down. `0.0` means particles never
+	/// slow down on their own.
+	pub damping: f32,
+	/// The rotation (in radians) of each particle when it spawns.
+	pub initial_rotation: RangeInclusive<f32>,
+	/// The color of particles over their lifetime.
+	pub color: TweenSequence<LinSrgba, f32>,
+	/// The scale of particles over their lifetime.
+	///
+	/// Particles are the size of the emitter's texture at a scale of
+	/// `1.0`, or one pixel if the emitter has no texture.
+	pub size: TweenSequence<f32, f32>,
+	/// The rotation (in radians) added to the initial rotation of particles
+	/// over their lifetime.
+	pub rotation: TweenSequence<f32, f32>,
+}
+
+impl Default for ParticleEmitterSettings {
+	fn default() -> Self {
+		Self {
+			shape: EmitterShape::Point,
+			rate: 10.0,
+			bursts: vec![],
+			duration: None,
+			max_particles: 1000,
+			lifetime: Duration::from_secs(1)..=Duration::from_secs(1),
+			speed: 50.0..=100.0,
+			direction: -std::f32::consts::FRAC_PI_2,
+			spread: std::f32::consts::PI,
+			gravity: Vec2::ZERO,
+			damping: 0.0,
+			initial_rotation: 0.0..=0.0,
+			color: TweenSequence::new(LinSrgba::WHITE),
+			size: TweenSequence::new(1.0),
+			rotation: TweenSequence::new(0.0),
+		}
+	}
+}
+
+/// A group of particles spawned all at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleBurst {
+	/// The time after the emitter starts when the particles are spawned.
+	pub time: Duration,
+	/// The number of particles to spawn.
+	pub count: usize,
+}
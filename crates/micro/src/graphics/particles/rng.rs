@@ -0,0 +1,36 @@
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+	ops::RangeInclusive,
+	time::Duration,
+};
+
+/// A small xorshift random number generator so particles don't
+/// depend on the optional `rand` feature.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Rng(u64);
+
+impl Rng {
+	pub(super) fn new() -> Self {
+		let seed = RandomState::new().build_hasher().finish();
+		Self(seed.max(1))
+	}
+
+	/// Returns a random number from `0.0` to `1.0`.
+	pub(super) fn next_f32(&mut self) -> f32 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		(self.0 >> 40) as f32 / (1u64 << 24) as f32
+	}
+
+	pub(super) fn range_f32(&mut self, range: &RangeInclusive<f32>) -> f32 {
+		range.start() + (range.end() - range.start()) * self.next_f32()
+	}
+
+	pub(super) fn range_duration(&mut self, range: &RangeInclusive<Duration>) -> Duration {
+		let start = range.start().as_secs_f32();
+		let end = range.end().as_secs_f32();
+		Duration::from_secs_f32((start + (end - start) * self.next_f32()).max(0.0))
+	}
+}
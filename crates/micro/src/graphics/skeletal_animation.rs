@@ -0,0 +1,168 @@
+//! Types for deforming 3D meshes with animated skeletons.
+
+mod animation_clip;
+mod animation_player;
+mod skin;
+mod skinned_vertex;
+
+pub use animation_clip::*;
+pub use animation_player::*;
+pub use skin::*;
+pub use skinned_vertex::*;
+
+use glam::{Mat4, Quat, Vec3};
+
+use crate::math::Lerp;
+
+/// A hierarchy of joints that can be used to deform a
+/// [`Mesh`](crate::graphics::mesh::Mesh).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skeleton {
+	joints: Vec<Joint>,
+}
+
+impl Skeleton {
+	/// Creates a new [`Skeleton`] from a list of joints.
+	///
+	/// Each joint's parent must come before the joint itself in the list.
+	pub fn new(joints: impl Into<Vec<Joint>>) -> Self {
+		let joints = joints.into();
+		for (index, joint) in joints.iter().enumerate() {
+			if let Some(parent) = joint.parent {
+				assert!(
+					parent < index,
+					"joint '{}' must come after its parent",
+					joint.name
+				);
+			}
+		}
+		Self { joints }
+	}
+
+	/// Returns the joints in the skeleton.
+	pub fn joints(&self) -> &[Joint] {
+		&self.joints
+	}
+
+	/// Returns the number of joints in the skeleton.
+	pub fn num_joints(&self) -> usize {
+		self.joints.len()
+	}
+
+	/// Returns the index of the joint with the given name, if it exists.
+	pub fn joint_index(&self, name: &str) -> Option<usize> {
+		self.joints.iter().position(|joint| joint.name == name)
+	}
+
+	/// Returns the pose where every joint is at its rest transform.
+	pub fn rest_pose(&self) -> Pose {
+		Pose {
+			local_transforms: self
+				.joints
+				.iter()
+				.map(|joint| joint.rest_transform)
+				.collect(),
+		}
+	}
+}
+
+/// A single bone in a [`Skeleton`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Joint {
+	/// The name of the joint.
+	pub name: String,
+	/// The index of the parent joint, or `None` if this is a root joint.
+	pub parent: Option<usize>,
+	/// Transforms vertices from model space into the joint's local space
+	/// when the mesh is in its bind pose.
+	pub inverse_bind_matrix: Mat4,
+	/// The transform of the joint relative to its parent when no animation
+	/// is applied.
+	pub rest_transform: JointTransform,
+}
+
+/// The translation, rotation, and scale of a joint relative to its parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JointTransform {
+	/// The position of the joint.
+	pub translation: Vec3,
+	/// The orientation of the joint.
+	pub rotation: Quat,
+	/// The scale of the joint.
+	pub scale: Vec3,
+}
+
+impl JointTransform {
+	/// A transform that leaves the joint unchanged.
+	pub const IDENTITY: Self = Self {
+		translation: Vec3::ZERO,
+		rotation: Quat::IDENTITY,
+		scale: Vec3::ONE,
+	};
+
+	/// Returns the transform as a matrix.
+	pub fn to_mat4(self) -> Mat4 {
+		Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+	}
+}
+
+impl Default for JointTransform {
+	fn default() -> Self {
+		Self::IDENTITY
+	}
+}
+
+impl Lerp for JointTransform {
+	fn lerp(self, other: Self, f: f32) -> Self {
+		Self {
+			translation: self.translation.lerp(other.translation, f),
+			rotation: self.rotation.slerp(other.rotation, f),
+			scale: self.scale.lerp(other.scale, f),
+		}
+	}
+}
+
+/// The local transforms of every joint in a [`Skeleton`] at a moment in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pose {
+	/// The transform of each joint relative to its parent, in the same
+	/// order as the joints in the skeleton.
+	pub local_transforms: Vec<JointTransform>,
+}
+
+impl Pose {
+	/// Returns a pose `f` of the way from this pose to the `other` pose.
+	pub fn blend(&self, other: &Pose, f: f32) -> Self {
+		Self {
+			local_transforms: self
+				.local_transforms
+				.iter()
+				.zip(&other.local_transforms)
+				.map(|(a, b)| a.lerp(*b, f))
+				.collect(),
+		}
+	}
+
+	/// Returns the transform of each joint relative to the root of the model.
+	pub fn global_transforms(&self, skeleton: &Skeleton) -> Vec<Mat4> {
+		let mut global_transforms: Vec<Mat4> = Vec::with_capacity(skeleton.num_joints());
+		for (joint, local_transform) in skeleton.joints.iter().zip(&self.local_transforms) {
+			let local_transform = local_transform.to_mat4();
+			global_transforms.push(match joint.parent {
+				Some(parent) => global_transforms[parent] * local_transform,
+				None => local_transform,
+			});
+		}
+		global_transforms
+	}
+
+	/// Returns the matrices that move each vertex from its bind pose to
+	/// this pose. These are the values uploaded to the GPU by a [`Skin`].
+	pub fn joint_matrices(&self, skeleton: &Skeleton) -> Vec<Mat4> {
+		self.global_transforms(skeleton)
+			.iter()
+			.zip(&skeleton.joints)
+			.map(|(global_transform, joint)| *global_transform * joint.inverse_bind_matrix)
+			.collect()
+	}
+}
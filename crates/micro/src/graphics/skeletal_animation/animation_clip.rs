@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use glam::{Quat, Vec3};
+
+use crate::math::InverseLerp;
+
+use super::{Pose, Skeleton};
+
+/// A named animation that moves the joints of a [`Skeleton`] over time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+	/// The name of the animation.
+	pub name: String,
+	/// The keyframes for each animated joint property.
+	pub channels: Vec<AnimationChannel>,
+	/// Whether the animation starts over when it reaches the end.
+	pub looping: bool,
+}
+
+impl AnimationClip {
+	/// Creates a new [`AnimationClip`] with no channels.
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			channels: vec![],
+			looping: false,
+		}
+	}
+
+	/// Adds a channel to the animation.
+	pub fn with_channel(mut self, channel: AnimationChannel) -> Self {
+		self.channels.push(channel);
+		self
+	}
+
+	/// Makes the [`AnimationClip`] a looping animation.
+	pub fn looping(self) -> Self {
+		Self {
+			looping: true,
+			..self
+		}
+	}
+
+	/// Returns the time of the last keyframe in any channel.
+	pub fn duration(&self) -> Duration {
+		self.channels
+			.iter()
+			.filter_map(AnimationChannel::last_keyframe_time)
+			.max()
+			.unwrap_or_default()
+	}
+
+	/// Overwrites the joint properties in `pose` that this animation
+	/// affects with their values at the given `time`.
+	pub fn sample(&self, time: Duration, pose: &mut Pose) {
+		let time = self.wrap_time(time);
+		for channel in &self.channels {
+			let Some(joint_transform) = pose.local_transforms.get_mut(channel.joint_index) else {
+				continue;
+			};
+			match &channel.values {
+				AnimationChannelValues::Translation(keyframes) => {
+					if let Some(translation) =
+						sample_keyframes(keyframes, time, channel.interpolation, Vec3::lerp)
+					{
+						joint_transform.translation = translation;
+					}
+				}
+				AnimationChannelValues::Rotation(keyframes) => {
+					if let Some(rotation) =
+						sample_keyframes(keyframes, time, channel.interpolation, Quat::slerp)
+					{
+						joint_transform.rotation = rotation;
+					}
+				}
+				AnimationChannelValues::Scale(keyframes) => {
+					if let Some(scale) =
+						sample_keyframes(keyframes, time, channel.interpolation, Vec3::lerp)
+					{
+						joint_transform.scale = scale;
+					}
+				}
+			}
+		}
+	}
+
+	/// Returns the pose of the `skeleton` at the given `time`. Joints that
+	/// aren't affected by this animation are left at their rest transform.
+	pub fn pose(&self, skeleton: &Skeleton, time: Duration) -> Pose {
+		let mut pose = skeleton.rest_pose();
+		self.sample(time, &mut pose);
+		pose
+	}
+
+	fn wrap_time(&self, time: Duration) -> Duration {
+		let duration = self.duration();
+		if !self.looping || duration.is_zero() {
+			return time;
+		}
+		Duration::from_secs_f64(time.as_secs_f64() % duration.as_secs_f64())
+	}
+}
+
+/// The keyframes for a single property of a single joint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationChannel {
+	/// The index of the joint in the [`Skeleton`] this channel affects.
+	pub joint_index: usize,
+	/// How values are calculated between keyframes.
+	pub interpolation: AnimationInterpolation,
+	/// The property being animated and its keyframes.
+	pub values: AnimationChannelValues,
+}
+
+impl AnimationChannel {
+	fn last_keyframe_time(&self) -> Option<Duration> {
+		match &self.values {
+			AnimationChannelValues::Translation(keyframes)
+			| AnimationChannelValues::Scale(keyframes) => keyframes.last().map(|k| k.time),
+			AnimationChannelValues::Rotation(keyframes) => keyframes.last().map(|k| k.time),
+		}
+	}
+}
+
+/// The property of a joint an [`AnimationChannel`] affects, along with
+/// the keyframes for that property.
+///
+/// Keyframes must be sorted by time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationChannelValues {
+	/// Keyframes for the translation of the joint.
+	Translation(Vec<AnimationKeyframe<Vec3>>),
+	/// Keyframes for the rotation of the joint.
+	Rotation(Vec<AnimationKeyframe<Quat>>),
+	/// Keyframes for the scale of the joint.
+	Scale(Vec<AnimationKeyframe<Vec3>>),
+}
+
+/// A value of a joint property at a specific time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationKeyframe<T> {
+	/// The time the keyframe occurs at.
+	pub time: Duration,
+	/// The value of the property at this time.
+	pub value: T,
+}
+
+/// How the values of an [`AnimationChannel`] are calculated between
+/// keyframes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnimationInterpolation {
+	/// The value stays at the previous keyframe's value until the next
+	/// keyframe is reached.
+	Step,
+	/// The value moves smoothly from one keyframe to the next.
+	#[default]
+	Linear,
+}
+
+fn sample_keyframes<T: Copy>(
+	keyframes: &[AnimationKeyframe<T>],
+	time: Duration,
+	interpolation: AnimationInterpolation,
+	lerp: impl Fn(T, T, f32) -> T,
+) -> Option<T> {
+	let first_keyframe = keyframes.first()?;
+	let next_keyframe_index = keyframes.partition_point(|keyframe| keyframe.time <= time);
+	if next_keyframe_index == 0 {
+		return Some(first_keyframe.value);
+	}
+	let current_keyframe = keyframes[next_keyframe_index - 1];
+	let Some(next_keyframe) = keyframes.get(next_keyframe_index) else {
+		return Some(current_keyframe.value);
+	};
+	match interpolation {
+		AnimationInterpolation::Step => Some(current_keyframe.value),
+		AnimationInterpolation::Linear => {
+			let f = time.inverse_lerp(current_keyframe.time, next_keyframe.time);
+			Some(lerp(current_keyframe.value, next_keyframe.value, f))
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use std::time::Duration;
+
+	use glam::{Mat4, Vec3, vec3};
+
+	use super::{AnimationChannel, AnimationChannelValues, AnimationClip, AnimationKeyframe};
+	use crate::graphics::skeletal_animation::{Joint, JointTransform, Skeleton};
+
+	fn skeleton() -> Skeleton {
+		Skeleton::new([
+			Joint {
+				name: "root".into(),
+				parent: None,
+				inverse_bind_matrix: Mat4::IDENTITY,
+				rest_transform: JointTransform::IDENTITY,
+			},
+			Joint {
+				name: "child".into(),
+				parent: Some(0),
+				inverse_bind_matrix: Mat4::from_translation(vec3(0.0, -1.0, 0.0)),
+				rest_transform: JointTransform {
+					translation: vec3(0.0, 1.0, 0.0),
+					..JointTransform::IDENTITY
+				},
+			},
+		])
+	}
+
+	fn clip() -> AnimationClip {
+		AnimationClip::new("move").with_channel(AnimationChannel {
+			joint_index: 0,
+			interpolation: Default::default(),
+			values: AnimationChannelValues::Translation(vec![
+				AnimationKeyframe {
+					time: Duration::ZERO,
+					value: Vec3::ZERO,
+				},
+				AnimationKeyframe {
+					time: Duration::from_secs(2),
+					value: vec3(2.0, 0.0, 0.0),
+				},
+			]),
+		})
+	}
+
+	#[test]
+	fn samples_between_keyframes() {
+		let pose = clip().pose(&skeleton(), Duration::from_secs(1));
+		assert_eq!(pose.local_transforms[0].translation, vec3(1.0, 0.0, 0.0));
+		assert_eq!(pose.local_transforms[1].translation, vec3(0.0, 1.0, 0.0));
+	}
+
+	#[test]
+	fn holds_last_keyframe() {
+		let pose = clip().pose(&skeleton(), Duration::from_secs(5));
+		assert_eq!(pose.local_transforms[0].translation, vec3(2.0, 0.0, 0.0));
+	}
+
+	#[test]
+	fn wraps_looping_clips() {
+		let pose = clip().looping().pose(&skeleton(), Duration::from_secs(3));
+		assert_eq!(pose.local_transforms[0].translation, vec3(1.0, 0.0, 0.0));
+	}
+
+	#[test]
+	fn child_joints_follow_parents() {
+		let skeleton = skeleton();
+		let pose = clip().pose(&skeleton, Duration::from_secs(2));
+		let joint_matrices = pose.joint_matrices(&skeleton);
+		assert_eq!(
+			joint_matrices[1].transform_point3(vec3(0.0, 1.0, 0.0)),
+			vec3(2.0, 1.0, 0.0)
+		);
+	}
+}
@@ -0,0 +1,157 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::{AnimationClip, Pose, Skeleton};
+
+/// Plays [`AnimationClip`]s and smoothly transitions between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationPlayer {
+	clips: HashMap<String, AnimationClip>,
+	current: PlayingClip,
+	fading_out: Option<FadingClip>,
+	/// How fast the animations play. `1.0` is normal speed.
+	pub speed: f32,
+	/// Whether the animations are currently paused.
+	pub paused: bool,
+}
+
+impl AnimationPlayer {
+	/// Creates a new [`AnimationPlayer`] with a set of clips, starting
+	/// with the clip with the specified name.
+	///
+	/// Panics if there is no clip called `initial_clip_name`.
+	pub fn new(
+		clips: impl IntoIterator<Item = AnimationClip>,
+		initial_clip_name: impl Into<String>,
+	) -> Self {
+		let clips = clips
+			.into_iter()
+			.map(|clip| (clip.name.clone(), clip))
+			.collect::<HashMap<_, _>>();
+		let initial_clip_name = initial_clip_name.into();
+		assert!(
+			clips.contains_key(&initial_clip_name),
+			"no animation clip called '{}'",
+			initial_clip_name
+		);
+		Self {
+			clips,
+			current: PlayingClip::new(initial_clip_name),
+			fading_out: None,
+			speed: 1.0,
+			paused: false,
+		}
+	}
+
+	/// Returns the clip with the specified name, if it exists.
+	pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+		self.clips.get(name)
+	}
+
+	/// Returns the name of the clip that's currently playing.
+	pub fn current_clip_name(&self) -> &str {
+		&self.current.name
+	}
+
+	/// Returns how far into the current clip the animation is.
+	pub fn current_time(&self) -> Duration {
+		self.current.time
+	}
+
+	/// Returns `true` if the current clip is not looping and has reached
+	/// its last keyframe.
+	pub fn finished(&self) -> bool {
+		let clip = &self.clips[&self.current.name];
+		!clip.looping && self.current.time >= clip.duration()
+	}
+
+	/// Returns `true` if the player is transitioning from one clip to another.
+	pub fn crossfading(&self) -> bool {
+		self.fading_out.is_some()
+	}
+
+	/// Immediately switches to the clip with the specified name and plays it
+	/// from the beginning.
+	///
+	/// Panics if there is no clip called `clip_name`.
+	pub fn play(&mut self, clip_name: impl Into<String>) {
+		let clip_name = clip_name.into();
+		assert!(
+			self.clips.contains_key(&clip_name),
+			"no animation clip called '{}'",
+			clip_name
+		);
+		self.current = PlayingClip::new(clip_name);
+		self.fading_out = None;
+	}
+
+	/// Starts playing the clip with the specified name, blending from
+	/// the current pose to the new clip over the given `duration`.
+	///
+	/// Panics if there is no clip called `clip_name`.
+	pub fn crossfade(&mut self, clip_name: impl Into<String>, duration: Duration) {
+		let clip_name = clip_name.into();
+		assert!(
+			self.clips.contains_key(&clip_name),
+			"no animation clip called '{}'",
+			clip_name
+		);
+		let previous = std::mem::replace(&mut self.current, PlayingClip::new(clip_name));
+		self.fading_out = (!duration.is_zero()).then_some(FadingClip {
+			clip: previous,
+			elapsed: Duration::ZERO,
+			duration,
+		});
+	}
+
+	/// Progresses the animations by the given amount of time.
+	pub fn update(&mut self, delta_time: Duration) {
+		if self.paused {
+			return;
+		}
+		let delta_time = delta_time.mul_f32(self.speed.max(0.0));
+		self.current.time += delta_time;
+		if let Some(fading_out) = &mut self.fading_out {
+			fading_out.clip.time += delta_time;
+			fading_out.elapsed += delta_time;
+			if fading_out.elapsed >= fading_out.duration {
+				self.fading_out = None;
+			}
+		}
+	}
+
+	/// Returns the current pose of the `skeleton`, including any blending
+	/// from a crossfade.
+	pub fn pose(&self, skeleton: &Skeleton) -> Pose {
+		let current_pose = self.clips[&self.current.name].pose(skeleton, self.current.time);
+		let Some(fading_out) = &self.fading_out else {
+			return current_pose;
+		};
+		let previous_pose = self.clips[&fading_out.clip.name].pose(skeleton, fading_out.clip.time);
+		previous_pose.blend(
+			&current_pose,
+			fading_out.elapsed.as_secs_f32() / fading_out.duration.as_secs_f32(),
+		)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlayingClip {
+	name: String,
+	time: Duration,
+}
+
+impl PlayingClip {
+	fn new(name: String) -> Self {
+		Self {
+			name,
+			time: Duration::ZERO,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FadingClip {
+	clip: PlayingClip,
+	elapsed: Duration,
+	duration: Duration,
+}
@@ -0,0 +1,83 @@
+use crate::{
+	Context, Push,
+	context::OnDrop,
+	graphics::{Shader, StorageBuffer},
+};
+
+use super::{Pose, Skeleton};
+
+/// The joint matrices for a [`Skeleton`] uploaded to the GPU.
+///
+/// Meshes made of [`SkinnedVertex3d`](super::SkinnedVertex3d)s can be drawn
+/// with the skin's pose by pushing the skin with [`Skin::push`] and then
+/// drawing the mesh as usual.
+///
+/// Custom shaders can read the joint matrices by adding
+/// [`Skin::joint_matrices`] as the first storage buffer. It's available at
+/// `layout (set = 2, binding = 0) readonly buffer { mat4 jointMatrices[]; }`.
+///
+/// This can be cheaply cloned. Clones will point to the same joint matrices
+/// on the GPU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skin {
+	joint_matrices: StorageBuffer,
+	num_joints: usize,
+}
+
+impl Skin {
+	/// Creates a new [`Skin`] for the `skeleton` in its rest pose.
+	pub fn new(ctx: &Context, skeleton: &Skeleton) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			joint_matrices: StorageBuffer::new(
+				ctx,
+				"Skin Joint Matrices",
+				&skeleton.rest_pose().joint_matrices(skeleton),
+			),
+			num_joints: skeleton.num_joints(),
+		}
+	}
+
+	/// Returns the number of joints the skin was created for.
+	pub fn num_joints(&self) -> usize {
+		self.num_joints
+	}
+
+	/// Returns the storage buffer holding the joint matrices.
+	pub fn joint_matrices(&self) -> &StorageBuffer {
+		&self.joint_matrices
+	}
+
+	/// Uploads the joint matrices for the `pose` to the GPU.
+	///
+	/// This will modify all clones of this [`Skin`] as well.
+	pub fn set_pose(&self, ctx: &Context, skeleton: &Skeleton, pose: &Pose) {
+		let _span = tracy_client::span!();
+		assert_eq!(
+			skeleton.num_joints(),
+			self.num_joints,
+			"skeleton has a different number of joints than the skin"
+		);
+		self.joint_matrices
+			.write(ctx, 0, &pose.joint_matrices(skeleton));
+	}
+
+	/// Returns the built-in skinning shader set up to use this skin's
+	/// joint matrices.
+	pub fn shader(&self, ctx: &Context) -> Shader {
+		ctx.graphics
+			.default_resources
+			.default_skinned_shader
+			.with_storage_buffers(vec![self.joint_matrices.clone()])
+	}
+
+	/// Pushes the built-in skinning shader for this skin to the graphics
+	/// stack.
+	pub fn push<'a>(&self, ctx: &'a mut Context) -> OnDrop<'a> {
+		let shader = self.shader(ctx);
+		ctx.push(Push {
+			shader: Some(shader),
+			..Default::default()
+		})
+	}
+}
@@ -0,0 +1,42 @@
+use bytemuck::{Pod, Zeroable};
+use glam::{UVec4, Vec2, Vec3, Vec4};
+use palette::LinSrgba;
+
+use crate::graphics::{HasVertexAttributes, Vertex, VertexAttribute, vertex_attr_array};
+
+/// A vertex type for 3D meshes that are deformed by a
+/// [`Skeleton`](super::Skeleton).
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct SkinnedVertex3d {
+	/// The coordinates of the vertex in the bind pose.
+	pub position: Vec3,
+	/// The surface normal at this vertex in the bind pose.
+	pub normal: Vec3,
+	/// The texture coordinates at this vertex.
+	pub texture_coords: Vec2,
+	/// The blend color of the vertex.
+	pub color: LinSrgba,
+	/// The indices of up to 4 joints that affect this vertex.
+	pub joints: UVec4,
+	/// How much each of the joints in `joints` affects this vertex.
+	///
+	/// The weights should add up to `1.0`.
+	pub weights: Vec4,
+}
+
+impl Vertex for SkinnedVertex3d {}
+
+impl HasVertexAttributes for SkinnedVertex3d {
+	fn attributes() -> Vec<VertexAttribute> {
+		vertex_attr_array![
+			0 => Float32x3,
+			1 => Float32x3,
+			2 => Float32x2,
+			3 => Float32x4,
+			4 => Uint32x4,
+			5 => Float32x4,
+		]
+		.into()
+	}
+}
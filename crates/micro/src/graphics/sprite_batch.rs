@@ -1,14 +1,18 @@
 //! Types for grouping multiple drawing operations involving the same texture.
 
+mod multi_texture_sprite_batch;
 mod sprite_params;
+#[cfg(test)]
+mod test;
 
+pub use multi_texture_sprite_batch::*;
 pub use sprite_params::SpriteParams;
 
 use std::sync::{Arc, Mutex};
 
 use derive_more::derive::{Display, Error};
 use generational_arena::{Arena, Index};
-use glam::{Mat4, Vec2};
+use glam::{Mat4, UVec2, Vec2};
 use palette::LinSrgba;
 
 use crate::{
@@ -25,6 +29,10 @@ use super::{IntoIndexRange, Vertex2d};
 /// for better performance.
 ///
 /// Can be modified as needed or reused as-is if the sprites don't change.
+///
+/// By default, sprites are drawn in the order of the slots they occupy.
+/// Use [`SpriteBatch::set_sort_mode`] to sort the sprites by depth or
+/// Y position instead.
 #[derive(Debug, Clone)]
 pub struct SpriteBatch {
 	inner: Arc<Mutex<SpriteBatchInner>>,
@@ -36,8 +44,13 @@ pub struct SpriteBatch {
 	pub color: LinSrgba,
 	/// The blend mode to use when drawing this sprite batch.
 	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this sprite batch.
+	pub palette: Option<Texture>,
 	/// The min and max sprite index to draw.
 	///
+	/// If the sprite batch is sorted, the indices refer to positions
+	/// in the sorted order.
+	///
 	/// Setting this results in only some of the sprites being drawn.
 	/// When `None`, all the sprites are drawn.
 	pub range: Option<(u32, u32)>,
@@ -48,14 +61,7 @@ impl SpriteBatch {
 	/// a maximum of `capacity` sprites.
 	pub fn new(ctx: &Context, texture: &Texture, capacity: usize) -> Self {
 		let _span = tracy_client::span!();
-		let vertices = vec![
-			Vertex2d {
-				position: Vec2::ZERO,
-				texture_coords: Vec2::ZERO,
-				color: LinSrgba::WHITE,
-			};
-			capacity * 4
-		];
+		let vertices = vec![EMPTY_VERTEX; capacity * 4];
 		let mut indices: Vec<u32> = vec![];
 		for i in 0..capacity {
 			let start_index = i * 4;
@@ -69,14 +75,13 @@ impl SpriteBatch {
 			]);
 		}
 		Self {
-			inner: Arc::new(Mutex::new(SpriteBatchInner {
-				sprites: Arena::with_capacity(capacity),
-			})),
+			inner: Arc::new(Mutex::new(SpriteBatchInner::new(capacity))),
 			texture: texture.clone(),
 			mesh: Mesh::new(ctx, &vertices, &indices),
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::default(),
+			palette: None,
 			range: None,
 		}
 	}
@@ -85,6 +90,9 @@ impl SpriteBatch {
 
 	/// Sets the range of sprites to draw.
 	///
+	/// If the sprite batch is sorted, the indices refer to positions
+	/// in the sorted order.
+	///
 	/// Setting this results in only some of the sprites being drawn.
 	/// When `None`, all the sprites are drawn.
 	pub fn range(&self, range: impl IntoIndexRange) -> Self {
@@ -108,6 +116,31 @@ impl SpriteBatch {
 		self.len() == 0
 	}
 
+	/// Returns how the sprites in this [`SpriteBatch`] are ordered.
+	pub fn sort_mode(&self) -> SpriteSortMode {
+		self.inner
+			.try_lock()
+			.expect("sprite batch mutex locked")
+			.sort_mode
+	}
+
+	/// Sets how the sprites in this [`SpriteBatch`] are ordered.
+	///
+	/// Sorting happens on the CPU the next time the sprite batch is drawn
+	/// after sprites have been added, removed, or changed.
+	pub fn set_sort_mode(&mut self, ctx: &Context, sort_mode: SpriteSortMode) {
+		let _span = tracy_client::span!();
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		if inner.sort_mode == sort_mode {
+			return;
+		}
+		inner.set_sort_mode(sort_mode);
+		if sort_mode == SpriteSortMode::None {
+			let vertices = inner.slot_order_vertices(self.texture.size());
+			self.mesh.set_vertices(ctx, 0, &vertices);
+		}
+	}
+
 	/// Adds a sprite to the [`SpriteBatch`] containing the entire texture.
 	///
 	/// Returns a [`SpriteId`] which can be used to remove the sprite later.
@@ -131,71 +164,109 @@ impl SpriteBatch {
 		params: impl Into<SpriteParams>,
 	) -> Result<SpriteId, SpriteLimitReached> {
 		let _span = tracy_client::span!();
-		let id = self
-			.inner
-			.try_lock()
-			.expect("sprite batch mutex locked")
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		let sprite = Sprite {
+			texture_region,
+			params: params.into(),
+		};
+		let index = inner
 			.sprites
-			.try_insert(())
-			.map(SpriteId)
+			.try_insert(sprite)
 			.map_err(|_| SpriteLimitReached)?;
-		let params: SpriteParams = params.into();
-		let (sprite_index, _) = id.0.into_raw_parts();
-		let start_vertex_index = sprite_index * 4;
-		let untransformed_display_rect = Rect::new(Vec2::ZERO, texture_region.size);
-		let relative_texture_region = self.texture.relative_rect(texture_region);
-		let transform = params.transform;
-		let corners = untransformed_display_rect.corners();
-		let vertices = corners
-			.iter()
-			.copied()
-			.zip(relative_texture_region.corners())
-			.map(|(position, texture_coords)| Vertex2d {
-				position: transform.transform_point2(position),
-				texture_coords,
-				color: params.color,
-			})
-			.collect::<Vec<_>>();
-		self.mesh.set_vertices(ctx, start_vertex_index, &vertices);
-		Ok(id)
+		if inner.sort_mode == SpriteSortMode::None {
+			let (sprite_index, _) = index.into_raw_parts();
+			self.mesh
+				.set_vertices(ctx, sprite_index * 4, &sprite.vertices(self.texture.size()));
+		} else {
+			inner.sorted_ids.push(index);
+			inner.needs_sort = true;
+		}
+		Ok(SpriteId(index))
+	}
+
+	/// Changes the settings of an existing sprite.
+	///
+	/// The sprite keeps its [`SpriteId`], but may move to a different
+	/// position in the draw order if the sprite batch is sorted.
+	pub fn set_params(
+		&self,
+		ctx: &Context,
+		id: SpriteId,
+		params: impl Into<SpriteParams>,
+	) -> Result<(), InvalidSpriteId> {
+		let _span = tracy_client::span!();
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		let sort_mode = inner.sort_mode;
+		let sprite = inner.sprites.get_mut(id.0).ok_or(InvalidSpriteId)?;
+		sprite.params = params.into();
+		let sprite = *sprite;
+		if sort_mode == SpriteSortMode::None {
+			let (sprite_index, _) = id.0.into_raw_parts();
+			self.mesh
+				.set_vertices(ctx, sprite_index * 4, &sprite.vertices(self.texture.size()));
+		} else {
+			inner.needs_sort = true;
+		}
+		Ok(())
 	}
 
 	/// Removes the sprite with the given `id` from the [`SpriteBatch`].
 	pub fn remove(&mut self, ctx: &Context, id: SpriteId) -> Result<(), InvalidSpriteId> {
 		let _span = tracy_client::span!();
-		if self
-			.inner
-			.try_lock()
-			.expect("sprite batch mutex locked")
-			.sprites
-			.remove(id.0)
-			.is_none()
-		{
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		if inner.sprites.remove(id.0).is_none() {
 			return Err(InvalidSpriteId);
 		}
-		let (sprite_index, _) = id.0.into_raw_parts();
-		let start_vertex_index = sprite_index * 4;
-		let vertices = [Vertex2d {
-			position: Vec2::ZERO,
-			texture_coords: Vec2::ZERO,
-			color: LinSrgba::WHITE,
-		}; 4];
-		self.mesh.set_vertices(ctx, start_vertex_index, &vertices);
+		if inner.sort_mode == SpriteSortMode::None {
+			let (sprite_index, _) = id.0.into_raw_parts();
+			self.mesh
+				.set_vertices(ctx, sprite_index * 4, &[EMPTY_VERTEX; 4]);
+		} else {
+			inner.sorted_ids.retain(|&index| index != id.0);
+			inner.needs_sort = true;
+		}
 		Ok(())
 	}
 
 	/// Draws the [`SpriteBatch`].
 	pub fn draw(&self, ctx: &mut Context) {
+		let _span = tracy_client::span!();
+		{
+			let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+			if inner.needs_sort {
+				let vertices = inner.sorted_vertices(self.texture.size());
+				self.mesh.set_vertices(ctx, 0, &vertices);
+			}
+		}
 		self.mesh
 			.texture(&self.texture)
 			.transformed(self.transform)
 			.color(self.color)
 			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
 			.range(self.range.map(|(start, end)| (start * 6, end * 6)))
 			.draw(ctx)
 	}
 }
 
+/// How the sprites in a [`SpriteBatch`] are ordered when drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpriteSortMode {
+	/// Sprites are drawn in the order of the slots they occupy in the
+	/// sprite batch.
+	#[default]
+	None,
+	/// Sprites are drawn from lowest to highest
+	/// [`depth`](SpriteParams::depth).
+	Depth,
+	/// Sprites are drawn from lowest to highest Y position of the
+	/// sprite's origin, so sprites lower on the screen are drawn on top.
+	///
+	/// Sprites with the same Y position are ordered by depth.
+	YPosition,
+}
+
 /// Uniquely identifies a sprite within a [`SpriteBatch`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SpriteId(pub Index);
@@ -212,7 +283,130 @@ pub struct SpriteLimitReached;
 #[display("No sprite with this ID exists")]
 pub struct InvalidSpriteId;
 
+const EMPTY_VERTEX: Vertex2d = Vertex2d {
+	position: Vec2::ZERO,
+	texture_coords: Vec2::ZERO,
+	color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+};
+
 #[derive(Debug)]
 struct SpriteBatchInner {
-	sprites: Arena<()>,
+	sprites: Arena<Sprite>,
+	sort_mode: SpriteSortMode,
+	/// The IDs of all the sprites in draw order. Only used when sorting
+	/// is enabled.
+	sorted_ids: Vec<Index>,
+	needs_sort: bool,
+	/// The number of sprites that may be in the vertex buffer from the
+	/// last upload. When sorting, any of these past the sorted sprites
+	/// are cleared.
+	num_uploaded_sprites: usize,
+}
+
+impl SpriteBatchInner {
+	fn new(capacity: usize) -> Self {
+		Self {
+			sprites: Arena::with_capacity(capacity),
+			sort_mode: SpriteSortMode::None,
+			sorted_ids: Vec::with_capacity(capacity),
+			needs_sort: false,
+			num_uploaded_sprites: 0,
+		}
+	}
+
+	fn set_sort_mode(&mut self, sort_mode: SpriteSortMode) {
+		if self.sort_mode == SpriteSortMode::None {
+			// unsorted sprites are written to the slot they occupy, so
+			// any slot in the buffer could have a sprite in it
+			self.num_uploaded_sprites = self.sprites.capacity();
+		}
+		self.sort_mode = sort_mode;
+		if sort_mode == SpriteSortMode::None {
+			self.sorted_ids.clear();
+			self.needs_sort = false;
+		} else {
+			self.sorted_ids = self.sprites.iter().map(|(index, _)| index).collect();
+			self.needs_sort = true;
+		}
+	}
+
+	/// Sorts the sprites and returns the vertices to write to the front
+	/// of the vertex buffer.
+	fn sorted_vertices(&mut self, texture_size: UVec2) -> Vec<Vertex2d> {
+		let _span = tracy_client::span!();
+		let sort_mode = self.sort_mode;
+		let sprites = &self.sprites;
+		// the previous order is a good starting point, since usually only
+		// a few sprites change position between frames
+		self.sorted_ids.sort_by(|a, b| {
+			let (a_primary, a_secondary) = sprites[*a].sort_key(sort_mode);
+			let (b_primary, b_secondary) = sprites[*b].sort_key(sort_mode);
+			a_primary
+				.total_cmp(&b_primary)
+				.then(a_secondary.total_cmp(&b_secondary))
+		});
+		let mut vertices = self
+			.sorted_ids
+			.iter()
+			.flat_map(|index| self.sprites[*index].vertices(texture_size))
+			.collect::<Vec<_>>();
+		let num_sprites = self.sorted_ids.len();
+		if self.num_uploaded_sprites > num_sprites {
+			vertices.resize(self.num_uploaded_sprites * 4, EMPTY_VERTEX);
+		}
+		self.num_uploaded_sprites = num_sprites;
+		self.needs_sort = false;
+		vertices
+	}
+
+	/// Returns the vertices of every slot in the vertex buffer, with each
+	/// sprite in the slot it occupies.
+	fn slot_order_vertices(&self, texture_size: UVec2) -> Vec<Vertex2d> {
+		let _span = tracy_client::span!();
+		let mut vertices = vec![EMPTY_VERTEX; self.sprites.capacity() * 4];
+		for (index, sprite) in &self.sprites {
+			let (sprite_index, _) = index.into_raw_parts();
+			vertices[sprite_index * 4..sprite_index * 4 + 4]
+				.copy_from_slice(&sprite.vertices(texture_size));
+		}
+		vertices
+	}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sprite {
+	texture_region: Rect,
+	params: SpriteParams,
+}
+
+impl Sprite {
+	fn sort_key(&self, sort_mode: SpriteSortMode) -> (f32, f32) {
+		match sort_mode {
+			SpriteSortMode::None | SpriteSortMode::Depth => (self.params.depth, 0.0),
+			SpriteSortMode::YPosition => (self.params.transform.translation.y, self.params.depth),
+		}
+	}
+
+	fn vertices(&self, texture_size: UVec2) -> [Vertex2d; 4] {
+		let untransformed_display_rect = Rect::new(Vec2::ZERO, self.texture_region.size);
+		let texture_size = texture_size.as_vec2();
+		let relative_texture_region = Rect::from_corners(
+			self.texture_region.top_left / texture_size,
+			self.texture_region.bottom_right() / texture_size,
+		);
+		let mut vertices = [EMPTY_VERTEX; 4];
+		for (vertex, (position, texture_coords)) in vertices.iter_mut().zip(
+			untransformed_display_rect
+				.corners()
+				.into_iter()
+				.zip(relative_texture_region.corners()),
+		) {
+			*vertex = Vertex2d {
+				position: self.params.transform.transform_point2(position),
+				texture_coords,
+				color: self.params.color,
+			};
+		}
+		vertices
+	}
 }
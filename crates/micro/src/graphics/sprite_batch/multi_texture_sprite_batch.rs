@@ -0,0 +1,342 @@
+use std::sync::{Arc, Mutex};
+
+use bytemuck::{Pod, Zeroable};
+use derive_more::derive::{Display, Error};
+use generational_arena::Arena;
+use glam::{Mat4, Vec2};
+use palette::LinSrgba;
+
+use crate::{
+	Context,
+	color::ColorConstants,
+	graphics::{
+		BlendMode, HasVertexAttributes, IntoIndexRange, Vertex, VertexAttribute, mesh::DynamicMesh,
+		texture::Texture, vertex_attr_array,
+	},
+	math::Rect,
+	standard_draw_param_methods,
+};
+
+use super::{InvalidSpriteId, SpriteId, SpriteLimitReached, SpriteParams};
+
+/// Like a [`SpriteBatch`](super::SpriteBatch), but the sprites can use
+/// different textures.
+///
+/// All of the sprites are drawn in one draw call in the order they were
+/// added, regardless of which texture they use. The number of different
+/// textures a batch can hold depends on the graphics adapter and can be
+/// checked with [`MultiTextureSpriteBatch::max_textures`]. A texture stops
+/// counting towards the limit once all the sprites using it are removed.
+///
+/// Sprites are drawn using a built-in shader, so any shader pushed to the
+/// graphics stack is ignored when drawing the batch.
+#[derive(Debug, Clone)]
+pub struct MultiTextureSpriteBatch {
+	inner: Arc<Mutex<MultiTextureSpriteBatchInner>>,
+	/// The transform to use when drawing this sprite batch.
+	pub transform: Mat4,
+	/// The blend color to use when drawing this sprite batch.
+	pub color: LinSrgba,
+	/// The blend mode to use when drawing this sprite batch.
+	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this sprite batch.
+	pub palette: Option<Texture>,
+	/// The min and max sprite index to draw, in the order the sprites
+	/// were added.
+	///
+	/// Setting this results in only some of the sprites being drawn.
+	/// When `None`, all the sprites are drawn.
+	pub range: Option<(u32, u32)>,
+}
+
+impl MultiTextureSpriteBatch {
+	/// Creates a new [`MultiTextureSpriteBatch`] that can hold a maximum of
+	/// `capacity` sprites.
+	pub fn new(ctx: &Context, capacity: usize) -> Self {
+		let _span = tracy_client::span!();
+		Self {
+			inner: Arc::new(Mutex::new(MultiTextureSpriteBatchInner {
+				sprites: Arena::with_capacity(capacity),
+				ordered_sprites: Vec::with_capacity(capacity),
+				textures: vec![],
+				mesh: DynamicMesh::with_capacity(ctx, capacity * 4, capacity * 6),
+				dirty: false,
+			})),
+			transform: Mat4::IDENTITY,
+			color: LinSrgba::WHITE,
+			blend_mode: BlendMode::default(),
+			palette: None,
+			range: None,
+		}
+	}
+
+	/// Returns the maximum number of different textures a
+	/// [`MultiTextureSpriteBatch`] can hold on this graphics adapter.
+	pub fn max_textures(ctx: &Context) -> usize {
+		ctx.graphics
+			.default_resources
+			.max_multi_texture_sprite_batch_textures
+	}
+
+	standard_draw_param_methods!();
+
+	/// Sets the range of sprites to draw.
+	///
+	/// Setting this results in only some of the sprites being drawn.
+	/// When `None`, all the sprites are drawn.
+	pub fn range(&self, range: impl IntoIndexRange) -> Self {
+		let mut new = self.clone();
+		new.range = range.into_index_range(self.len() as u32);
+		new
+	}
+
+	/// Returns the number of sprites in this [`MultiTextureSpriteBatch`].
+	pub fn len(&self) -> usize {
+		self.inner
+			.try_lock()
+			.expect("sprite batch mutex locked")
+			.ordered_sprites
+			.len()
+	}
+
+	/// Returns `true` if there are no sprites in this
+	/// [`MultiTextureSpriteBatch`].
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Adds a sprite to the batch containing the entire `texture`.
+	///
+	/// Returns a [`SpriteId`] which can be used to remove the sprite later.
+	pub fn add(
+		&mut self,
+		ctx: &Context,
+		texture: &Texture,
+		params: impl Into<SpriteParams>,
+	) -> Result<SpriteId, AddMultiTextureSpriteError> {
+		let size = texture.size().as_vec2();
+		self.add_region(ctx, texture, Rect::new(Vec2::ZERO, size), params)
+	}
+
+	/// Adds a sprite to the batch containing a portion of the `texture`.
+	///
+	/// Returns a [`SpriteId`] which can be used to remove the sprite later.
+	pub fn add_region(
+		&mut self,
+		ctx: &Context,
+		texture: &Texture,
+		texture_region: Rect,
+		params: impl Into<SpriteParams>,
+	) -> Result<SpriteId, AddMultiTextureSpriteError> {
+		let _span = tracy_client::span!();
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		// checked before claiming a texture slot so a sprite that can't be
+		// added doesn't leave an unused texture behind
+		if inner.sprites.len() >= inner.sprites.capacity() {
+			return Err(AddMultiTextureSpriteError::SpriteLimitReached(
+				SpriteLimitReached,
+			));
+		}
+		let texture_index = inner.texture_slot(texture, Self::max_textures(ctx))?;
+		let id = inner
+			.sprites
+			.try_insert(texture_index)
+			.map(SpriteId)
+			.map_err(|_| AddMultiTextureSpriteError::SpriteLimitReached(SpriteLimitReached))?;
+		inner.textures[texture_index]
+			.as_mut()
+			.expect("texture slot should be in use")
+			.num_sprites += 1;
+		let params: SpriteParams = params.into();
+		let untransformed_display_rect = Rect::new(Vec2::ZERO, texture_region.size);
+		let relative_texture_region = texture.relative_rect(texture_region);
+		let mut vertices = [MultiTextureVertex2d::zeroed(); 4];
+		for (vertex, (position, texture_coords)) in vertices.iter_mut().zip(
+			untransformed_display_rect
+				.corners()
+				.into_iter()
+				.zip(relative_texture_region.corners()),
+		) {
+			*vertex = MultiTextureVertex2d {
+				position: params.transform.transform_point2(position),
+				texture_coords,
+				color: params.color,
+				texture_index: texture_index as u32,
+			};
+		}
+		inner.ordered_sprites.push(OrderedSprite { id, vertices });
+		inner.dirty = true;
+		Ok(id)
+	}
+
+	/// Removes the sprite with the given `id` from the batch.
+	///
+	/// The remaining sprites keep their order.
+	pub fn remove(&mut self, id: SpriteId) -> Result<(), InvalidSpriteId> {
+		let _span = tracy_client::span!();
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		let texture_index = inner.sprites.remove(id.0).ok_or(InvalidSpriteId)?;
+		let slot = &mut inner.textures[texture_index];
+		let TextureSlot { num_sprites, .. } = slot.as_mut().expect("texture slot should be in use");
+		*num_sprites -= 1;
+		// free up the slot for other textures once no sprites use it
+		if *num_sprites == 0 {
+			*slot = None;
+		}
+		inner.ordered_sprites.retain(|sprite| sprite.id != id);
+		inner.dirty = true;
+		Ok(())
+	}
+
+	/// Removes all of the sprites and textures from the batch.
+	pub fn clear(&mut self) {
+		let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+		inner.sprites.clear();
+		inner.ordered_sprites.clear();
+		inner.textures.clear();
+		inner.dirty = true;
+	}
+
+	/// Draws the [`MultiTextureSpriteBatch`].
+	pub fn draw(&self, ctx: &mut Context) {
+		let _span = tracy_client::span!();
+		let (mesh, mut textures) = {
+			let mut inner = self.inner.try_lock().expect("sprite batch mutex locked");
+			if inner.dirty {
+				inner.upload(ctx);
+			}
+			(inner.mesh.clone(), inner.textures.clone())
+		};
+		let default_resources = &ctx.graphics.default_resources;
+		textures.resize(
+			default_resources.max_multi_texture_sprite_batch_textures,
+			None,
+		);
+		let textures = textures
+			.into_iter()
+			.map(|slot| {
+				slot.map(|slot| slot.texture)
+					.unwrap_or_else(|| default_resources.default_texture.clone())
+			})
+			.collect();
+		let shader = default_resources
+			.multi_texture_sprite_shader
+			.with_textures(textures);
+		let ctx = &mut ctx.push(&shader);
+		mesh.transformed(self.transform)
+			.color(self.color)
+			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
+			.range(self.range.map(|(start, end)| (start * 6, end * 6)))
+			.draw(ctx)
+	}
+}
+
+/// An error that can occur when adding a sprite to a
+/// [`MultiTextureSpriteBatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+pub enum AddMultiTextureSpriteError {
+	/// The batch is full.
+	SpriteLimitReached(SpriteLimitReached),
+	/// The sprite uses a new texture, but the batch already has the
+	/// maximum number of textures.
+	#[display("Cannot add more textures to the sprite batch")]
+	TextureLimitReached,
+}
+
+#[derive(Debug)]
+struct MultiTextureSpriteBatchInner {
+	/// The texture index of each sprite.
+	sprites: Arena<usize>,
+	ordered_sprites: Vec<OrderedSprite>,
+	/// The textures used by the sprites. Slots are emptied when no sprites
+	/// use them anymore so they can be reused by other textures.
+	textures: Vec<Option<TextureSlot>>,
+	mesh: DynamicMesh<MultiTextureVertex2d>,
+	dirty: bool,
+}
+
+impl MultiTextureSpriteBatchInner {
+	/// Returns the index of the slot holding `texture`, putting it in an
+	/// empty slot if it isn't in the batch yet.
+	fn texture_slot(
+		&mut self,
+		texture: &Texture,
+		max_textures: usize,
+	) -> Result<usize, AddMultiTextureSpriteError> {
+		if let Some(index) = self.textures.iter().position(|slot| {
+			slot.as_ref()
+				.is_some_and(|slot| slot.texture.texture == texture.texture)
+		}) {
+			return Ok(index);
+		}
+		let new_slot = Some(TextureSlot {
+			texture: texture.clone(),
+			num_sprites: 0,
+		});
+		if let Some(index) = self.textures.iter().position(Option::is_none) {
+			self.textures[index] = new_slot;
+			return Ok(index);
+		}
+		if self.textures.len() >= max_textures {
+			return Err(AddMultiTextureSpriteError::TextureLimitReached);
+		}
+		self.textures.push(new_slot);
+		Ok(self.textures.len() - 1)
+	}
+
+	fn upload(&mut self, ctx: &Context) {
+		let _span = tracy_client::span!();
+		let vertices = self
+			.ordered_sprites
+			.iter()
+			.flat_map(|sprite| sprite.vertices)
+			.collect::<Vec<_>>();
+		let indices = (0..self.ordered_sprites.len() as u32)
+			.flat_map(|i| {
+				let start_index = i * 4;
+				[
+					start_index,
+					start_index + 1,
+					start_index + 3,
+					start_index + 1,
+					start_index + 2,
+					start_index + 3,
+				]
+			})
+			.collect::<Vec<_>>();
+		self.mesh.set(ctx, &vertices, &indices);
+		self.dirty = false;
+	}
+}
+
+#[derive(Debug, Clone)]
+struct TextureSlot {
+	texture: Texture,
+	/// The number of sprites using the texture.
+	num_sprites: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrderedSprite {
+	id: SpriteId,
+	vertices: [MultiTextureVertex2d; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct MultiTextureVertex2d {
+	position: Vec2,
+	texture_coords: Vec2,
+	color: LinSrgba,
+	texture_index: u32,
+}
+
+impl Vertex for MultiTextureVertex2d {}
+
+impl HasVertexAttributes for MultiTextureVertex2d {
+	fn attributes() -> Vec<VertexAttribute> {
+		vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4, 3 => Uint32].into()
+	}
+}
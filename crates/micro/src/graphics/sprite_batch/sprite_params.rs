@@ -10,6 +10,11 @@ pub struct SpriteParams {
 	pub transform: Affine2,
 	/// The blend color of this sprite.
 	pub color: LinSrgba,
+	/// The sort key used when the sprite batch is sorted by
+	/// [`SpriteSortMode::Depth`](super::SpriteSortMode::Depth).
+	///
+	/// Sprites with lower depths are drawn first.
+	pub depth: f32,
 }
 
 impl SpriteParams {
@@ -18,6 +23,7 @@ impl SpriteParams {
 		Self {
 			transform: Affine2::IDENTITY,
 			color: LinSrgba::WHITE,
+			depth: 0.0,
 		}
 	}
 
@@ -60,6 +66,11 @@ impl SpriteParams {
 			..self
 		}
 	}
+
+	/// Sets the depth of the sprite.
+	pub fn depth(self, depth: f32) -> Self {
+		Self { depth, ..self }
+	}
 }
 
 impl Default for SpriteParams {
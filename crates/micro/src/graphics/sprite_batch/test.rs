@@ -0,0 +1,152 @@
+use glam::{Affine2, UVec2, Vec2, vec2};
+
+use crate::{
+	graphics::sprite_batch::{SpriteParams, SpriteSortMode},
+	math::Rect,
+};
+
+use super::{EMPTY_VERTEX, Sprite, SpriteBatchInner, Vertex2d};
+
+const TEXTURE_SIZE: UVec2 = UVec2::splat(16);
+
+fn sprite(x: f32, y: f32, depth: f32) -> Sprite {
+	Sprite {
+		texture_region: Rect::new(Vec2::ZERO, Vec2::splat(16.0)),
+		params: SpriteParams {
+			transform: Affine2::from_translation(vec2(x, y)),
+			depth,
+			..SpriteParams::new()
+		},
+	}
+}
+
+/// Returns the position of the top-left corner of each sprite in the
+/// vertices, or `None` for empty slots.
+fn positions(vertices: &[Vertex2d]) -> Vec<Option<Vec2>> {
+	vertices
+		.chunks(4)
+		.map(|quad| {
+			(quad != [EMPTY_VERTEX; 4]).then(|| {
+				quad.iter()
+					.map(|vertex| vertex.position)
+					.reduce(Vec2::min)
+					.unwrap()
+			})
+		})
+		.collect()
+}
+
+fn batch(sprites: &[Sprite], sort_mode: SpriteSortMode) -> SpriteBatchInner {
+	let mut inner = SpriteBatchInner::new(sprites.len());
+	for &sprite in sprites {
+		inner.sprites.try_insert(sprite).unwrap();
+	}
+	inner.set_sort_mode(sort_mode);
+	inner
+}
+
+#[test]
+fn sorts_by_depth() {
+	let mut inner = batch(
+		&[
+			sprite(0.0, 0.0, 2.0),
+			sprite(1.0, 0.0, 0.0),
+			sprite(2.0, 0.0, 1.0),
+		],
+		SpriteSortMode::Depth,
+	);
+	assert_eq!(
+		positions(&inner.sorted_vertices(TEXTURE_SIZE)),
+		vec![
+			Some(vec2(1.0, 0.0)),
+			Some(vec2(2.0, 0.0)),
+			Some(vec2(0.0, 0.0))
+		]
+	);
+}
+
+#[test]
+fn sorts_by_y_position_then_depth() {
+	let mut inner = batch(
+		&[
+			sprite(0.0, 5.0, 0.0),
+			sprite(1.0, 2.0, 1.0),
+			sprite(2.0, 2.0, 0.0),
+		],
+		SpriteSortMode::YPosition,
+	);
+	assert_eq!(
+		positions(&inner.sorted_vertices(TEXTURE_SIZE)),
+		vec![
+			Some(vec2(2.0, 2.0)),
+			Some(vec2(1.0, 2.0)),
+			Some(vec2(0.0, 5.0))
+		]
+	);
+}
+
+#[test]
+fn sorts_nan_keys() {
+	let mut inner = batch(
+		&[
+			sprite(0.0, 0.0, f32::NAN),
+			sprite(1.0, 0.0, 1.0),
+			sprite(2.0, 0.0, f32::NAN),
+			sprite(3.0, 0.0, 0.0),
+		],
+		SpriteSortMode::Depth,
+	);
+	let positions = positions(&inner.sorted_vertices(TEXTURE_SIZE));
+	assert_eq!(positions[..2], [Some(vec2(3.0, 0.0)), Some(vec2(1.0, 0.0))]);
+	assert_eq!(positions.len(), 4);
+}
+
+#[test]
+fn clears_removed_sprites_when_sorting() {
+	let mut inner = batch(
+		&[sprite(0.0, 0.0, 0.0), sprite(1.0, 0.0, 1.0)],
+		SpriteSortMode::Depth,
+	);
+	inner.sorted_vertices(TEXTURE_SIZE);
+	let removed = inner.sorted_ids.remove(0);
+	inner.sprites.remove(removed);
+	assert_eq!(
+		positions(&inner.sorted_vertices(TEXTURE_SIZE)),
+		vec![Some(vec2(1.0, 0.0)), None]
+	);
+}
+
+#[test]
+fn switching_modes_with_a_removed_sprite() {
+	let mut inner = batch(
+		&[
+			sprite(0.0, 0.0, 1.0),
+			sprite(1.0, 0.0, 0.0),
+			sprite(2.0, 0.0, 0.0),
+		],
+		SpriteSortMode::None,
+	);
+	let removed = inner.sprites.iter().nth(1).unwrap().0;
+	inner.sprites.remove(removed);
+	assert_eq!(
+		positions(&inner.slot_order_vertices(TEXTURE_SIZE)),
+		vec![Some(vec2(0.0, 0.0)), None, Some(vec2(2.0, 0.0))]
+	);
+	// the sprite in the last slot has to be cleared, or it would be
+	// drawn twice
+	inner.set_sort_mode(SpriteSortMode::Depth);
+	assert_eq!(
+		positions(&inner.sorted_vertices(TEXTURE_SIZE)),
+		vec![Some(vec2(2.0, 0.0)), Some(vec2(0.0, 0.0)), None]
+	);
+	inner.set_sort_mode(SpriteSortMode::None);
+	assert_eq!(
+		positions(&inner.slot_order_vertices(TEXTURE_SIZE)),
+		vec![Some(vec2(0.0, 0.0)), None, Some(vec2(2.0, 0.0))]
+	);
+	inner.set_sort_mode(SpriteSortMode::YPosition);
+	assert_eq!(
+		positions(&inner.sorted_vertices(TEXTURE_SIZE)),
+		vec![Some(vec2(2.0, 0.0)), Some(vec2(0.0, 0.0)), None]
+	);
+}
@@ -26,4 +26,19 @@ impl StorageBuffer {
 				}),
 		)
 	}
+
+	/// Overwrites data starting at the specified `index`, where `index`
+	/// is measured in elements of type `T`.
+	///
+	/// The data must fit within the buffer's original size.
+	///
+	/// This will modify all clones of this [`StorageBuffer`] as well.
+	pub fn write<T: NoUninit>(&self, ctx: &Context, index: usize, data: &[T]) {
+		let _span = tracy_client::span!();
+		ctx.graphics.queue.write_buffer(
+			&self.0,
+			(index * std::mem::size_of::<T>()) as u64,
+			bytemuck::cast_slice(data),
+		);
+	}
 }
@@ -1,25 +1,47 @@
 //! Types related to drawing text.
 
+mod bitmap_font;
+mod bitmap_text;
+mod glyph;
+mod glyph_atlas_stats;
+mod layout;
+mod rich_text;
+mod text_effects;
+
+pub use bitmap_font::*;
+pub use bitmap_text::*;
 pub use cosmic_text::{
 	Align as TextAlign, Stretch as TextStretch, Style as TextStyle, Weight as TextWeight,
 };
 use cosmic_text::{Attrs, Family, LetterSpacing, Metrics, Shaping};
+pub use glyph::*;
+pub use glyph_atlas_stats::*;
+use layout::TextLayout;
+pub use rich_text::*;
+pub use text_effects::*;
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, ops::Range, sync::Arc};
 
-use glam::{Mat4, Vec2, vec2};
+use bytemuck::{Pod, Zeroable};
+use glam::{Affine2, Mat4, Vec2, Vec4, vec2};
 use palette::LinSrgba;
 
 use crate::{
 	Context,
 	color::ColorConstants,
-	graphics::BlendMode,
-	math::{IRect, Rect},
+	graphics::{
+		BlendMode, HasVertexAttributes, Vertex, VertexAttribute, mesh::Mesh, texture::Texture,
+		vertex_attr_array,
+	},
+	math::Rect,
 	standard_draw_param_methods,
-	text::GlyphInfo,
+	text::{GlyphInfo, GlyphLease, SDF_FONT_SIZE, SDF_SPREAD},
 };
 
-use super::{IntoIndexRange, sprite_batch::SpriteBatch};
+use super::{
+	IntoIndexRange,
+	sprite_batch::{SpriteBatch, SpriteId, SpriteParams},
+};
 
 /// A block of text rendered into a texture.
 #[derive(Debug, Clone)]
@@ -33,17 +55,31 @@ pub struct Text {
 	pub color: LinSrgba,
 	/// The blend mode to use when drawing this text.
 	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this text.
+	pub palette: Option<Texture>,
 	/// The min and max character index to use when drawing this text.
 	///
 	/// Setting this results in a portion of the text being drawn.
 	/// When `None`, all the characters are drawn.
 	pub range: Option<(u32, u32)>,
+	/// The outline, shadow, and glow to draw around the glyphs.
+	///
+	/// Text that doesn't use signed distance field glyphs only draws
+	/// the shadow.
+	pub effects: TextEffects,
 }
 
 impl Text {
 	/// Creates a new [`Text`].
 	pub fn new(ctx: &mut Context, builder: TextBuilder) -> Self {
+		Self::new_rich(ctx, builder.into())
+	}
+
+	/// Creates a new [`Text`] made up of multiple spans with their own
+	/// settings.
+	pub fn new_rich(ctx: &mut Context, builder: RichTextBuilder) -> Self {
 		let _span = tracy_client::span!();
+		let sdf = builder.sdf;
 		let mut buffer = cosmic_text::Buffer::new(
 			&mut ctx.text.font_system,
 			Metrics::relative(builder.font_size, builder.line_height),
@@ -52,44 +88,113 @@ impl Text {
 			TextHorizontalSizing::Min => (None, None),
 			TextHorizontalSizing::Fixed { width, align } => (Some(width), Some(align)),
 		};
-		buffer.set_text(
-			&builder.text,
-			&Attrs {
-				family: Family::Name(&builder.font_family),
-				stretch: builder.stretch,
-				style: builder.style,
-				weight: builder.weight,
-				letter_spacing_opt: builder.letter_spacing.map(LetterSpacing),
-				..Attrs::new()
-			},
+		let default_attrs = Attrs {
+			family: Family::Name(&builder.font_family),
+			stretch: builder.stretch,
+			style: builder.style,
+			weight: builder.weight,
+			letter_spacing_opt: builder.letter_spacing.map(LetterSpacing),
+			..Attrs::new()
+		};
+		// each span's index is stored in the glyph metadata so the glyphs
+		// can be matched back up with the span colors
+		let span_colors = builder
+			.spans
+			.iter()
+			.map(|span| span.color.unwrap_or(builder.color))
+			.collect::<Vec<_>>();
+		buffer.set_rich_text(
+			builder.spans.iter().enumerate().map(|(i, span)| {
+				let mut attrs = default_attrs.clone().metadata(i);
+				if let Some(font_family) = &span.font_family {
+					attrs.family = Family::Name(font_family);
+				}
+				if let Some(font_size) = span.font_size {
+					attrs.metrics_opt =
+						Some(Metrics::relative(font_size, builder.line_height).into());
+				}
+				attrs.stretch = span.stretch.unwrap_or(attrs.stretch);
+				attrs.style = span.style.unwrap_or(attrs.style);
+				attrs.weight = span.weight.unwrap_or(attrs.weight);
+				(span.text.as_str(), attrs)
+			}),
+			&default_attrs,
 			Shaping::Advanced,
 			align,
 		);
 		buffer.set_size(width, None);
 		buffer.shape_until_scroll(&mut ctx.text.font_system, true);
-		let mut sprites: Vec<(IRect, Vec2)> = vec![];
+		let layout = TextLayout::new(buffer);
+		let mut glyphs: Vec<Glyph> = vec![];
+		let mut glyph_leases = vec![];
 		let mut glyph_bounds: Option<Rect> = None;
 		let mut line_bounds: Option<Rect> = None;
-		for run in buffer.layout_runs() {
+		for (line, run) in layout.buffer().layout_runs().enumerate() {
 			for glyph in run.glyphs {
 				let physical_glyph = glyph.physical((0.0, 0.0), 1.0);
-				let Some(GlyphInfo {
+				let (glyph_info, position, scale) = if sdf {
+					// sdf glyphs are shared between all font sizes, so they're
+					// positioned without snapping to whole pixels
+					let Some(glyph_info) = ctx.text.sdf_glyph_rect(
+						&ctx.graphics.device,
+						&ctx.graphics.queue,
+						physical_glyph.cache_key,
+					) else {
+						continue;
+					};
+					let scale = glyph.font_size / SDF_FONT_SIZE;
+					let origin = vec2(
+						glyph.x + glyph.font_size * glyph.x_offset,
+						run.line_y + glyph.y - glyph.font_size * glyph.y_offset,
+					);
+					let position = origin + glyph_info.offset.as_vec2() * scale;
+					(glyph_info, position, scale)
+				} else {
+					let Some(glyph_info) = ctx.text.glyph_rect(
+						&ctx.graphics.device,
+						&ctx.graphics.queue,
+						physical_glyph.cache_key,
+					) else {
+						continue;
+					};
+					let position = vec2(
+						physical_glyph.x as f32,
+						run.line_y + physical_glyph.y as f32,
+					) + glyph_info.offset.as_vec2();
+					(glyph_info, position, 1.0)
+				};
+				let GlyphInfo {
+					page,
 					texture_rect,
-					offset,
-				}) = ctx.text.glyph_rect(
-					&ctx.graphics.device,
-					&ctx.graphics.queue,
-					physical_glyph.cache_key,
-				)
-				else {
-					continue;
+					lease,
+					..
+				} = glyph_info;
+				glyph_leases.push(lease);
+				let color = span_colors
+					.get(glyph.metadata)
+					.copied()
+					.unwrap_or(builder.color);
+				let char_index = layout.line_start_char_index(run.line_i)
+					+ run.text[..glyph.start].chars().count();
+				let glyph = Glyph {
+					position,
+					page,
+					texture_rect,
+					scale,
+					char_index,
+					line,
+					color,
+				};
+				glyphs.push(glyph);
+				// the padding around sdf glyphs is only used for effects,
+				// so it shouldn't count towards the bounds
+				let individual_glyph_bounds = if sdf {
+					glyph
+						.bounds()
+						.padded(Vec2::splat(-SDF_SPREAD as f32 * scale))
+				} else {
+					glyph.bounds()
 				};
-				let position = vec2(
-					physical_glyph.x as f32,
-					run.line_y + physical_glyph.y as f32,
-				) + offset.as_vec2();
-				sprites.push((texture_rect, position));
-				let individual_glyph_bounds = Rect::new(position, texture_rect.size.as_vec2());
 				if let Some(glyph_bounds) = &mut glyph_bounds {
 					*glyph_bounds = glyph_bounds.union(individual_glyph_bounds);
 				} else {
@@ -108,23 +213,30 @@ impl Text {
 				}
 			}
 		}
-		let mut sprite_batch = SpriteBatch::new(ctx, &ctx.text.texture, sprites.len());
-		for (texture_region, position) in &sprites {
-			sprite_batch
-				.add_region(ctx, texture_region.as_rect(), *position)
-				.expect("sprite batch is full");
-		}
+		// the textures are captured now so the text keeps drawing correctly
+		// if the atlas pages are resized later
+		let page_textures = glyphs
+			.iter()
+			.map(|glyph| (glyph.page, ctx.text.page_texture(glyph.page).clone()))
+			.collect::<BTreeMap<_, _>>();
+		let pages = glyph_pages(ctx, page_textures, &glyphs, sdf);
 		Self {
 			inner: Arc::new(TextInner {
-				sprite_batch,
+				pages,
+				_glyph_leases: glyph_leases,
+				layout: Arc::new(layout),
 				glyph_bounds,
 				line_bounds,
-				num_glyphs: sprites.len() as u32,
+				num_glyphs: glyphs.len() as u32,
+				glyphs,
+				sdf,
 			}),
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::default(),
+			palette: None,
 			range: None,
+			effects: builder.effects,
 		}
 	}
 
@@ -140,11 +252,77 @@ impl Text {
 		new
 	}
 
+	/// Sets the outline, shadow, and glow to draw around the glyphs.
+	///
+	/// Outlines, glows, and soft shadows are only drawn for text built
+	/// with [`TextBuilder::sdf`]. Other text only draws the shadow.
+	pub fn effects(&self, effects: TextEffects) -> Self {
+		let mut new = self.clone();
+		new.effects = effects;
+		new
+	}
+
+	/// Returns `true` if this text uses signed distance field glyphs.
+	pub fn is_sdf(&self) -> bool {
+		self.inner.sdf
+	}
+
 	/// Returns the number of glyphs this text contains.
 	pub fn num_glyphs(&self) -> u32 {
 		self.inner.num_glyphs
 	}
 
+	/// Returns the glyphs in this text in the order they're drawn.
+	///
+	/// The indices of the glyphs match the indices used by [`Text::range`].
+	pub fn glyphs(&self) -> &[Glyph] {
+		&self.inner.glyphs
+	}
+
+	/// Changes the transform and color of each glyph with the `params`
+	/// callback. This is useful for effects like wavy or shaking text.
+	///
+	/// The returned params' transform is applied around the center of the
+	/// glyph, and its color is multiplied with the glyph's own color.
+	///
+	/// The glyphs' vertices are updated in place, so this is cheap enough
+	/// to call every frame. Copies of this text (for example, ones made
+	/// with [`Text::color`]) share the glyphs, so they're changed as well.
+	pub fn set_glyph_params(&self, ctx: &Context, mut params: impl FnMut(&Glyph) -> SpriteParams) {
+		let _span = tracy_client::span!();
+		let inner = &self.inner;
+		for page in &inner.pages {
+			match &page.glyphs {
+				TextPageGlyphs::Bitmap {
+					sprite_ids,
+					sprite_batch,
+				} => {
+					for (&index, &sprite_id) in page.glyph_indices.iter().zip(sprite_ids.iter()) {
+						let glyph = &inner.glyphs[index as usize];
+						sprite_batch
+							.set_params(ctx, sprite_id, glyph_sprite_params(glyph, params(glyph)))
+							.expect("glyph sprite was removed from the sprite batch");
+					}
+				}
+				TextPageGlyphs::Sdf(mesh) => {
+					let vertices = page
+						.glyph_indices
+						.iter()
+						.flat_map(|&index| {
+							let glyph = &inner.glyphs[index as usize];
+							sdf_glyph_vertices(
+								glyph,
+								glyph_sprite_params(glyph, params(glyph)),
+								&page.texture,
+							)
+						})
+						.collect::<Vec<_>>();
+					mesh.set_vertices(ctx, 0, &vertices);
+				}
+			}
+		}
+	}
+
 	/// Returns a rectangle that surrounds all the glyphs in the text.
 	///
 	/// Returns `None` if there's no characters in this [`Text`].
@@ -160,25 +338,264 @@ impl Text {
 		self.inner.line_bounds
 	}
 
+	/// Returns the index of the character closest to the `point`
+	/// (relative to the text), or `None` if the text has no lines.
+	///
+	/// The index is where a text cursor would be placed when clicking
+	/// the point, so it can be one past the last character of a line.
+	/// Character indices count characters (not bytes) across all lines,
+	/// including line endings, like [`Glyph::char_index`].
+	pub fn hit_test(&self, point: impl Into<Vec2>) -> Option<usize> {
+		let _span = tracy_client::span!();
+		let point = point.into();
+		self.inner.layout.hit_test(point.x, point.y)
+	}
+
+	/// Returns the rectangle a text cursor placed before the character at
+	/// `char_index` would occupy, relative to the text. The rectangle has
+	/// a width of `0.0` and spans the height of the line.
+	///
+	/// Indices past the end of a line place the cursor at the end of the
+	/// line. Returns `None` if the text has no lines.
+	pub fn cursor_rect(&self, char_index: usize) -> Option<Rect> {
+		let _span = tracy_client::span!();
+		self.inner.layout.cursor_rect(char_index)
+	}
+
+	/// Returns the rectangles covering the characters in the `range` of
+	/// character indices, relative to the text, for drawing a selection
+	/// highlight.
+	///
+	/// There's one rectangle per line the selection covers (or more for
+	/// lines with mixed left-to-right and right-to-left text).
+	pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+		let _span = tracy_client::span!();
+		self.inner.layout.selection_rects(range)
+	}
+
 	/// Draws the text.
 	pub fn draw(&self, ctx: &mut Context) {
 		let _span = tracy_client::span!();
-		self.inner
-			.sprite_batch
-			.transformed(self.transform)
-			.color(self.color)
-			.blend_mode(self.blend_mode)
-			.range(self.range)
-			.draw(ctx);
+		if self.inner.sdf {
+			let shader = ctx
+				.graphics
+				.default_resources
+				.sdf_text_shader
+				.with_params(ctx, self.effects.to_raw());
+			self.draw_pages(&mut ctx.push(&shader), self.transform, self.color);
+		} else {
+			// without a distance field, the shadow is drawn as a tinted
+			// copy of the text behind it
+			if let Some(shadow) = self.effects.shadow {
+				let mut color = shadow.color;
+				color.alpha *= self.color.alpha;
+				self.draw_pages(
+					ctx,
+					self.transform * Mat4::from_translation(shadow.offset.extend(0.0)),
+					color,
+				);
+			}
+			self.draw_pages(ctx, self.transform, self.color);
+		}
+	}
+
+	fn draw_pages(&self, ctx: &mut Context, transform: Mat4, color: LinSrgba) {
+		for page in &self.inner.pages {
+			// the glyph indices of each page are in ascending order, so the
+			// text's range covers a contiguous range of each page's sprites
+			let range = self.range.map(|(start, end)| {
+				(
+					page.glyph_indices.partition_point(|&index| index < start) as u32,
+					page.glyph_indices.partition_point(|&index| index < end) as u32,
+				)
+			});
+			if let Some((start, end)) = range
+				&& start >= end
+			{
+				continue;
+			}
+			match &page.glyphs {
+				TextPageGlyphs::Bitmap { sprite_batch, .. } => sprite_batch
+					.transformed(transform)
+					.color(color)
+					.blend_mode(self.blend_mode)
+					.palette(self.palette.as_ref())
+					.range(range)
+					.draw(ctx),
+				TextPageGlyphs::Sdf(mesh) => mesh
+					.texture(&page.texture)
+					.transformed(transform)
+					.color(color)
+					.blend_mode(self.blend_mode)
+					.palette(self.palette.as_ref())
+					.range(range.map(|(start, end)| (start * 6, end * 6)))
+					.draw(ctx),
+			}
+		}
 	}
 }
 
 #[derive(Debug)]
 struct TextInner {
-	pub(crate) sprite_batch: SpriteBatch,
+	pub(crate) pages: Vec<TextPage>,
+	/// Keeps the glyphs from being evicted from the atlas while the
+	/// text exists.
+	pub(crate) _glyph_leases: Vec<GlyphLease>,
+	pub(crate) layout: Arc<TextLayout>,
+	pub(crate) glyphs: Vec<Glyph>,
 	pub(crate) glyph_bounds: Option<Rect>,
 	pub(crate) line_bounds: Option<Rect>,
 	pub(crate) num_glyphs: u32,
+	pub(crate) sdf: bool,
+}
+
+/// The glyphs of a [`Text`] that are on one page of the glyph atlas.
+#[derive(Debug)]
+struct TextPage {
+	glyph_indices: Vec<u32>,
+	texture: Texture,
+	glyphs: TextPageGlyphs,
+}
+
+#[derive(Debug)]
+enum TextPageGlyphs {
+	/// A sprite for each glyph in `glyph_indices`.
+	Bitmap {
+		sprite_ids: Vec<SpriteId>,
+		sprite_batch: SpriteBatch,
+	},
+	/// A quad for each glyph in `glyph_indices`, drawn with the built-in
+	/// signed distance field text shader.
+	Sdf(Mesh<SdfGlyphVertex>),
+}
+
+fn glyph_pages(
+	ctx: &Context,
+	page_textures: BTreeMap<usize, Texture>,
+	glyphs: &[Glyph],
+	sdf: bool,
+) -> Vec<TextPage> {
+	page_textures
+		.into_iter()
+		.map(|(page, texture)| {
+			let glyph_indices = glyphs
+				.iter()
+				.enumerate()
+				.filter(|(_, glyph)| glyph.page == page)
+				.map(|(index, _)| index as u32)
+				.collect::<Vec<_>>();
+			let glyphs = if sdf {
+				TextPageGlyphs::Sdf(sdf_glyph_mesh(ctx, &texture, glyphs, &glyph_indices))
+			} else {
+				let mut sprite_batch = SpriteBatch::new(ctx, &texture, glyph_indices.len());
+				let sprite_ids = glyph_indices
+					.iter()
+					.map(|&index| {
+						let glyph = &glyphs[index as usize];
+						sprite_batch
+							.add_region(
+								ctx,
+								glyph.texture_rect.as_rect(),
+								glyph_sprite_params(glyph, SpriteParams::new()),
+							)
+							.expect("sprite batch is full")
+					})
+					.collect();
+				TextPageGlyphs::Bitmap {
+					sprite_ids,
+					sprite_batch,
+				}
+			};
+			TextPage {
+				glyph_indices,
+				texture,
+				glyphs,
+			}
+		})
+		.collect()
+}
+
+/// Returns the params for the sprite that draws a glyph, with the
+/// `params` transform applied around the center of the glyph.
+fn glyph_sprite_params(glyph: &Glyph, params: SpriteParams) -> SpriteParams {
+	let half_size = glyph.size() / 2.0;
+	let transform = Affine2::from_translation(glyph.position + half_size)
+		* params.transform
+		* Affine2::from_translation(-half_size)
+		* Affine2::from_scale(Vec2::splat(glyph.scale));
+	SpriteParams {
+		transform,
+		color: glyph.color * params.color,
+		depth: params.depth,
+	}
+}
+
+fn sdf_glyph_mesh(
+	ctx: &Context,
+	texture: &Texture,
+	glyphs: &[Glyph],
+	glyph_indices: &[u32],
+) -> Mesh<SdfGlyphVertex> {
+	let vertices = glyph_indices
+		.iter()
+		.flat_map(|&index| {
+			let glyph = &glyphs[index as usize];
+			sdf_glyph_vertices(
+				glyph,
+				glyph_sprite_params(glyph, SpriteParams::new()),
+				texture,
+			)
+		})
+		.collect::<Vec<_>>();
+	let indices = (0..glyph_indices.len() as u32)
+		.flat_map(|i| {
+			let start_index = i * 4;
+			[
+				start_index,
+				start_index + 1,
+				start_index + 3,
+				start_index + 1,
+				start_index + 2,
+				start_index + 3,
+			]
+		})
+		.collect::<Vec<_>>();
+	Mesh::new(ctx, &vertices, &indices)
+}
+
+/// Returns the vertices of the quad that draws a signed distance field
+/// glyph using the sprite `params` from [`glyph_sprite_params`].
+fn sdf_glyph_vertices(
+	glyph: &Glyph,
+	params: SpriteParams,
+	texture: &Texture,
+) -> [SdfGlyphVertex; 4] {
+	let untransformed_display_rect = Rect::new(Vec2::ZERO, glyph.texture_rect.size.as_vec2());
+	let relative_texture_rect = texture.relative_rect(glyph.texture_rect.as_rect());
+	let glyph_rect = relative_texture_rect
+		.top_left
+		.extend(relative_texture_rect.bottom_right().x)
+		.extend(relative_texture_rect.bottom_right().y);
+	// the shader grows the quad to make room for effects, so it needs to
+	// know how the glyph is transformed
+	let texture_size = texture.size().as_vec2();
+	let mut vertices = [SdfGlyphVertex::zeroed(); 4];
+	for (vertex, (position, texture_coords)) in vertices.iter_mut().zip(
+		untransformed_display_rect
+			.corners()
+			.into_iter()
+			.zip(relative_texture_rect.corners()),
+	) {
+		*vertex = SdfGlyphVertex {
+			position: params.transform.transform_point2(position),
+			texture_coords,
+			color: params.color,
+			glyph_rect,
+			position_per_texture_coord_x: params.transform.matrix2.x_axis * texture_size.x,
+			position_per_texture_coord_y: params.transform.matrix2.y_axis * texture_size.y,
+		};
+	}
+	vertices
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -192,6 +609,8 @@ pub struct TextBuilder {
 	pub weight: TextWeight,
 	pub letter_spacing: Option<f32>,
 	pub horizontal_sizing: TextHorizontalSizing,
+	pub sdf: bool,
+	pub effects: TextEffects,
 }
 
 impl TextBuilder {
@@ -206,6 +625,8 @@ impl TextBuilder {
 			weight: TextWeight::NORMAL,
 			letter_spacing: None,
 			horizontal_sizing: TextHorizontalSizing::default(),
+			sdf: false,
+			effects: TextEffects::default(),
 		}
 	}
 
@@ -260,6 +681,50 @@ impl TextBuilder {
 		}
 	}
 
+	/// Sets whether the text uses signed distance field glyphs.
+	///
+	/// SDF text stays sharp when scaled up and can be drawn with
+	/// [`TextEffects`], but small text may look softer than normal text.
+	pub fn sdf(self, sdf: bool) -> Self {
+		Self { sdf, ..self }
+	}
+
+	/// Sets the outline, shadow, and glow to draw around the glyphs.
+	///
+	/// Outlines, glows, and soft shadows are only drawn when the text uses
+	/// signed distance field glyphs (see [`TextBuilder::sdf`]).
+	pub fn effects(self, effects: TextEffects) -> Self {
+		Self { effects, ..self }
+	}
+
+	/// Draws an outline with the given width and color around each glyph.
+	pub fn outline(mut self, width: f32, color: impl Into<LinSrgba>) -> Self {
+		self.effects.outline = Some(TextOutline {
+			width,
+			color: color.into(),
+		});
+		self
+	}
+
+	/// Draws a shadow with the given offset and color behind each glyph.
+	pub fn shadow(mut self, offset: impl Into<Vec2>, color: impl Into<LinSrgba>) -> Self {
+		self.effects.shadow = Some(TextShadow {
+			offset: offset.into(),
+			color: color.into(),
+			softness: 0.0,
+		});
+		self
+	}
+
+	/// Draws a glow with the given radius and color around each glyph.
+	pub fn glow(mut self, radius: f32, color: impl Into<LinSrgba>) -> Self {
+		self.effects.glow = Some(TextGlow {
+			radius,
+			color: color.into(),
+		});
+		self
+	}
+
 	pub fn build(self, ctx: &mut Context) -> Text {
 		Text::new(ctx, self)
 	}
@@ -274,3 +739,35 @@ pub enum TextHorizontalSizing {
 		align: TextAlign,
 	},
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+struct SdfGlyphVertex {
+	position: Vec2,
+	texture_coords: Vec2,
+	color: LinSrgba,
+	/// The top-left and bottom-right texture coordinates of the glyph's
+	/// region of the atlas. The region's distance field is only valid
+	/// inside these bounds.
+	glyph_rect: Vec4,
+	/// How far the vertex moves per unit of texture coordinates along
+	/// each axis.
+	position_per_texture_coord_x: Vec2,
+	position_per_texture_coord_y: Vec2,
+}
+
+impl Vertex for SdfGlyphVertex {}
+
+impl HasVertexAttributes for SdfGlyphVertex {
+	fn attributes() -> Vec<VertexAttribute> {
+		vertex_attr_array![
+			0 => Float32x2,
+			1 => Float32x2,
+			2 => Float32x4,
+			3 => Float32x4,
+			4 => Float32x2,
+			5 => Float32x2,
+		]
+		.into()
+	}
+}
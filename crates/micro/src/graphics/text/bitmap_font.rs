@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod test;
+
+use std::{collections::HashMap, path::Path};
+
+use derive_more::derive::{Display, Error, From};
+use glam::{UVec2, Vec2, vec2};
+use image::ImageError;
+
+use crate::{
+	Context,
+	graphics::texture::{LoadTextureError, Texture, TextureSettings},
+	math::Rect,
+};
+
+/// A font made of pre-drawn images of each character.
+///
+/// Bitmap fonts can be loaded from [BMFont](https://www.angelcode.com/products/bmfont/)
+/// `.fnt` files (text format) or from an image with the characters
+/// arranged in a grid. They're drawn using [`BitmapText`](super::BitmapText).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitmapFont {
+	pages: Vec<Texture>,
+	glyphs: HashMap<char, BitmapGlyph>,
+	kerning: HashMap<(char, char), f32>,
+	line_height: f32,
+	base: f32,
+}
+
+impl BitmapFont {
+	/// Loads a BMFont `.fnt` file in the text format.
+	///
+	/// The page images are loaded from paths relative to the `.fnt` file.
+	pub fn from_bmfont_file(
+		ctx: &Context,
+		path: impl AsRef<Path>,
+		texture_settings: TextureSettings,
+	) -> Result<Self, LoadBitmapFontError> {
+		let _span = tracy_client::span!();
+		let path = path.as_ref();
+		let source = std::fs::read_to_string(path)?;
+		let dir = path.parent().unwrap_or(Path::new(""));
+		Self::from_bmfont_str(&source, |file| {
+			Ok(Texture::from_file(
+				ctx,
+				dir.join(file),
+				texture_settings.clone(),
+			)?)
+		})
+	}
+
+	/// Parses the contents of a BMFont `.fnt` file in the text format.
+	///
+	/// `load_page` is called with the file name of each page image and
+	/// should return the texture for that page.
+	pub fn from_bmfont_str(
+		source: &str,
+		mut load_page: impl FnMut(&str) -> Result<Texture, LoadBitmapFontError>,
+	) -> Result<Self, LoadBitmapFontError> {
+		let _span = tracy_client::span!();
+		let BMFontData {
+			page_files,
+			glyphs,
+			kerning,
+			line_height,
+			base,
+		} = parse_bmfont(source)?;
+		let pages = page_files
+			.into_iter()
+			.enumerate()
+			.map(|(id, file)| load_page(file.ok_or(LoadBitmapFontError::MissingPage { id })?))
+			.collect::<Result<Vec<_>, _>>()?;
+		if let Some(glyph) = glyphs.values().find(|glyph| glyph.page >= pages.len()) {
+			return Err(LoadBitmapFontError::MissingPage { id: glyph.page });
+		}
+		Ok(Self {
+			pages,
+			glyphs,
+			kerning,
+			line_height,
+			base,
+		})
+	}
+
+	/// Creates a monospaced font from a texture with the characters
+	/// arranged in a grid.
+	///
+	/// `characters` lists the characters in the grid from left to right,
+	/// then top to bottom. Each cell of the grid is `glyph_size` pixels.
+	pub fn from_grid(texture: &Texture, glyph_size: UVec2, characters: &str) -> Self {
+		let _span = tracy_client::span!();
+		let columns = (texture.size().x / glyph_size.x.max(1)).max(1);
+		let glyph_size = glyph_size.as_vec2();
+		let glyphs = characters
+			.chars()
+			.enumerate()
+			.map(|(i, character)| {
+				let column = i as u32 % columns;
+				let row = i as u32 / columns;
+				(
+					character,
+					BitmapGlyph {
+						page: 0,
+						rect: Rect::new(vec2(column as f32, row as f32) * glyph_size, glyph_size),
+						offset: Vec2::ZERO,
+						advance: glyph_size.x,
+					},
+				)
+			})
+			.collect();
+		Self {
+			pages: vec![texture.clone()],
+			glyphs,
+			kerning: HashMap::new(),
+			line_height: glyph_size.y,
+			base: glyph_size.y,
+		}
+	}
+
+	/// Returns the textures containing the glyph images.
+	pub fn pages(&self) -> &[Texture] {
+		&self.pages
+	}
+
+	/// Returns the distance between the tops of consecutive lines
+	/// of text.
+	pub fn line_height(&self) -> f32 {
+		self.line_height
+	}
+
+	/// Returns the distance from the top of a line to the baseline.
+	pub fn base(&self) -> f32 {
+		self.base
+	}
+
+	/// Returns the glyph for the given character, if the font has one.
+	pub fn glyph(&self, character: char) -> Option<&BitmapGlyph> {
+		self.glyphs.get(&character)
+	}
+
+	/// Returns the adjustment to the advance between two characters.
+	pub fn kerning(&self, first: char, second: char) -> f32 {
+		self.kerning.get(&(first, second)).copied().unwrap_or(0.0)
+	}
+}
+
+/// A character in a [`BitmapFont`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitmapGlyph {
+	/// The index of the page texture containing the glyph.
+	pub page: usize,
+	/// The region of the page texture containing the glyph, in pixels.
+	pub rect: Rect,
+	/// The position to draw the glyph image at relative to the cursor.
+	pub offset: Vec2,
+	/// How far to move the cursor after drawing the glyph.
+	pub advance: f32,
+}
+
+/// An error that can occur when loading a [`BitmapFont`].
+#[derive(Debug, Error, Display, From)]
+pub enum LoadBitmapFontError {
+	/// An error reading the font file or a page image.
+	IoError(std::io::Error),
+	/// An error interpreting a page image.
+	ImageError(ImageError),
+	/// The font file is not a valid BMFont text file.
+	InvalidBMFont(InvalidBMFont),
+	/// A page used by the font was not defined.
+	#[display("Page {id} is missing")]
+	#[from(ignore)]
+	MissingPage {
+		/// The ID of the missing page.
+		id: usize,
+	},
+}
+
+impl From<LoadTextureError> for LoadBitmapFontError {
+	fn from(error: LoadTextureError) -> Self {
+		match error {
+			LoadTextureError::IoError(error) => Self::IoError(error),
+			LoadTextureError::ImageError(error) => Self::ImageError(error),
+		}
+	}
+}
+
+/// An error that occurs when a BMFont file can't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+#[display("Invalid BMFont data on line {line}")]
+pub struct InvalidBMFont {
+	/// The line number (starting at 1) the error occurred on.
+	pub line: usize,
+}
+
+/// The contents of a BMFont file before the page images are loaded.
+#[derive(Debug, Clone, PartialEq)]
+struct BMFontData<'a> {
+	/// The file name of each page image, indexed by page ID.
+	page_files: Vec<Option<&'a str>>,
+	glyphs: HashMap<char, BitmapGlyph>,
+	kerning: HashMap<(char, char), f32>,
+	line_height: f32,
+	base: f32,
+}
+
+/// Parses the contents of a BMFont file in the text format.
+fn parse_bmfont(source: &str) -> Result<BMFontData<'_>, InvalidBMFont> {
+	let mut page_files = vec![];
+	let mut glyphs = HashMap::new();
+	let mut kerning = HashMap::new();
+	let mut line_height = 0.0;
+	let mut base = 0.0;
+	for (line_index, line) in source.lines().enumerate() {
+		let invalid = || InvalidBMFont {
+			line: line_index + 1,
+		};
+		if line.trim().is_empty() {
+			continue;
+		}
+		let (tag, attributes) = parse_bmfont_line(line).ok_or_else(invalid)?;
+		let get = |name: &str| attributes.get(name).copied().ok_or_else(invalid);
+		let get_number = |name: &str| get(name)?.parse::<f32>().map_err(|_| invalid());
+		let get_char = |name: &str| {
+			get(name)?
+				.parse::<u32>()
+				.ok()
+				.and_then(char::from_u32)
+				.ok_or_else(invalid)
+		};
+		match tag {
+			"common" => {
+				line_height = get_number("lineHeight")?;
+				base = get_number("base")?;
+			}
+			"page" => {
+				let id = get_number("id")? as usize;
+				let file = get("file")?;
+				if page_files.len() <= id {
+					page_files.resize(id + 1, None);
+				}
+				page_files[id] = Some(file);
+			}
+			"char" => {
+				glyphs.insert(
+					get_char("id")?,
+					BitmapGlyph {
+						page: get_number("page").unwrap_or(0.0) as usize,
+						rect: Rect::new(
+							vec2(get_number("x")?, get_number("y")?),
+							vec2(get_number("width")?, get_number("height")?),
+						),
+						offset: vec2(get_number("xoffset")?, get_number("yoffset")?),
+						advance: get_number("xadvance")?,
+					},
+				);
+			}
+			"kerning" => {
+				kerning.insert(
+					(get_char("first")?, get_char("second")?),
+					get_number("amount")?,
+				);
+			}
+			_ => {}
+		}
+	}
+	Ok(BMFontData {
+		page_files,
+		glyphs,
+		kerning,
+		line_height,
+		base,
+	})
+}
+
+/// Splits a line of a BMFont file into the tag and the `key=value`
+/// attributes. Values may be surrounded by quotes to include spaces.
+///
+/// Returns `None` if the line is malformed.
+fn parse_bmfont_line(line: &str) -> Option<(&str, HashMap<&str, &str>)> {
+	let line = line.trim();
+	let (tag, mut remaining) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+	let mut attributes = HashMap::new();
+	loop {
+		remaining = remaining.trim_start();
+		if remaining.is_empty() {
+			break;
+		}
+		let (key, rest) = remaining.split_once('=')?;
+		let (value, rest) = if let Some(quoted) = rest.strip_prefix('"') {
+			quoted.split_once('"')?
+		} else {
+			rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+		};
+		attributes.insert(key, value);
+		remaining = rest;
+	}
+	Some((tag, attributes))
+}
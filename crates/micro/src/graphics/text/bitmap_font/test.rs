@@ -0,0 +1,116 @@
+use glam::vec2;
+
+use crate::{
+	graphics::text::{BitmapFont, BitmapGlyph, InvalidBMFont, LoadBitmapFontError},
+	math::Rect,
+};
+
+use super::{parse_bmfont, parse_bmfont_line};
+
+const FONT: &str = r#"info face="Comic Sans" size=32 bold=0 italic=0 charset="" unicode=1 padding=0,0,0,0
+common lineHeight=36 base=29 scaleW=256 scaleH=256 pages=2 packed=0
+page id=0 file="font_0.png"
+page id=1 file="font 1.png"
+chars count=2
+char id=65   x=10    y=20    width=18    height=24    xoffset=-1    yoffset=5     xadvance=17    page=0  chnl=15
+char id=86 x=30 y=20 width=19 height=24 xoffset=0 yoffset=5 xadvance=18 page=1 chnl=15
+kernings count=1
+kerning first=65 second=86 amount=-2
+"#;
+
+#[test]
+fn info_common_char_and_kerning_lines() {
+	let font = parse_bmfont(FONT).unwrap();
+	assert_eq!(font.line_height, 36.0);
+	assert_eq!(font.base, 29.0);
+	assert_eq!(
+		font.page_files,
+		vec![Some("font_0.png"), Some("font 1.png")]
+	);
+	assert_eq!(
+		font.glyphs.get(&'A'),
+		Some(&BitmapGlyph {
+			page: 0,
+			rect: Rect::new(vec2(10.0, 20.0), vec2(18.0, 24.0)),
+			offset: vec2(-1.0, 5.0),
+			advance: 17.0,
+		})
+	);
+	assert_eq!(font.glyphs.get(&'V').map(|glyph| glyph.page), Some(1));
+	assert_eq!(font.kerning.get(&('A', 'V')), Some(&-2.0));
+	assert_eq!(font.kerning.get(&('V', 'A')), None);
+}
+
+#[test]
+fn missing_optional_values() {
+	let font =
+		parse_bmfont("char id=97 x=0 y=0 width=8 height=8 xoffset=0 yoffset=0 xadvance=9").unwrap();
+	assert_eq!(font.glyphs.get(&'a').map(|glyph| glyph.page), Some(0));
+	assert_eq!(font.line_height, 0.0);
+	assert!(font.page_files.is_empty());
+}
+
+#[test]
+fn quoted_values() {
+	let (tag, attributes) =
+		parse_bmfont_line(r#"info face="Comic Sans" charset="" size=32"#).unwrap();
+	assert_eq!(tag, "info");
+	assert_eq!(attributes.get("face"), Some(&"Comic Sans"));
+	assert_eq!(attributes.get("charset"), Some(&""));
+	assert_eq!(attributes.get("size"), Some(&"32"));
+	let (tag, attributes) = parse_bmfont_line("  chars   count=2  ").unwrap();
+	assert_eq!(tag, "chars");
+	assert_eq!(attributes.get("count"), Some(&"2"));
+}
+
+#[test]
+fn malformed_input() {
+	// missing `=`
+	assert_eq!(
+		parse_bmfont("info face=Arial\ncommon lineHeight"),
+		Err(InvalidBMFont { line: 2 })
+	);
+	// unterminated quote
+	assert_eq!(
+		parse_bmfont(r#"page id=0 file="font_0.png"#),
+		Err(InvalidBMFont { line: 1 })
+	);
+	// value that isn't a number
+	assert_eq!(
+		parse_bmfont("common lineHeight=tall base=29"),
+		Err(InvalidBMFont { line: 1 })
+	);
+	// missing required value
+	assert_eq!(
+		parse_bmfont("\nkerning first=65 second=86"),
+		Err(InvalidBMFont { line: 2 })
+	);
+	// character ID that isn't a valid character
+	assert_eq!(
+		parse_bmfont("kerning first=55296 second=86 amount=1"),
+		Err(InvalidBMFont { line: 1 })
+	);
+}
+
+#[test]
+fn missing_pages() {
+	let load_page =
+		|_: &str| -> Result<_, LoadBitmapFontError> { panic!("no pages should be loaded") };
+	assert!(matches!(
+		BitmapFont::from_bmfont_str("page id=1 file=font_1.png", load_page),
+		Err(LoadBitmapFontError::MissingPage { id: 0 })
+	));
+	assert!(matches!(
+		BitmapFont::from_bmfont_str(
+			"char id=65 x=0 y=0 width=8 height=8 xoffset=0 yoffset=0 xadvance=9 page=0",
+			load_page
+		),
+		Err(LoadBitmapFontError::MissingPage { id: 0 })
+	));
+	assert!(matches!(
+		BitmapFont::from_bmfont_str("common lineHeight=tall base=29", load_page),
+		Err(LoadBitmapFontError::InvalidBMFont(InvalidBMFont {
+			line: 1
+		}))
+	));
+}
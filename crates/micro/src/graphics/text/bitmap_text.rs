@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use glam::{Mat4, vec2};
+use palette::LinSrgba;
+use tracing::warn;
+
+use crate::{
+	Context,
+	color::ColorConstants,
+	graphics::{
+		BlendMode, IntoIndexRange,
+		sprite_batch::{MultiTextureSpriteBatch, SpriteParams},
+		texture::Texture,
+	},
+	math::Rect,
+	standard_draw_param_methods,
+};
+
+use super::BitmapFont;
+
+/// A block of text drawn with a [`BitmapFont`].
+///
+/// Unlike [`Text`](super::Text), the glyphs are drawn from the font's
+/// images as-is, which makes this a good fit for pixel art fonts.
+#[derive(Debug, Clone)]
+pub struct BitmapText {
+	inner: Arc<BitmapTextInner>,
+
+	// params
+	/// The transform to use when drawing this text.
+	pub transform: Mat4,
+	/// The blend color to use when drawing this text.
+	pub color: LinSrgba,
+	/// The blend mode to use when drawing this text.
+	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this text.
+	pub palette: Option<Texture>,
+	/// The min and max glyph index to use when drawing this text.
+	///
+	/// Setting this results in a portion of the text being drawn.
+	/// When `None`, all the glyphs are drawn.
+	pub range: Option<(u32, u32)>,
+}
+
+impl BitmapText {
+	/// Lays out `text` using the given [`BitmapFont`].
+	///
+	/// Characters the font doesn't have are skipped.
+	pub fn new(ctx: &Context, font: &BitmapFont, text: &str, settings: BitmapTextSettings) -> Self {
+		let _span = tracy_client::span!();
+		let num_chars = text.chars().count();
+		let mut sprite_batch = MultiTextureSpriteBatch::new(ctx, num_chars);
+		let mut glyph_bounds: Option<Rect> = None;
+		let mut cursor = vec2(0.0, 0.0);
+		let mut previous_character = None;
+		for character in text.chars() {
+			if character == '\n' {
+				cursor.x = 0.0;
+				cursor.y += font.line_height() * settings.line_height;
+				previous_character = None;
+				continue;
+			}
+			let Some(glyph) = font.glyph(character) else {
+				continue;
+			};
+			if let Some(previous_character) = previous_character {
+				cursor.x += font.kerning(previous_character, character);
+			}
+			previous_character = Some(character);
+			let position = cursor + glyph.offset;
+			cursor.x += glyph.advance + settings.letter_spacing;
+			if glyph.rect.size.x <= 0.0 || glyph.rect.size.y <= 0.0 {
+				continue;
+			}
+			if let Err(err) = sprite_batch.add_region(
+				ctx,
+				&font.pages()[glyph.page],
+				glyph.rect,
+				SpriteParams::new().translated(position),
+			) {
+				warn!("could not add glyph to bitmap text: {err}");
+				continue;
+			}
+			let individual_glyph_bounds = Rect::new(position, glyph.rect.size);
+			if let Some(glyph_bounds) = &mut glyph_bounds {
+				*glyph_bounds = glyph_bounds.union(individual_glyph_bounds);
+			} else {
+				glyph_bounds = Some(individual_glyph_bounds);
+			}
+		}
+		Self {
+			inner: Arc::new(BitmapTextInner {
+				num_glyphs: sprite_batch.len() as u32,
+				sprite_batch,
+				glyph_bounds,
+			}),
+			transform: Mat4::IDENTITY,
+			color: LinSrgba::WHITE,
+			blend_mode: BlendMode::default(),
+			palette: None,
+			range: None,
+		}
+	}
+
+	standard_draw_param_methods!();
+
+	/// Sets the range of glyph indices used for drawing.
+	///
+	/// Setting this results in a portion of the text being drawn.
+	/// When `None`, all the glyphs are drawn.
+	pub fn range(&self, range: impl IntoIndexRange) -> Self {
+		let mut new = self.clone();
+		new.range = range.into_index_range(self.inner.num_glyphs);
+		new
+	}
+
+	/// Returns the number of glyphs this text contains.
+	pub fn num_glyphs(&self) -> u32 {
+		self.inner.num_glyphs
+	}
+
+	/// Returns a rectangle that surrounds all the glyphs in the text.
+	///
+	/// Returns `None` if there's no glyphs in this [`BitmapText`].
+	pub fn glyph_bounds(&self) -> Option<Rect> {
+		self.inner.glyph_bounds
+	}
+
+	/// Draws the text.
+	pub fn draw(&self, ctx: &mut Context) {
+		let _span = tracy_client::span!();
+		self.inner
+			.sprite_batch
+			.transformed(self.transform)
+			.color(self.color)
+			.blend_mode(self.blend_mode)
+			.palette(self.palette.as_ref())
+			.range(self.range)
+			.draw(ctx);
+	}
+}
+
+/// Settings for laying out a [`BitmapText`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct BitmapTextSettings {
+	/// Extra space between each character, in pixels.
+	pub letter_spacing: f32,
+	/// The distance between lines, relative to the font's line height.
+	pub line_height: f32,
+}
+
+impl Default for BitmapTextSettings {
+	fn default() -> Self {
+		Self {
+			letter_spacing: 0.0,
+			line_height: 1.0,
+		}
+	}
+}
+
+#[derive(Debug)]
+struct BitmapTextInner {
+	sprite_batch: MultiTextureSpriteBatch,
+	glyph_bounds: Option<Rect>,
+	num_glyphs: u32,
+}
@@ -0,0 +1,43 @@
+use glam::Vec2;
+use palette::LinSrgba;
+
+use crate::math::{IRect, Rect};
+
+/// A single glyph in a [`Text`](super::Text).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+	/// The position of the top-left corner of the glyph, relative to
+	/// the text.
+	pub position: Vec2,
+	/// The index of the glyph atlas page containing the glyph.
+	pub page: usize,
+	/// The region of the glyph atlas page containing the glyph.
+	pub texture_rect: IRect,
+	/// How much the glyph's region of the atlas is scaled when drawing.
+	///
+	/// This is `1.0` except for signed distance field text.
+	pub scale: f32,
+	/// The index of the character (not the byte) in the source text that
+	/// this glyph was shaped from.
+	///
+	/// Multiple glyphs can share a character index, and ligatures can
+	/// cover multiple characters.
+	pub char_index: usize,
+	/// The index of the line the glyph is on, including lines created by
+	/// wrapping.
+	pub line: usize,
+	/// The color of the glyph, as determined by the text span it came from.
+	pub color: LinSrgba,
+}
+
+impl Glyph {
+	/// Returns the size of the glyph.
+	pub fn size(&self) -> Vec2 {
+		self.texture_rect.size.as_vec2() * self.scale
+	}
+
+	/// Returns the rectangle the glyph occupies, relative to the text.
+	pub fn bounds(&self) -> Rect {
+		Rect::new(self.position, self.size())
+	}
+}
@@ -0,0 +1,59 @@
+use crate::egui;
+
+/// Statistics about the glyph atlas used for drawing [`Text`](super::Text).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphAtlasStats {
+	/// The width and height of each page of the atlas.
+	pub page_sizes: Vec<u32>,
+	/// The number of glyphs currently stored in the atlas.
+	pub num_glyphs: usize,
+	/// The number of glyphs that are used by at least one
+	/// [`Text`](super::Text) and can't be evicted.
+	pub num_glyphs_in_use: usize,
+	/// The number of pixels of the atlas that are allocated to glyphs.
+	pub used_area: u64,
+	/// The total number of pixels in all the pages of the atlas.
+	pub total_area: u64,
+	/// The number of glyphs that have been rasterized since the game started.
+	pub num_rasterizations: u64,
+	/// The number of glyphs that have been evicted from the atlas to make
+	/// room for other glyphs since the game started.
+	pub num_evictions: u64,
+}
+
+impl GlyphAtlasStats {
+	/// Shows the stats in an egui [`Ui`](egui::Ui).
+	pub fn ui(&self, ui: &mut egui::Ui) {
+		egui::Grid::new("glyph_atlas_stats")
+			.num_columns(2)
+			.show(ui, |ui| {
+				ui.label("Pages");
+				ui.label(
+					self.page_sizes
+						.iter()
+						.map(|size| format!("{size}x{size}"))
+						.collect::<Vec<_>>()
+						.join(", "),
+				);
+				ui.end_row();
+				ui.label("Glyphs");
+				ui.label(format!(
+					"{} ({} in use)",
+					self.num_glyphs, self.num_glyphs_in_use
+				));
+				ui.end_row();
+				ui.label("Used area");
+				ui.label(format!(
+					"{:.1}%",
+					self.used_area as f64 / self.total_area.max(1) as f64 * 100.0
+				));
+				ui.end_row();
+				ui.label("Rasterizations");
+				ui.label(self.num_rasterizations.to_string());
+				ui.end_row();
+				ui.label("Evictions");
+				ui.label(self.num_evictions.to_string());
+				ui.end_row();
+			});
+	}
+}
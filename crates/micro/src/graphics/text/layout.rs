@@ -0,0 +1,100 @@
+use std::ops::Range;
+
+use cosmic_text::{Buffer, Cursor};
+use glam::vec2;
+
+use crate::math::Rect;
+
+/// The shaped and laid out text a [`Text`](super::Text) was created from.
+///
+/// Positions are relative to the text, and character indices count
+/// characters (not bytes) across all lines, including line endings,
+/// matching [`Glyph::char_index`](super::Glyph::char_index).
+#[derive(Debug)]
+pub(crate) struct TextLayout {
+	buffer: Buffer,
+	line_start_char_indices: Vec<usize>,
+}
+
+impl TextLayout {
+	pub(crate) fn new(buffer: Buffer) -> Self {
+		let mut line_start_char_indices = Vec::with_capacity(buffer.lines.len());
+		let mut line_start_char_index = 0;
+		for line in &buffer.lines {
+			line_start_char_indices.push(line_start_char_index);
+			line_start_char_index += line.text().chars().count() + line.ending().as_str().len();
+		}
+		Self {
+			buffer,
+			line_start_char_indices,
+		}
+	}
+
+	pub(crate) fn buffer(&self) -> &Buffer {
+		&self.buffer
+	}
+
+	pub(crate) fn line_start_char_index(&self, line: usize) -> usize {
+		self.line_start_char_indices[line]
+	}
+
+	pub(crate) fn hit_test(&self, x: f32, y: f32) -> Option<usize> {
+		self.buffer
+			.hit(x, y)
+			.map(|cursor| self.cursor_to_char_index(cursor))
+	}
+
+	pub(crate) fn cursor_rect(&self, char_index: usize) -> Option<Rect> {
+		let cursor = self.char_index_to_cursor(char_index)?;
+		self.buffer
+			.layout_runs()
+			.filter(|run| run.line_i == cursor.line)
+			.find_map(|run| {
+				run.cursor_position(&cursor)
+					.map(|x| Rect::new(vec2(x, run.line_top), vec2(0.0, run.line_height)))
+			})
+	}
+
+	pub(crate) fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+		let (Some(start), Some(end)) = (
+			self.char_index_to_cursor(range.start),
+			self.char_index_to_cursor(range.end),
+		) else {
+			return vec![];
+		};
+		if start >= end {
+			return vec![];
+		}
+		self.buffer
+			.layout_runs()
+			.filter(|run| run.line_i >= start.line && run.line_i <= end.line)
+			.flat_map(|run| {
+				run.highlight(start, end).map(move |(x, width)| {
+					Rect::new(vec2(x, run.line_top), vec2(width, run.line_height))
+				})
+			})
+			.collect()
+	}
+
+	fn cursor_to_char_index(&self, cursor: Cursor) -> usize {
+		let text = self.buffer.lines[cursor.line].text();
+		self.line_start_char_indices[cursor.line]
+			+ text[..cursor.index.min(text.len())].chars().count()
+	}
+
+	/// Converts a character index to a cursor. Indices that point to a line
+	/// ending are placed at the end of the line, and indices past the end
+	/// of the text are placed at the end of the last line.
+	fn char_index_to_cursor(&self, char_index: usize) -> Option<Cursor> {
+		let line = self
+			.line_start_char_indices
+			.partition_point(|&start| start <= char_index)
+			.checked_sub(1)?;
+		let text = self.buffer.lines[line].text();
+		let index = text
+			.char_indices()
+			.nth(char_index - self.line_start_char_indices[line])
+			.map_or(text.len(), |(index, _)| index);
+		Some(Cursor::new(line, index))
+	}
+}
@@ -0,0 +1,399 @@
+#[cfg(test)]
+mod test;
+
+use derive_more::derive::{Display, Error};
+use palette::{LinSrgba, Srgb, Srgba};
+
+use crate::{Context, color::ColorConstants};
+
+use super::{
+	Text, TextBuilder, TextEffects, TextHorizontalSizing, TextStretch, TextStyle, TextWeight,
+};
+
+/// Builds a [`Text`] out of multiple spans, each of which can have its own
+/// font, size, weight, style, and color.
+///
+/// Any setting a [`TextSpan`] doesn't specify falls back to the setting
+/// on the builder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RichTextBuilder {
+	/// The default font family.
+	pub font_family: String,
+	/// The default font size.
+	pub font_size: f32,
+	/// The height of each line, relative to the font size.
+	pub line_height: f32,
+	/// The default font stretch.
+	pub stretch: TextStretch,
+	/// The default font style.
+	pub style: TextStyle,
+	/// The default font weight.
+	pub weight: TextWeight,
+	/// The default text color.
+	pub color: LinSrgba,
+	/// Extra space between each character, relative to the font size.
+	pub letter_spacing: Option<f32>,
+	/// How the width of the text is determined.
+	pub horizontal_sizing: TextHorizontalSizing,
+	/// The spans that make up the text, in order.
+	pub spans: Vec<TextSpan>,
+	/// Whether the text uses signed distance field glyphs.
+	pub sdf: bool,
+	/// The outline, shadow, and glow to draw around the glyphs.
+	pub effects: TextEffects,
+}
+
+impl RichTextBuilder {
+	/// Creates a new [`RichTextBuilder`] with no spans.
+	pub fn new(font_family: impl Into<String>) -> Self {
+		Self {
+			font_family: font_family.into(),
+			font_size: 16.0,
+			line_height: 1.0,
+			stretch: TextStretch::Normal,
+			style: TextStyle::Normal,
+			weight: TextWeight::NORMAL,
+			color: LinSrgba::WHITE,
+			letter_spacing: None,
+			horizontal_sizing: TextHorizontalSizing::default(),
+			spans: vec![],
+			sdf: false,
+			effects: TextEffects::default(),
+		}
+	}
+
+	/// Sets the default font family.
+	pub fn font_family(self, font_family: impl Into<String>) -> Self {
+		Self {
+			font_family: font_family.into(),
+			..self
+		}
+	}
+
+	/// Sets the default font size.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { font_size, ..self }
+	}
+
+	/// Sets the height of each line, relative to the font size.
+	pub fn line_height(self, line_height: f32) -> Self {
+		Self {
+			line_height,
+			..self
+		}
+	}
+
+	/// Sets the default font stretch.
+	pub fn stretch(self, stretch: TextStretch) -> Self {
+		Self { stretch, ..self }
+	}
+
+	/// Sets the default font style.
+	pub fn style(self, style: TextStyle) -> Self {
+		Self { style, ..self }
+	}
+
+	/// Sets the default font weight.
+	pub fn weight(self, weight: TextWeight) -> Self {
+		Self { weight, ..self }
+	}
+
+	/// Sets the default text color.
+	pub fn color(self, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			color: color.into(),
+			..self
+		}
+	}
+
+	/// Sets the extra space between each character, relative to the font size.
+	pub fn letter_spacing(self, letter_spacing: impl Into<Option<f32>>) -> Self {
+		Self {
+			letter_spacing: letter_spacing.into(),
+			..self
+		}
+	}
+
+	/// Sets how the width of the text is determined.
+	pub fn horizontal_sizing(self, horizontal_sizing: TextHorizontalSizing) -> Self {
+		Self {
+			horizontal_sizing,
+			..self
+		}
+	}
+
+	/// Sets whether the text uses signed distance field glyphs.
+	///
+	/// See [`TextBuilder::sdf`] for details.
+	pub fn sdf(self, sdf: bool) -> Self {
+		Self { sdf, ..self }
+	}
+
+	/// Sets the outline, shadow, and glow to draw around the glyphs.
+	///
+	/// See [`TextBuilder::effects`] for details.
+	pub fn effects(self, effects: TextEffects) -> Self {
+		Self { effects, ..self }
+	}
+
+	/// Adds a span to the end of the text.
+	pub fn span(mut self, span: TextSpan) -> Self {
+		self.spans.push(span);
+		self
+	}
+
+	/// Adds a span with the builder's default settings to the end
+	/// of the text.
+	pub fn text(self, text: impl Into<String>) -> Self {
+		self.span(TextSpan::new(text))
+	}
+
+	/// Parses text with inline markup and adds the resulting spans to the
+	/// end of the text.
+	///
+	/// See [`parse_markup`] for the supported tags.
+	pub fn markup(mut self, markup: &str) -> Result<Self, ParseMarkupError> {
+		self.spans.extend(parse_markup(markup)?);
+		Ok(self)
+	}
+
+	/// Lays out the text and creates a [`Text`].
+	pub fn build(self, ctx: &mut Context) -> Text {
+		Text::new_rich(ctx, self)
+	}
+}
+
+impl From<TextBuilder> for RichTextBuilder {
+	fn from(builder: TextBuilder) -> Self {
+		Self {
+			font_family: builder.font_family,
+			font_size: builder.font_size,
+			line_height: builder.line_height,
+			stretch: builder.stretch,
+			style: builder.style,
+			weight: builder.weight,
+			color: LinSrgba::WHITE,
+			letter_spacing: builder.letter_spacing,
+			horizontal_sizing: builder.horizontal_sizing,
+			spans: vec![TextSpan::new(builder.text)],
+			sdf: builder.sdf,
+			effects: builder.effects,
+		}
+	}
+}
+
+/// A piece of text with its own settings, used with a [`RichTextBuilder`].
+///
+/// Settings that are `None` use the value from the [`RichTextBuilder`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextSpan {
+	/// The text in this span.
+	pub text: String,
+	/// The font family of this span.
+	pub font_family: Option<String>,
+	/// The font size of this span.
+	pub font_size: Option<f32>,
+	/// The font stretch of this span.
+	pub stretch: Option<TextStretch>,
+	/// The font style of this span.
+	pub style: Option<TextStyle>,
+	/// The font weight of this span.
+	pub weight: Option<TextWeight>,
+	/// The color of this span.
+	pub color: Option<LinSrgba>,
+}
+
+impl TextSpan {
+	/// Creates a new [`TextSpan`] that uses the builder's settings.
+	pub fn new(text: impl Into<String>) -> Self {
+		Self {
+			text: text.into(),
+			..Default::default()
+		}
+	}
+
+	/// Sets the font family of this span.
+	pub fn font_family(self, font_family: impl Into<String>) -> Self {
+		Self {
+			font_family: Some(font_family.into()),
+			..self
+		}
+	}
+
+	/// Sets the font size of this span.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self {
+			font_size: Some(font_size),
+			..self
+		}
+	}
+
+	/// Sets the font stretch of this span.
+	pub fn stretch(self, stretch: TextStretch) -> Self {
+		Self {
+			stretch: Some(stretch),
+			..self
+		}
+	}
+
+	/// Sets the font style of this span.
+	pub fn style(self, style: TextStyle) -> Self {
+		Self {
+			style: Some(style),
+			..self
+		}
+	}
+
+	/// Sets the font weight of this span.
+	pub fn weight(self, weight: TextWeight) -> Self {
+		Self {
+			weight: Some(weight),
+			..self
+		}
+	}
+
+	/// Sets the color of this span.
+	pub fn color(self, color: impl Into<LinSrgba>) -> Self {
+		Self {
+			color: Some(color.into()),
+			..self
+		}
+	}
+
+	fn with_text(&self, text: String) -> Self {
+		Self {
+			text,
+			..self.clone()
+		}
+	}
+}
+
+/// Splits text with inline markup into [`TextSpan`]s.
+///
+/// The supported tags are:
+/// - `[b]...[/b]` - bold
+/// - `[i]...[/i]` - italic
+/// - `[color=red]...[/color]` - text color, either as a name (`white`,
+///   `black`, `red`, `green`, or `blue`) or an sRGB hex code
+///   (`#ff8000`, `#ff800080`)
+/// - `[size=24]...[/size]` - font size
+/// - `[font=Name]...[/font]` - font family
+///
+/// Tags can be nested, and every closing tag must match the most recently
+/// opened tag. Tags that are still open at the end of the text are closed
+/// automatically. Use `[[` to write a literal `[`.
+pub fn parse_markup(markup: &str) -> Result<Vec<TextSpan>, ParseMarkupError> {
+	let _span = tracy_client::span!();
+	let mut spans = vec![];
+	let mut open_tags: Vec<(String, TextSpan)> = vec![];
+	let mut current = TextSpan::default();
+	let mut text = String::new();
+	let mut remaining = markup;
+	while let Some(bracket_index) = remaining.find('[') {
+		text.push_str(&remaining[..bracket_index]);
+		remaining = &remaining[bracket_index + 1..];
+		if let Some(after_escape) = remaining.strip_prefix('[') {
+			text.push('[');
+			remaining = after_escape;
+			continue;
+		}
+		let position = markup.len() - remaining.len() - 1;
+		let Some(closing_bracket_index) = remaining.find(']') else {
+			return Err(ParseMarkupError::UnclosedBracket { position });
+		};
+		let tag = &remaining[..closing_bracket_index];
+		remaining = &remaining[closing_bracket_index + 1..];
+		if !text.is_empty() {
+			spans.push(current.with_text(std::mem::take(&mut text)));
+		}
+		if let Some(name) = tag.strip_prefix('/') {
+			match open_tags.pop() {
+				Some((open_name, previous)) if open_name == name => current = previous,
+				_ => {
+					return Err(ParseMarkupError::MismatchedClosingTag {
+						tag: name.to_string(),
+						position,
+					});
+				}
+			}
+			continue;
+		}
+		let (name, value) = match tag.split_once('=') {
+			Some((name, value)) => (name, Some(value)),
+			None => (tag, None),
+		};
+		let previous = current.clone();
+		current = apply_tag(current, name, value).ok_or_else(|| ParseMarkupError::InvalidTag {
+			tag: tag.to_string(),
+			position,
+		})?;
+		open_tags.push((name.to_string(), previous));
+	}
+	text.push_str(remaining);
+	if !text.is_empty() {
+		spans.push(current.with_text(text));
+	}
+	Ok(spans)
+}
+
+/// An error that can occur when parsing text markup.
+#[derive(Debug, Clone, PartialEq, Eq, Error, Display)]
+pub enum ParseMarkupError {
+	/// A `[` was not followed by a `]`.
+	#[display("Unclosed bracket at byte {position}")]
+	UnclosedBracket {
+		/// The byte index of the `[`.
+		position: usize,
+	},
+	/// A tag had an unknown name or an invalid value.
+	#[display("Invalid tag '{tag}' at byte {position}")]
+	InvalidTag {
+		/// The contents of the tag.
+		tag: String,
+		/// The byte index of the tag's `[`.
+		position: usize,
+	},
+	/// A closing tag didn't match the most recently opened tag.
+	#[display("Closing tag '[/{tag}]' at byte {position} does not match an open tag")]
+	MismatchedClosingTag {
+		/// The name of the closing tag.
+		tag: String,
+		/// The byte index of the tag's `[`.
+		position: usize,
+	},
+}
+
+fn apply_tag(span: TextSpan, name: &str, value: Option<&str>) -> Option<TextSpan> {
+	match (name, value) {
+		("b", None) => Some(span.weight(TextWeight::BOLD)),
+		("i", None) => Some(span.style(TextStyle::Italic)),
+		("color", Some(value)) => parse_color(value).map(|color| span.color(color)),
+		("size", Some(value)) => value.parse().ok().map(|size| span.font_size(size)),
+		("font", Some(value)) if !value.is_empty() => Some(span.font_family(value)),
+		_ => None,
+	}
+}
+
+fn parse_color(value: &str) -> Option<LinSrgba> {
+	match value {
+		"white" => Some(LinSrgba::WHITE),
+		"black" => Some(LinSrgba::BLACK),
+		"red" => Some(LinSrgba::RED),
+		"green" => Some(LinSrgba::GREEN),
+		"blue" => Some(LinSrgba::BLUE),
+		_ => {
+			let hex = value.strip_prefix('#')?;
+			match hex.len() {
+				3 | 6 => value
+					.parse::<Srgb<u8>>()
+					.ok()
+					.map(|color| color.into_linear().into()),
+				4 | 8 => value
+					.parse::<Srgba<u8>>()
+					.ok()
+					.map(|color| color.into_linear()),
+				_ => None,
+			}
+		}
+	}
+}
@@ -0,0 +1,85 @@
+use palette::LinSrgba;
+
+use crate::{
+	color::ColorConstants,
+	graphics::text::{ParseMarkupError, TextSpan, TextStyle, TextWeight, parse_markup},
+};
+
+#[test]
+fn plain_text() {
+	assert_eq!(parse_markup("hello"), Ok(vec![TextSpan::new("hello")]));
+	assert_eq!(parse_markup(""), Ok(vec![]));
+}
+
+#[test]
+fn nested_tags() {
+	assert_eq!(
+		parse_markup("a [color=red]b [b]c[/b][/color] d"),
+		Ok(vec![
+			TextSpan::new("a "),
+			TextSpan::new("b ").color(LinSrgba::RED),
+			TextSpan::new("c")
+				.color(LinSrgba::RED)
+				.weight(TextWeight::BOLD),
+			TextSpan::new(" d"),
+		])
+	);
+}
+
+#[test]
+fn unclosed_tags() {
+	assert_eq!(
+		parse_markup("[i][size=24]a"),
+		Ok(vec![
+			TextSpan::new("a").style(TextStyle::Italic).font_size(24.0)
+		])
+	);
+}
+
+#[test]
+fn escaped_bracket() {
+	assert_eq!(
+		parse_markup("[[b] [b]x"),
+		Ok(vec![
+			TextSpan::new("[b] "),
+			TextSpan::new("x").weight(TextWeight::BOLD)
+		])
+	);
+}
+
+#[test]
+fn hex_color() {
+	assert_eq!(
+		parse_markup("[color=#fff]a"),
+		Ok(vec![TextSpan::new("a").color(LinSrgba::WHITE)])
+	);
+}
+
+#[test]
+fn errors() {
+	assert_eq!(
+		parse_markup("a [b"),
+		Err(ParseMarkupError::UnclosedBracket { position: 2 })
+	);
+	assert_eq!(
+		parse_markup("[wave]a"),
+		Err(ParseMarkupError::InvalidTag {
+			tag: "wave".into(),
+			position: 0
+		})
+	);
+	assert_eq!(
+		parse_markup("[color=nope]a"),
+		Err(ParseMarkupError::InvalidTag {
+			tag: "color=nope".into(),
+			position: 0
+		})
+	);
+	assert_eq!(
+		parse_markup("[b]a[/i]"),
+		Err(ParseMarkupError::MismatchedClosingTag {
+			tag: "i".into(),
+			position: 4
+		})
+	);
+}
@@ -0,0 +1,118 @@
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+use palette::LinSrgba;
+
+use crate::{color::ColorConstants, text::SDF_SPREAD};
+
+/// Outlines, shadows, and glows drawn around the glyphs of a
+/// [`Text`](super::Text).
+///
+/// For text built with [`TextBuilder::sdf`](super::TextBuilder::sdf), all
+/// of the effects are drawn in a single pass using the text's signed
+/// distance field. Sizes are in the text's own pixels (before any transform
+/// is applied). Outlines and glows can only extend about a sixth of the
+/// font size past the edge of each glyph.
+///
+/// Other text only draws the shadow, as a copy of the text tinted with the
+/// shadow's color.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct TextEffects {
+	/// An outline drawn around each glyph.
+	pub outline: Option<TextOutline>,
+	/// A copy of each glyph drawn behind it at an offset.
+	pub shadow: Option<TextShadow>,
+	/// A soft glow that fades out from the edge of each glyph.
+	pub glow: Option<TextGlow>,
+	/// How much to blur the edges of each glyph and its outline.
+	/// `0.0` gives the sharpest edges possible.
+	pub softness: f32,
+}
+
+impl TextEffects {
+	/// Returns `true` if any of the effects are enabled.
+	pub fn is_any(&self) -> bool {
+		self.outline.is_some()
+			|| self.shadow.is_some()
+			|| self.glow.is_some()
+			|| self.softness > 0.0
+	}
+
+	pub(crate) fn to_raw(self) -> RawTextEffects {
+		let outline = self.outline.unwrap_or(TextOutline {
+			width: 0.0,
+			color: LinSrgba::BLACK,
+		});
+		let shadow = self.shadow.unwrap_or(TextShadow {
+			offset: Vec2::ZERO,
+			color: LinSrgba::new(0.0, 0.0, 0.0, 0.0),
+			softness: 0.0,
+		});
+		let glow = self.glow.unwrap_or(TextGlow {
+			radius: 0.0,
+			color: LinSrgba::BLACK,
+		});
+		RawTextEffects {
+			outline_color: outline.color,
+			shadow_color: shadow.color,
+			glow_color: glow.color,
+			shadow_offset: shadow.offset,
+			outline_width: outline.width.max(0.0),
+			shadow_softness: shadow.softness.max(0.0),
+			glow_radius: glow.radius.max(0.0),
+			softness: self.softness.max(0.0),
+			spread: SDF_SPREAD as f32,
+			_padding: 0.0,
+		}
+	}
+}
+
+/// An outline drawn around each glyph of a [`Text`](super::Text).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextOutline {
+	/// The width of the outline.
+	pub width: f32,
+	/// The color of the outline.
+	pub color: LinSrgba,
+}
+
+/// A copy of each glyph of a [`Text`](super::Text) drawn behind it at
+/// an offset.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextShadow {
+	/// How far the shadow is moved from the glyph.
+	pub offset: Vec2,
+	/// The color of the shadow.
+	pub color: LinSrgba,
+	/// How much to blur the shadow. Only used for signed distance field
+	/// text.
+	pub softness: f32,
+}
+
+/// A soft glow around each glyph of a [`Text`](super::Text).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextGlow {
+	/// How far the glow extends from the edge of the glyph.
+	pub radius: f32,
+	/// The color of the glow at the edge of the glyph.
+	pub color: LinSrgba,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct RawTextEffects {
+	outline_color: LinSrgba,
+	shadow_color: LinSrgba,
+	glow_color: LinSrgba,
+	shadow_offset: Vec2,
+	outline_width: f32,
+	shadow_softness: f32,
+	glow_radius: f32,
+	softness: f32,
+	spread: f32,
+	_padding: f32,
+}
@@ -1,5 +1,8 @@
 //! Types related to drawing images.
 
+mod indexed;
+
+pub use indexed::*;
 pub use wgpu::{AddressMode, FilterMode, SamplerBorderColor, TextureViewDimension};
 
 use std::{collections::HashSet, path::Path};
@@ -7,7 +10,7 @@ use std::{collections::HashSet, path::Path};
 use derive_more::{Display, Error, From};
 use glam::{Mat4, UVec2, Vec2};
 use image::{ImageBuffer, ImageError};
-use palette::LinSrgba;
+use palette::{LinSrgba, Srgba};
 use wgpu::{
 	Device, Extent3d, Origin3d, Queue, Sampler, SamplerDescriptor, TexelCopyBufferLayout,
 	TexelCopyTextureInfo, TextureAspect, TextureDescriptor, TextureDimension, TextureFormat,
@@ -43,6 +46,8 @@ pub struct Texture {
 	pub color: LinSrgba,
 	/// The blend mode to use when drawing this texture.
 	pub blend_mode: BlendMode,
+	/// The palette to look up colors in when drawing this texture.
+	pub palette: Option<Box<Texture>>,
 }
 
 impl Texture {
@@ -180,6 +185,54 @@ impl Texture {
 		)
 	}
 
+	/// Creates a new texture that stores palette indices instead of colors.
+	///
+	/// Draw the texture with a [`palette`](Self::palette) to look up the
+	/// color for each index. [`palette_indices`] can be used to convert
+	/// an RGBA image to indices. Since the indices can't be blended
+	/// with each other, indexed textures should be filtered with
+	/// [`FilterMode::Nearest`].
+	pub fn from_indices(
+		ctx: &Context,
+		indices: &ImageBuffer<image::Luma<u8>, Vec<u8>>,
+		settings: TextureSettings,
+	) -> Self {
+		let _span = tracy_client::span!();
+		Self::new(
+			&ctx.graphics.device,
+			&ctx.graphics.queue,
+			UVec2::new(indices.width(), indices.height()),
+			1,
+			[indices.as_raw().as_slice()],
+			settings,
+			InternalTextureSettings {
+				format: TextureFormat::R8Unorm,
+				..Default::default()
+			},
+		)
+	}
+
+	/// Creates a palette texture with a single row of colors, which can be
+	/// used to draw textures created with [`Texture::from_indices`].
+	///
+	/// Any texture can be used as a palette. To keep multiple palettes
+	/// in one texture, put each palette in its own row and choose a row
+	/// with [`region`](Self::region). Indices past the end of the region
+	/// use its last color.
+	pub fn from_palette(ctx: &Context, colors: &[Srgba<u8>], settings: TextureSettings) -> Self {
+		let _span = tracy_client::span!();
+		assert!(!colors.is_empty(), "must provide at least one color");
+		Self::new(
+			&ctx.graphics.device,
+			&ctx.graphics.queue,
+			UVec2::new(colors.len() as u32, 1),
+			1,
+			[palette::cast::into_component_slice(colors)],
+			settings,
+			InternalTextureSettings::default(),
+		)
+	}
+
 	/// Returns a new texture with the specified `size` and with data copied
 	/// over from the previous texture.
 	pub fn resized(&self, ctx: &Context, size: UVec2) -> Self {
@@ -244,6 +297,7 @@ impl Texture {
 		.transformed(self.transform)
 		.color(self.color)
 		.blend_mode(self.blend_mode)
+		.palette(self.palette.as_deref())
 		.draw(ctx)
 	}
 
@@ -289,7 +343,9 @@ impl Texture {
 				layer,
 				TexelCopyBufferLayout {
 					offset: 0,
-					bytes_per_row: Some(4 * size.x),
+					bytes_per_row: Some(
+						internal_settings.format.block_copy_size(None).unwrap_or(4) * size.x,
+					),
 					rows_per_image: Some(size.y),
 				},
 				Extent3d {
@@ -326,6 +382,7 @@ impl Texture {
 			transform: Mat4::IDENTITY,
 			color: LinSrgba::WHITE,
 			blend_mode: BlendMode::default(),
+			palette: None,
 		}
 	}
 
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod test;
+
+use std::collections::HashMap;
+
+use derive_more::{Display, Error};
+use glam::UVec2;
+use image::ImageBuffer;
+use palette::Srgba;
+
+/// Converts an RGBA image to an image of palette indices, which can be
+/// uploaded with [`Texture::from_indices`](super::Texture::from_indices).
+///
+/// Each pixel is replaced with the index of the first matching color
+/// in `palette`. Fully transparent pixels match the first fully
+/// transparent color in the palette, regardless of their RGB values.
+pub fn palette_indices(
+	image: &ImageBuffer<image::Rgba<u8>, Vec<u8>>,
+	palette: &[Srgba<u8>],
+) -> Result<ImageBuffer<image::Luma<u8>, Vec<u8>>, PaletteIndexError> {
+	let _span = tracy_client::span!();
+	if palette.len() > 256 {
+		return Err(PaletteIndexError::TooManyColors {
+			num_colors: palette.len(),
+		});
+	}
+	let mut indices = HashMap::new();
+	for (index, color) in palette.iter().enumerate() {
+		indices
+			.entry(normalize_transparent((*color).into()))
+			.or_insert(index as u8);
+	}
+	let mut output = ImageBuffer::new(image.width(), image.height());
+	for (x, y, pixel) in image.enumerate_pixels() {
+		let index = indices
+			.get(&normalize_transparent(pixel.0))
+			.copied()
+			.ok_or(PaletteIndexError::ColorNotInPalette {
+				color: pixel.0.into(),
+				position: UVec2::new(x, y),
+			})?;
+		output.put_pixel(x, y, image::Luma([index]));
+	}
+	Ok(output)
+}
+
+/// An error that can occur when converting an image to palette indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error, Display)]
+pub enum PaletteIndexError {
+	/// The palette has more colors than can be referred to by an 8-bit
+	/// index.
+	#[display("The palette has {num_colors} colors, but at most 256 are supported")]
+	TooManyColors {
+		/// The number of colors in the palette.
+		num_colors: usize,
+	},
+	/// A pixel in the image doesn't match any color in the palette.
+	#[display("The color {color:?} at {position} is not in the palette")]
+	ColorNotInPalette {
+		/// The color of the pixel.
+		color: Srgba<u8>,
+		/// The position of the pixel in the image.
+		position: UVec2,
+	},
+}
+
+/// Makes all fully transparent colors compare as equal.
+fn normalize_transparent(color: [u8; 4]) -> [u8; 4] {
+	if color[3] == 0 { [0; 4] } else { color }
+}
@@ -0,0 +1,41 @@
+use glam::UVec2;
+use image::ImageBuffer;
+use palette::Srgba;
+
+use super::{PaletteIndexError, palette_indices};
+
+const PALETTE: [Srgba<u8>; 3] = [
+	Srgba::new(0, 0, 0, 0),
+	Srgba::new(255, 0, 0, 255),
+	Srgba::new(0, 0, 255, 255),
+];
+
+#[test]
+fn converts_colors_to_indices() {
+	let image = ImageBuffer::from_fn(3, 1, |x, _| match x {
+		0 => image::Rgba([0, 0, 255, 255]),
+		1 => image::Rgba([255, 0, 0, 255]),
+		// transparent pixels match regardless of color
+		_ => image::Rgba([12, 34, 56, 0]),
+	});
+	let indices = palette_indices(&image, &PALETTE).unwrap();
+	assert_eq!(indices.as_raw(), &[2, 1, 0]);
+}
+
+#[test]
+fn reports_colors_not_in_palette() {
+	let image = ImageBuffer::from_fn(2, 2, |x, y| {
+		if (x, y) == (1, 1) {
+			image::Rgba([0, 255, 0, 255])
+		} else {
+			image::Rgba([255, 0, 0, 255])
+		}
+	});
+	assert_eq!(
+		palette_indices(&image, &PALETTE),
+		Err(PaletteIndexError::ColorNotInPalette {
+			color: Srgba::new(0, 255, 0, 255),
+			position: UVec2::new(1, 1),
+		})
+	);
+}
@@ -0,0 +1,326 @@
+//! Types for packing many images into a few textures at runtime.
+
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+};
+
+use derive_more::{Display, Error, From};
+use etagere::{AllocId, AtlasAllocator, size2};
+use glam::{UVec2, uvec2};
+use image::{ImageError, RgbaImage};
+
+use crate::{
+	Context,
+	graphics::texture::{Texture, TextureSettings},
+	math::Rect,
+};
+
+/// Packs many images into one or more [`Texture`] pages.
+///
+/// Each image is stored under a name and gets an [`AtlasRegion`]
+/// describing where it is in the atlas. Regions never move once they're
+/// allocated, so they can be stored and used with [`Texture::region`] or
+/// [`SpriteBatch::add_region`](crate::graphics::sprite_batch::SpriteBatch::add_region)
+/// for as long as the image is in the atlas.
+#[derive(Debug)]
+pub struct TextureAtlas {
+	settings: TextureAtlasSettings,
+	pages: Vec<AtlasPage>,
+	regions: HashMap<String, AtlasEntry>,
+}
+
+impl TextureAtlas {
+	/// Creates a new empty [`TextureAtlas`].
+	pub fn new(settings: TextureAtlasSettings) -> Self {
+		Self {
+			settings,
+			pages: vec![],
+			regions: HashMap::new(),
+		}
+	}
+
+	/// Creates a new [`TextureAtlas`] containing every image in a
+	/// directory and its subdirectories.
+	///
+	/// Each image is named after its path relative to `dir`, without the
+	/// file extension and with `/` as the separator (for example,
+	/// `"enemies/slime"`).
+	pub fn from_dir(
+		ctx: &Context,
+		dir: impl AsRef<Path>,
+		settings: TextureAtlasSettings,
+	) -> Result<Self, LoadTextureAtlasError> {
+		let _span = tracy_client::span!();
+		let dir = dir.as_ref();
+		let mut paths = vec![];
+		collect_image_paths(dir, &mut paths)?;
+		paths.sort();
+		let mut atlas = Self::new(settings);
+		for path in paths {
+			let name = path
+				.strip_prefix(dir)
+				.expect("image path should be inside the atlas directory")
+				.with_extension("")
+				.components()
+				.map(|component| component.as_os_str().to_string_lossy())
+				.collect::<Vec<_>>()
+				.join("/");
+			atlas.add_file(ctx, name, &path)?;
+		}
+		Ok(atlas)
+	}
+
+	/// Returns the settings the atlas was created with.
+	pub fn settings(&self) -> &TextureAtlasSettings {
+		&self.settings
+	}
+
+	/// Returns the number of images in the atlas.
+	pub fn len(&self) -> usize {
+		self.regions.len()
+	}
+
+	/// Returns `true` if there are no images in the atlas.
+	pub fn is_empty(&self) -> bool {
+		self.regions.is_empty()
+	}
+
+	/// Returns the number of texture pages in the atlas.
+	pub fn num_pages(&self) -> usize {
+		self.pages.len()
+	}
+
+	/// Returns the texture for the page with the specified index.
+	pub fn page(&self, index: usize) -> Option<&Texture> {
+		self.pages.get(index).map(|page| &page.texture)
+	}
+
+	/// Returns an iterator over the names of all the images in the atlas.
+	pub fn names(&self) -> impl Iterator<Item = &str> {
+		self.regions.keys().map(String::as_str)
+	}
+
+	/// Returns the region of the image with the specified name.
+	pub fn region(&self, name: &str) -> Option<AtlasRegion> {
+		self.regions.get(name).map(|entry| entry.region)
+	}
+
+	/// Returns the page texture containing the image with the specified
+	/// name, cropped to that image's region.
+	pub fn texture(&self, name: &str) -> Option<Texture> {
+		let region = self.region(name)?;
+		Some(self.pages[region.page].texture.region(region.rect))
+	}
+
+	/// Adds an image to the atlas, replacing any existing image with the
+	/// same name.
+	pub fn add(
+		&mut self,
+		ctx: &Context,
+		name: impl Into<String>,
+		image: &RgbaImage,
+	) -> Result<AtlasRegion, ImageTooLarge> {
+		let _span = tracy_client::span!();
+		let name = name.into();
+		self.remove(&name);
+		let border = self.settings.padding + self.settings.extrusion;
+		let allocation_size = uvec2(image.width(), image.height()) + UVec2::splat(border * 2);
+		if allocation_size.x > self.settings.page_size.x
+			|| allocation_size.y > self.settings.page_size.y
+		{
+			return Err(ImageTooLarge);
+		}
+		let allocation_size = size2(
+			allocation_size.x.max(1) as i32,
+			allocation_size.y.max(1) as i32,
+		);
+		let (page_index, allocation) = match self
+			.pages
+			.iter_mut()
+			.enumerate()
+			.find_map(|(i, page)| page.allocator.allocate(allocation_size).map(|a| (i, a)))
+		{
+			Some(result) => result,
+			None => {
+				let mut page = AtlasPage::new(ctx, &self.settings);
+				let allocation = page
+					.allocator
+					.allocate(allocation_size)
+					.ok_or(ImageTooLarge)?;
+				self.pages.push(page);
+				(self.pages.len() - 1, allocation)
+			}
+		};
+		let allocation_top_left = uvec2(
+			allocation.rectangle.min.x as u32,
+			allocation.rectangle.min.y as u32,
+		);
+		let extrusion = self.settings.extrusion;
+		if image.width() > 0 && image.height() > 0 {
+			let extruded = extrude(image, extrusion);
+			self.pages[page_index].texture.replace(
+				ctx,
+				allocation_top_left + UVec2::splat(self.settings.padding),
+				&extruded,
+			);
+		}
+		let region = AtlasRegion {
+			page: page_index,
+			rect: Rect::new(
+				(allocation_top_left + UVec2::splat(border)).as_vec2(),
+				uvec2(image.width(), image.height()).as_vec2(),
+			),
+		};
+		self.regions.insert(
+			name,
+			AtlasEntry {
+				region,
+				allocation_id: allocation.id,
+			},
+		);
+		Ok(region)
+	}
+
+	/// Loads an image file and adds it to the atlas, replacing any existing
+	/// image with the same name.
+	pub fn add_file(
+		&mut self,
+		ctx: &Context,
+		name: impl Into<String>,
+		path: impl AsRef<Path>,
+	) -> Result<AtlasRegion, LoadTextureAtlasError> {
+		let image = image::ImageReader::open(path)?.decode()?.to_rgba8();
+		Ok(self.add(ctx, name, &image)?)
+	}
+
+	/// Removes the image with the specified name from the atlas, freeing
+	/// up its space for future images.
+	///
+	/// Returns the region the image used to occupy, if it existed.
+	pub fn remove(&mut self, name: &str) -> Option<AtlasRegion> {
+		let entry = self.regions.remove(name)?;
+		self.pages[entry.region.page]
+			.allocator
+			.deallocate(entry.allocation_id);
+		Some(entry.region)
+	}
+}
+
+/// Settings for a [`TextureAtlas`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serializing", serde(default))]
+pub struct TextureAtlasSettings {
+	/// The size of each texture page in pixels.
+	pub page_size: UVec2,
+	/// The number of transparent pixels to leave around each image.
+	pub padding: u32,
+	/// The number of times to repeat the edge pixels of each image
+	/// outward.
+	///
+	/// This prevents neighboring images from bleeding into each other when
+	/// drawing with linear filtering or at non-integer positions.
+	pub extrusion: u32,
+	/// Settings for the page textures.
+	pub texture_settings: TextureSettings,
+}
+
+impl Default for TextureAtlasSettings {
+	fn default() -> Self {
+		Self {
+			page_size: UVec2::splat(2048),
+			padding: 1,
+			extrusion: 1,
+			texture_settings: TextureSettings {
+				label: "Texture Atlas Page".into(),
+				..Default::default()
+			},
+		}
+	}
+}
+
+/// The location of an image in a [`TextureAtlas`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasRegion {
+	/// The index of the page texture the image is on.
+	pub page: usize,
+	/// The rectangle the image occupies in the page texture in pixels.
+	pub rect: Rect,
+}
+
+/// An error that occurs when trying to add an image to a [`TextureAtlas`]
+/// that is bigger than a single page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Display)]
+#[display("The image is too large to fit on a texture atlas page")]
+pub struct ImageTooLarge;
+
+/// An error that can occur when loading images into a [`TextureAtlas`].
+#[derive(Debug, Error, Display, From)]
+pub enum LoadTextureAtlasError {
+	/// An error reading a file.
+	IoError(std::io::Error),
+	/// An error interpreting the image data.
+	ImageError(ImageError),
+	/// The image is too large to fit on a page.
+	ImageTooLarge(ImageTooLarge),
+}
+
+struct AtlasPage {
+	allocator: AtlasAllocator,
+	texture: Texture,
+}
+
+impl std::fmt::Debug for AtlasPage {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("AtlasPage")
+			.field("texture", &self.texture)
+			.finish_non_exhaustive()
+	}
+}
+
+impl AtlasPage {
+	fn new(ctx: &Context, settings: &TextureAtlasSettings) -> Self {
+		Self {
+			allocator: AtlasAllocator::new(size2(
+				settings.page_size.x as i32,
+				settings.page_size.y as i32,
+			)),
+			texture: Texture::empty(ctx, settings.page_size, settings.texture_settings.clone()),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AtlasEntry {
+	region: AtlasRegion,
+	allocation_id: AllocId,
+}
+
+fn extrude(image: &RgbaImage, extrusion: u32) -> RgbaImage {
+	if extrusion == 0 {
+		return image.clone();
+	}
+	RgbaImage::from_fn(
+		image.width() + extrusion * 2,
+		image.height() + extrusion * 2,
+		|x, y| {
+			let source_x = x.saturating_sub(extrusion).min(image.width() - 1);
+			let source_y = y.saturating_sub(extrusion).min(image.height() - 1);
+			*image.get_pixel(source_x, source_y)
+		},
+	)
+}
+
+fn collect_image_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+	for entry in std::fs::read_dir(dir)? {
+		let path = entry?.path();
+		if path.is_dir() {
+			collect_image_paths(&path, paths)?;
+		} else if image::ImageFormat::from_path(&path).is_ok_and(|format| format.reading_enabled())
+		{
+			paths.push(path);
+		}
+	}
+	Ok(())
+}
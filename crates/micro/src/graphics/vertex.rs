@@ -1,7 +1,7 @@
 pub use wgpu::{VertexAttribute, vertex_attr_array};
 
 use bytemuck::{Pod, Zeroable};
-use glam::Vec2;
+use glam::{Vec2, Vec3};
 use palette::LinSrgba;
 
 /// A trait for types that can be used as [`Mesh`](crate::graphics::mesh::Mesh)
@@ -33,3 +33,32 @@ impl HasVertexAttributes for Vertex2d {
 		vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4].into()
 	}
 }
+
+/// A vertex type for 3D meshes that can be lit using
+/// [`lighting`](crate::graphics::lighting).
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Vertex3d {
+	/// The coordinates of the vertex.
+	pub position: Vec3,
+	/// The surface normal at this vertex.
+	pub normal: Vec3,
+	/// The texture coordinates at this vertex.
+	pub texture_coords: Vec2,
+	/// The blend color of the vertex.
+	pub color: LinSrgba,
+}
+
+impl Vertex for Vertex3d {}
+
+impl HasVertexAttributes for Vertex3d {
+	fn attributes() -> Vec<VertexAttribute> {
+		vertex_attr_array![
+			0 => Float32x3,
+			1 => Float32x3,
+			2 => Float32x2,
+			3 => Float32x4,
+		]
+		.into()
+	}
+}
@@ -4,6 +4,7 @@ use super::{Circle, LineSegment, Rect};
 
 /// A 2D polygon.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serializing", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polygon {
 	/// The points that make up the polygon.
 	pub points: Vec<Vec2>,
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod test;
+
 use glam::{UVec2, uvec2};
 
 use super::{IRect, Rect};
@@ -144,6 +147,23 @@ impl URect {
 		Self::from_corners(top_left, bottom_right)
 	}
 
+	/// Returns the region where this rectangle and the specified other
+	/// rectangle overlap, or `None` if they don't overlap.
+	pub fn intersection(self, other: Self) -> Option<Self> {
+		if !self.overlaps(other) {
+			return None;
+		}
+		let top_left = UVec2::new(
+			self.top_left.x.max(other.top_left.x),
+			self.top_left.y.max(other.top_left.y),
+		);
+		let bottom_right = UVec2::new(
+			self.bottom_right().x.min(other.bottom_right().x),
+			self.bottom_right().y.min(other.bottom_right().y),
+		);
+		Some(Self::from_corners(top_left, bottom_right))
+	}
+
 	/// Returns `true` if a point lies within (or on the edge of) this rectangle.
 	pub fn contains_point(self, point: impl Into<UVec2>) -> bool {
 		let point = point.into();
@@ -0,0 +1,41 @@
+use crate::math::URect;
+
+#[test]
+fn intersection_of_overlapping_rects() {
+	assert_eq!(
+		URect::new((0, 0), (100, 100)).intersection(URect::new((50, 25), (100, 100))),
+		Some(URect::from_corners((50, 25), (100, 100)))
+	)
+}
+
+#[test]
+fn intersection_of_touching_rects() {
+	assert_eq!(
+		URect::new((0, 0), (100, 100)).intersection(URect::new((100, 0), (100, 100))),
+		None
+	);
+	assert_eq!(
+		URect::new((0, 0), (100, 100)).intersection(URect::new((0, 100), (100, 100))),
+		None
+	)
+}
+
+#[test]
+fn intersection_of_contained_rects() {
+	assert_eq!(
+		URect::new((0, 0), (100, 100)).intersection(URect::new((25, 25), (50, 50))),
+		Some(URect::new((25, 25), (50, 50)))
+	);
+	assert_eq!(
+		URect::new((25, 25), (50, 50)).intersection(URect::new((0, 0), (100, 100))),
+		Some(URect::new((25, 25), (50, 50)))
+	)
+}
+
+#[test]
+fn intersection_of_disjoint_rects() {
+	assert_eq!(
+		URect::new((0, 0), (100, 100)).intersection(URect::new((200, 200), (100, 100))),
+		None
+	)
+}